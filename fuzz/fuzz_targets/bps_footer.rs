@@ -0,0 +1,15 @@
+//! Données arbitraires (patch téléchargé, tronqué ou malveillant) données au décodage du footer et
+//! de l'en-tête BPS : une entrée invalide doit donner une erreur, jamais une panique.
+//! Lancement : cargo +nightly fuzz run bps_footer (depuis la racine du dépôt).
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+#[path = "../../src/bps.rs"]
+mod bps;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = bps::parse_bps_footer(data);
+    let _ = bps::parse_bps_sizes(data);
+});
@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::messages;
+use crate::selection::Selection;
+
+/// Formats d'archive pris en charge pour distribuer le patch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    #[serde(rename = "7z")]
+    SevenZ,
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveFormat::Zip => write!(f, "ZIP"),
+            ArchiveFormat::TarGz => write!(f, "tar.gz"),
+            ArchiveFormat::SevenZ => write!(f, "7z"),
+        }
+    }
+}
+
+/// Reconnaît le format d'après la signature en tête du fichier.
+pub fn detect_format(archive_path: &Path) -> io::Result<Option<ArchiveFormat>> {
+    let mut magic = [0u8; 6];
+    let mut file = File::open(archive_path)?;
+    let mut read = 0;
+    while read < magic.len() {
+        match file.read(&mut magic[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    let magic = &magic[..read];
+    Ok(if magic.starts_with(b"PK\x03\x04") || magic.starts_with(b"PK\x05\x06") {
+        Some(ArchiveFormat::Zip)
+    } else if magic.starts_with(&[0x1F, 0x8B]) {
+        Some(ArchiveFormat::TarGz)
+    } else if magic.starts_with(&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C]) {
+        Some(ArchiveFormat::SevenZ)
+    } else {
+        None
+    })
+}
+
+/// Format de l'archive téléchargée : la signature fait foi, le type déclaré par l'index
+/// ne sert que si elle n'est pas reconnue.
+pub fn resolve_format(archive_path: &Path, declared: Option<ArchiveFormat>) -> Result<ArchiveFormat, Box<dyn Error>> {
+    match (detect_format(archive_path)?, declared) {
+        (Some(detected), Some(declared)) if detected != declared => {
+            eprintln!("{}", messages::archive_format_mismatch(&declared, &detected));
+            Ok(detected)
+        }
+        (Some(detected), _) => Ok(detected),
+        (None, Some(declared)) => Ok(declared),
+        (None, None) => Err(messages::archive_format_unknown(archive_path).into()),
+    }
+}
+
+// Chemin relatif sans « .. » ni racine : l'entrée ne peut pas sortir du répertoire d'extraction.
+pub(crate) fn enclosed_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    let mut enclosed = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => enclosed.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!enclosed.as_os_str().is_empty()).then_some(enclosed)
+}
+
+// Caractères qu'un nom de fichier ne peut pas contenir sous Windows, en plus des caractères de contrôle.
+const WINDOWS_FORBIDDEN_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+// Noms de périphériques réservés par Windows, quelle que soit la casse et même suivis d'une extension (nul.txt).
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+fn windows_reserved_stem(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    WINDOWS_RESERVED_NAMES.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+/// Raison pour laquelle ce chemin relatif ne peut pas être créé sous Windows, ou None s'il est portable.
+/// Un patch construit sous Linux peut contenir de tels noms sans que rien ne le signale.
+pub(crate) fn windows_name_problem(relative_path: &Path) -> Option<String> {
+    for component in relative_path.components() {
+        let Component::Normal(part) = component else { continue };
+        let name = part.to_string_lossy();
+        if let Some(c) = name.chars().find(|c| WINDOWS_FORBIDDEN_CHARS.contains(c) || c.is_control()) {
+            return Some(messages::windows_forbidden_char(&name, c));
+        }
+        if windows_reserved_stem(&name) {
+            return Some(messages::windows_reserved_name(&name));
+        }
+        if name.ends_with('.') || name.ends_with(' ') {
+            return Some(messages::windows_trailing_dot(&name));
+        }
+    }
+    None
+}
+
+/// Équivalent portable d'un chemin refusé par Windows, à proposer aux auteurs du patch :
+/// caractères interdits remplacés par « _ », « _ » ajouté aux noms réservés, points et espaces finaux retirés.
+pub(crate) fn windows_safe_path(relative_path: &Path) -> PathBuf {
+    relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .map(|name| {
+            let mut safe: String = name
+                .chars()
+                .map(|c| if WINDOWS_FORBIDDEN_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+                .collect();
+            safe.truncate(safe.trim_end_matches(['.', ' ']).len());
+            if safe.is_empty() {
+                safe.push('_');
+            }
+            if windows_reserved_stem(&safe) {
+                safe = match safe.split_once('.') {
+                    Some((stem, extension)) => format!("{}_.{}", stem, extension),
+                    None => format!("{}_", safe),
+                };
+            }
+            safe
+        })
+        .collect()
+}
+
+// Sous Windows, un nom invalide ferait échouer l'extraction avec une erreur d'E/S peu parlante :
+// on l'explique avant. Ailleurs, l'extraction fonctionne, mais le patch n'est pas portable.
+fn check_windows_name(relative_path: &Path) -> Result<(), Box<dyn Error>> {
+    let Some(problem) = windows_name_problem(relative_path) else {
+        return Ok(());
+    };
+    let suggestion = windows_safe_path(relative_path);
+    if cfg!(windows) {
+        return Err(messages::windows_name_refused(relative_path, &problem, &suggestion).into());
+    }
+    eprintln!("{}", messages::windows_name_warning(relative_path, &problem, &suggestion));
+    Ok(())
+}
+
+/// Refuse une archive dont deux entrées ne diffèrent que par la casse (Data.win / data.win) :
+/// sur un système de fichiers insensible à la casse, l'une écraserait l'autre selon l'ordre d'extraction.
+fn check_case_collisions<'a>(names: impl Iterator<Item = &'a str>) -> Result<(), Box<dyn Error>> {
+    let mut by_lowercase: HashMap<String, Vec<&str>> = HashMap::new();
+    for name in names {
+        by_lowercase.entry(name.trim_end_matches('/').to_lowercase()).or_default().push(name);
+    }
+
+    let mut collisions: Vec<String> = by_lowercase
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|mut names| {
+            names.sort();
+            names.join(" / ")
+        })
+        .collect();
+    if collisions.is_empty() {
+        return Ok(());
+    }
+    collisions.sort();
+    Err(messages::archive_case_collisions(&collisions.join("\n  ")).into())
+}
+
+/// Taille totale des fichiers de l'archive une fois extraits, quand le format permet de la lire
+/// sans tout décompresser (ZIP et 7z ; pas tar.gz, dont l'en-tête de chaque fichier est compressé).
+pub fn uncompressed_size(archive_path: &Path, format: ArchiveFormat) -> Result<Option<u64>, Box<dyn Error>> {
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip::ZipArchive::new(File::open(archive_path)?)?;
+            let mut total = 0;
+            for i in 0..archive.len() {
+                total += archive.by_index(i)?.size();
+            }
+            Ok(Some(total))
+        }
+        ArchiveFormat::SevenZ => {
+            let reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())?;
+            Ok(Some(reader.archive().files.iter().map(|entry| entry.size()).sum()))
+        }
+        ArchiveFormat::TarGz => Ok(None),
+    }
+}
+
+// Écrit une entrée retenue dans le répertoire d'extraction.
+fn write_entry(target_dir: &Path, relative_path: &Path, reader: &mut dyn Read, mode: Option<u32>) -> Result<(), Box<dyn Error>> {
+    check_windows_name(relative_path)?;
+    let output_path = target_dir.join(relative_path);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut output_file = File::create(&output_path)?;
+    io::copy(reader, &mut output_file)
+        .map_err(|e| messages::archive_entry_failed(relative_path, &e))?;
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&output_path, fs::Permissions::from_mode(mode))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    Ok(())
+}
+
+fn dangerous_path(name: &str) -> Box<dyn Error> {
+    messages::archive_dangerous_path(name).into()
+}
+
+fn extract_zip(archive_path: &Path, target_dir: &Path, keep: &dyn Fn(&Path) -> bool) -> Result<usize, Box<dyn Error>> {
+    let mut archive = zip::ZipArchive::new(File::open(archive_path)?)?;
+    check_case_collisions(archive.file_names())?;
+
+    let mut skipped = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative_path) = entry.enclosed_name() else {
+            return Err(dangerous_path(entry.name()));
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        if !keep(&relative_path) {
+            skipped += 1;
+            continue;
+        }
+        let mode = entry.unix_mode();
+        write_entry(target_dir, &relative_path, &mut entry, mode)?;
+    }
+    Ok(skipped)
+}
+
+// Les noms d'un tar ne sont connus qu'au fil de la lecture : les collisions de casse
+// sont détectées pendant l'extraction.
+fn extract_tar_gz(archive_path: &Path, target_dir: &Path, keep: &dyn Fn(&Path) -> bool) -> Result<usize, Box<dyn Error>> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(File::open(archive_path)?));
+    let mut seen = HashMap::new();
+    let mut skipped = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let Some(relative_path) = enclosed_path(&name) else {
+            return Err(dangerous_path(&name));
+        };
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            continue;
+        }
+        if !entry_type.is_file() {
+            eprintln!("{}", messages::archive_not_regular_file(&name));
+            continue;
+        }
+        if let Some(other) = seen.insert(name.to_lowercase(), name.clone())
+            && other != name
+        {
+            check_case_collisions([other.as_str(), name.as_str()].into_iter())?;
+        }
+        if !keep(&relative_path) {
+            skipped += 1;
+            continue;
+        }
+        let mode = entry.header().mode().ok();
+        write_entry(target_dir, &relative_path, &mut entry, mode)?;
+    }
+    Ok(skipped)
+}
+
+fn extract_7z(archive_path: &Path, target_dir: &Path, keep: &dyn Fn(&Path) -> bool) -> Result<usize, Box<dyn Error>> {
+    let mut reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())?;
+    check_case_collisions(reader.archive().files.iter().map(|entry| entry.name()))?;
+    if let Some(entry) = reader.archive().files.iter().find(|entry| enclosed_path(entry.name()).is_none()) {
+        return Err(dangerous_path(entry.name()));
+    }
+
+    let mut skipped = 0;
+    let mut failure = None;
+    reader.for_each_entries(|entry, data| {
+        if entry.is_directory() {
+            return Ok(true);
+        }
+        // Vérifié avant l'extraction.
+        let relative_path = enclosed_path(entry.name()).unwrap_or_default();
+        if !keep(&relative_path) {
+            skipped += 1;
+            // Dans une archive « solide », le flux doit être lu pour atteindre l'entrée suivante.
+            io::copy(data, &mut io::sink())?;
+            return Ok(true);
+        }
+        if let Err(e) = write_entry(target_dir, &relative_path, data, None) {
+            failure = Some(e);
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(skipped),
+    }
+}
+
+/// Extrait de l'archive les seules entrées retenues par `selection` : les patchs de `patch_paths`,
+/// les métadonnées et les fichiers supplémentaires non exclus.
+pub fn extract_archive(
+    archive_path: &Path,
+    format: ArchiveFormat,
+    target_dir: &Path,
+    patch_paths: &HashSet<&str>,
+    selection: &Selection,
+) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::extracting(archive_path, &format, target_dir));
+    let keep = |relative_path: &Path| selection.keeps_entry(relative_path, patch_paths);
+    let skipped = match format {
+        ArchiveFormat::Zip => extract_zip(archive_path, target_dir, &keep)?,
+        ArchiveFormat::TarGz => extract_tar_gz(archive_path, target_dir, &keep)?,
+        ArchiveFormat::SevenZ => extract_7z(archive_path, target_dir, &keep)?,
+    };
+
+    if skipped > 0 {
+        println!("{}", messages::extract_skipped(skipped));
+    }
+    println!("{}", messages::extract_done());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_names_are_accepted() {
+        for name in ["chapter1_windows/data.win", "lang/console.txt", "COM10", "CONFIG.ini", "a.b.c"] {
+            assert_eq!(windows_name_problem(Path::new(name)), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn windows_names_get_safe_suggestions() {
+        // Le caractère « : » vient après un répertoire : en tête de chemin, Windows y lirait un lecteur.
+        for (name, safe) in [
+            ("CON", "CON_"),
+            ("lang/nul.txt", "lang/nul_.txt"),
+            ("Aux/data.win", "Aux_/data.win"),
+            ("lang/a:b", "lang/a_b"),
+            ("lang/a?b*.txt", "lang/a_b_.txt"),
+            ("lang/fin.", "lang/fin"),
+            ("lang/fin ", "lang/fin"),
+            ("lang/...", "lang/_"),
+            ("lang/a\u{1}b\tc", "lang/a_b_c"),
+        ] {
+            assert!(windows_name_problem(Path::new(name)).is_some(), "{:?}", name);
+            let suggestion = windows_safe_path(Path::new(name));
+            assert_eq!(suggestion, Path::new(safe), "{:?}", name);
+            assert_eq!(windows_name_problem(&suggestion), None, "{:?}", suggestion);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use crate::manifest::{self, ManifestEntry};
+use crate::{calculate_crc32_stream, messages, verify_backups};
+
+/// État d'un fichier installé par le patch, comparé au manifeste.
+pub enum FileState {
+    Intact,
+    Missing,
+    /// Le manifeste (antérieur à l'enregistrement des CRC) ne permet pas de conclure.
+    Unknown,
+    /// Revenu à sa version d'origine, celle de la sauvegarde : typiquement une mise à jour du jeu.
+    Reverted,
+    /// Ni la version installée, ni celle d'origine.
+    Corrupted(u32),
+}
+
+/// Compare un fichier du jeu au CRC32 enregistré par le manifeste, puis à celui de sa sauvegarde.
+pub fn file_state(game_dir: &Path, entry: &ManifestEntry) -> Result<FileState, Box<dyn Error>> {
+    let Some(expected) = entry.crc else {
+        return Ok(if game_dir.join(&entry.path).exists() { FileState::Unknown } else { FileState::Missing });
+    };
+    let Ok(file) = File::open(game_dir.join(&entry.path)) else {
+        return Ok(FileState::Missing);
+    };
+    let crc = calculate_crc32_stream(file)?;
+    if crc == expected {
+        return Ok(FileState::Intact);
+    }
+    let original_crc = entry
+        .backup
+        .as_ref()
+        .and_then(|backup| verify_backups::backup_crc(&game_dir.join(backup)).ok());
+    if original_crc == Some(crc) {
+        return Ok(FileState::Reverted);
+    }
+    Ok(FileState::Corrupted(crc))
+}
+
+/// Contrôle périodique d'une installation : chaque fichier du dernier manifeste doit avoir gardé
+/// le CRC32 enregistré à l'installation. Distingue un fichier revenu à sa version d'origine (mise à
+/// jour du jeu, réparable en réinstallant) d'un fichier corrompu (ni d'origine ni patché).
+pub fn run_audit(game_dir: &Path, state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let Some((manifest_path, install_manifest)) = manifest::latest_manifest(state_dir, game_dir)? else {
+        return Err(messages::audit_not_installed().into());
+    };
+    println!("{}", messages::audit_header());
+    println!("{}", messages::audit_manifest(&manifest_path, &install_manifest.platform));
+
+    let (mut intact, mut unknown, mut reverted, mut corrupted, mut missing) = (0, 0, 0, 0, 0);
+    for entry in &install_manifest.files {
+        match file_state(game_dir, entry)? {
+            FileState::Intact => intact += 1,
+            FileState::Unknown => {
+                println!("{}", messages::audit_unknown(&entry.path));
+                unknown += 1;
+            }
+            FileState::Missing => {
+                eprintln!("{}", messages::audit_missing(&entry.path));
+                missing += 1;
+            }
+            FileState::Reverted => {
+                eprintln!("{}", messages::audit_reverted(&entry.path));
+                reverted += 1;
+            }
+            FileState::Corrupted(crc) => {
+                eprintln!("{}", messages::audit_corrupted(&entry.path, crc, entry.crc.unwrap_or_default()));
+                corrupted += 1;
+            }
+        }
+    }
+
+    println!("{}", messages::audit_summary(intact, reverted, corrupted, missing, unknown));
+    if reverted + missing > 0 {
+        println!("{}", messages::audit_reverted_advice());
+    }
+    if corrupted > 0 {
+        println!("{}", messages::audit_corrupted_advice());
+    }
+    if reverted + corrupted + missing > 0 {
+        return Err(messages::audit_failed(reverted + corrupted + missing).into());
+    }
+    if unknown == 0 {
+        println!("{}", messages::audit_all_intact());
+    }
+    Ok(())
+}
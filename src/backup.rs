@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::calculate_crc32;
+use crate::messages;
+
+const BACKUP_SUFFIX: &str = ".bak";
+const COMPRESSED_SUFFIX: &str = ".zst";
+
+/// Chemin de la sauvegarde d'un fichier : « .bak » est ajouté au nom complet
+/// (data.win -> data.win.bak, DELTARUNE -> DELTARUNE.bak, .config -> .config.bak).
+pub fn backup_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(BACKUP_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// Chemin de la sauvegarde compressée d'un fichier (data.win -> data.win.bak.zst).
+pub fn compressed_backup_path_for(path: &Path) -> PathBuf {
+    let mut name = backup_path_for(path).into_os_string();
+    name.push(COMPRESSED_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Sauvegarde `original` par copie, à côté de lui : le fichier d'origine reste en place jusqu'à
+/// ce que sa nouvelle version le remplace. Même convention pour les fichiers patchés, les extras
+/// et les opérations post-installation, que la désinstallation restaure donc tous de la même façon.
+pub fn create_backup(original: &Path) -> std::io::Result<PathBuf> {
+    let backup_path = backup_path_for(original);
+    crate::fsutil::copy_file(original, &backup_path)?;
+    Ok(backup_path)
+}
+
+/// Sauvegarde `original` par un lien physique quand c'est possible : instantané, sans espace disque
+/// supplémentaire. Réservé aux fichiers que l'installation remplace ensuite par un renommage
+/// (écriture atomique) : jusqu'à ce renommage, le fichier et sa sauvegarde sont le même inode, et une
+/// écriture en place dans l'un modifierait l'autre ; après, seule la sauvegarde désigne encore la
+/// version d'origine. Un lien symbolique ou un fichier déjà lié ailleurs (partagé entre plusieurs
+/// installations) est copié, de même qu'en cas d'échec du lien (autre système de fichiers, FAT...).
+pub fn create_linked_backup(original: &Path) -> io::Result<PathBuf> {
+    let backup_path = backup_path_for(original);
+    let metadata = fs::symlink_metadata(original)?;
+    if metadata.is_file() && !is_shared(&metadata) {
+        match fs::remove_file(&backup_path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        match fs::hard_link(original, &backup_path) {
+            Ok(()) => {
+                tracing::info!("{:?} sauvegardé par lien physique.", original);
+                return Ok(backup_path);
+            }
+            Err(e) => tracing::info!("Lien physique impossible pour {:?} ({}) : copie.", original, e),
+        }
+    }
+    create_backup(original)
+}
+
+#[cfg(unix)]
+fn is_shared(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+// Le nombre de liens n'est pas accessible sans API instable ailleurs : par prudence, on copie.
+#[cfg(not(unix))]
+fn is_shared(_metadata: &fs::Metadata) -> bool {
+    true
+}
+
+/// Vrai pour une sauvegarde compressée (`.bak.zst`).
+pub fn is_compressed(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.ends_with(".bak.zst"))
+}
+
+/// Retrouve le fichier d'origine d'une sauvegarde, compressée ou non, en retirant le
+/// suffixe « .bak » (ou « .bak.zst ») final du nom.
+pub fn original_path_for(backup_path: &Path) -> Option<PathBuf> {
+    let name = backup_path.file_name()?.to_str()?;
+    let name = name.strip_suffix(COMPRESSED_SUFFIX).unwrap_or(name);
+    let original = name.strip_suffix(BACKUP_SUFFIX)?;
+    // Ancien nommage des fichiers sans extension : DELTARUNE..bak pour DELTARUNE.
+    let original = original.strip_suffix('.').unwrap_or(original);
+    if original.is_empty() {
+        return None;
+    }
+    Some(backup_path.with_file_name(original))
+}
+
+/// Sauvegarde `source` en zstd et renvoie le chemin de la sauvegarde et le CRC32 du contenu d'origine.
+pub fn create_compressed_backup(source: &Path) -> Result<(PathBuf, u32), Box<dyn Error>> {
+    let backup_path = compressed_backup_path_for(source);
+    let data = fs::read(source)?;
+    let crc = calculate_crc32(&data);
+
+    let output = File::create(&backup_path)?;
+    let mut encoder = zstd::stream::write::Encoder::new(output, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+    // Somme de contrôle zstd : détecte une sauvegarde abîmée même sans manifeste.
+    encoder.include_checksum(true)?;
+    encoder.write_all(&data)?;
+    encoder.finish()?;
+
+    let compressed_size = fs::metadata(&backup_path)?.len();
+    println!("{}", messages::backup_compressed_size(data.len(), compressed_size));
+    Ok((backup_path, crc))
+}
+
+/// Décompresse une sauvegarde `.bak.zst` vers `destination`.
+/// Le CRC32 du contenu décompressé est comparé à `expected_crc` avant toute écriture.
+pub fn decompress_backup(backup_path: &Path, destination: &Path, expected_crc: Option<u32>) -> Result<(), Box<dyn Error>> {
+    let data = zstd::stream::decode_all(File::open(backup_path)?)
+        .map_err(|e| messages::backup_unreadable(backup_path, &e))?;
+
+    let actual_crc = calculate_crc32(&data);
+    match expected_crc {
+        Some(expected) if expected != actual_crc => {
+            return Err(messages::backup_crc_mismatch(backup_path, actual_crc, expected).into());
+        }
+        Some(_) => println!("{}", messages::backup_crc_ok(actual_crc)),
+        None => println!("{}", messages::backup_crc_unknown(backup_path)),
+    }
+
+    // Écriture atomique : interrompue, elle laisse le fichier patché intact et la sauvegarde en place.
+    crate::fsutil::write_atomic(destination, &data)?;
+    Ok(())
+}
+
+/// Lit le contenu d'origine d'une sauvegarde, compressée ou non.
+pub fn read_backup(backup_path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    if is_compressed(backup_path) {
+        Ok(zstd::stream::decode_all(File::open(backup_path)?)
+            .map_err(|e| messages::backup_unreadable(backup_path, &e))?)
+    } else {
+        Ok(fs::read(backup_path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_names_round_trip() {
+        for (name, backup_name) in [
+            (".config", ".config.bak"),
+            ("fichier", "fichier.bak"),
+            ("a.b", "a.b.bak"),
+            (".a.b", ".a.b.bak"),
+        ] {
+            let original = Path::new("chapter1_windows").join(name);
+            let backup = backup_path_for(&original);
+            assert_eq!(backup, Path::new("chapter1_windows").join(backup_name));
+            assert_eq!(original_path_for(&backup), Some(original.clone()));
+
+            let compressed = compressed_backup_path_for(&original);
+            assert_eq!(compressed, Path::new("chapter1_windows").join(format!("{}.zst", backup_name)));
+            assert_eq!(original_path_for(&compressed), Some(original));
+        }
+    }
+
+    #[test]
+    fn legacy_backup_name_without_extension() {
+        assert_eq!(original_path_for(Path::new("DELTARUNE..bak")), Some(PathBuf::from("DELTARUNE")));
+    }
+
+    #[test]
+    fn not_a_backup() {
+        assert_eq!(original_path_for(Path::new("data.win")), None);
+        assert_eq!(original_path_for(Path::new(".bak")), None);
+    }
+}
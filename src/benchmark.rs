@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::{calculate_crc32, fsutil};
+
+const PHASES: [&str; 5] = ["Lecture du source", "Lecture du patch", "CRC32 du source", "Application (flips)", "Écriture atomique"];
+
+fn median(sorted: &[Duration]) -> Duration {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2
+    } else {
+        sorted[middle]
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Outil de développement (feature `benchmark`) : applique `patch` à `source` `iterations` fois
+/// et affiche la durée moyenne et médiane de chaque étape, pour distinguer le coût des
+/// entrées-sorties de celui du calcul. Le résultat est écrit dans le répertoire temporaire
+/// du système, puis supprimé : le fichier source n'est jamais modifié.
+pub fn run_benchmark(source: &Path, patch: &Path, iterations: u32) -> Result<(), Box<dyn Error>> {
+    let output_path = std::env::temp_dir().join(format!("patcher_drfr_benchmark_{}", std::process::id()));
+    let mut timings: Vec<Vec<Duration>> = vec![Vec::new(); PHASES.len()];
+
+    for iteration in 1..=iterations {
+        let mut phase = 0;
+        let mut measure = |start: Instant| {
+            timings[phase].push(start.elapsed());
+            phase += 1;
+        };
+
+        let start = Instant::now();
+        let source_data = fs::read(source).map_err(|e| format!("Impossible de lire {:?} : {}", source, e))?;
+        measure(start);
+
+        let start = Instant::now();
+        let patch_data = fs::read(patch).map_err(|e| format!("Impossible de lire {:?} : {}", patch, e))?;
+        measure(start);
+
+        let start = Instant::now();
+        let source_crc = calculate_crc32(&source_data);
+        measure(start);
+
+        let start = Instant::now();
+        let output = flips::BpsPatch::new(patch_data)
+            .apply(source_data)
+            .map_err(|e| format!("Erreur lors de l'application du patch BPS: {}", e))?;
+        let output_data = output.to_bytes();
+        measure(start);
+
+        let start = Instant::now();
+        let written = fsutil::write_atomic(&output_path, &output_data);
+        measure(start);
+        let _ = fs::remove_file(&output_path);
+        written?;
+
+        tracing::info!("Itération {}/{} terminée (CRC32 source {:#010X}).", iteration, iterations, source_crc);
+    }
+
+    println!("{} itération(s) de {:?} sur {:?} :\n", iterations, patch, source);
+    println!("{:<22} {:>12} {:>12}", "Étape", "Moyenne", "Médiane");
+    let mut total_mean = Duration::ZERO;
+    for (name, durations) in PHASES.iter().zip(&mut timings) {
+        durations.sort();
+        let mean = durations.iter().sum::<Duration>() / iterations;
+        total_mean += mean;
+        println!("{:<22} {:>9.1} ms {:>9.1} ms", name, millis(mean), millis(median(durations)));
+    }
+    println!("{:<22} {:>9.1} ms", "Total", millis(total_mean));
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// CRC32 stockés dans le footer d'un fichier BPS (les 12 derniers octets :
+/// CRC source, CRC cible puis CRC du patch lui-même).
+#[derive(Debug, Clone, Copy)]
+pub struct BpsFooter {
+    /// CRC32 du fichier auquel le patch s'applique.
+    pub source_crc: u32,
+    /// CRC32 du fichier produit par le patch.
+    pub target_crc: u32,
+}
+
+/// Taille du footer : CRC source, CRC cible et CRC du patch, 4 octets chacun.
+const FOOTER_SIZE: usize = 12;
+
+/// Lit le footer d'un patch BPS sans charger le patch en mémoire.
+pub fn read_bps_footer(patch_file_path: &Path) -> Result<BpsFooter, Box<dyn Error>> {
+    let mut f = File::open(patch_file_path)?;
+    f.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))
+        .map_err(|e| format!("Patch {:?} trop court pour être un BPS valide : {}", patch_file_path, e))?;
+
+    let mut buf = [0u8; FOOTER_SIZE];
+    f.read_exact(&mut buf)?;
+    parse_bps_footer(&buf)
+}
+
+/// Décode le footer à la fin de `data` (le patch entier ou seulement ses derniers octets).
+/// Toute entrée trop courte est une erreur, jamais une panique : voir la cible de fuzzing fuzz/.
+pub fn parse_bps_footer(data: &[u8]) -> Result<BpsFooter, Box<dyn Error>> {
+    let start = data
+        .len()
+        .checked_sub(FOOTER_SIZE)
+        .ok_or_else(|| format!("Patch trop court pour être un BPS valide ({} octets).", data.len()))?;
+    let crc_at = |offset: usize| {
+        let bytes = &data[start + offset..start + offset + 4];
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    };
+    Ok(BpsFooter { source_crc: crc_at(0), target_crc: crc_at(4) })
+}
+
+// Entier à longueur variable du format BPS (7 bits par octet, bit de poids fort = dernier octet).
+fn read_varint(reader: &mut impl Read) -> Result<u64, Box<dyn Error>> {
+    let mut value: u64 = 0;
+    let mut shift: u64 = 1;
+    loop {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        value = value
+            .checked_add(u64::from(byte[0] & 0x7f).checked_mul(shift).ok_or("Entier BPS trop grand")?)
+            .ok_or("Entier BPS trop grand")?;
+        if byte[0] & 0x80 != 0 {
+            return Ok(value);
+        }
+        // checked_shl ne détecte pas le dépassement (seulement un décalage de 64 bits ou plus) :
+        // le facteur finissait par valoir 0 et un entier trop long était accepté avec une valeur fausse.
+        shift = shift.checked_mul(0x80).ok_or("Entier BPS trop grand")?;
+        value = value.checked_add(shift).ok_or("Entier BPS trop grand")?;
+    }
+}
+
+/// Lit dans l'en-tête d'un patch BPS les tailles du fichier source et du fichier produit.
+pub fn read_bps_sizes(patch_file_path: &Path) -> Result<(u64, u64), Box<dyn Error>> {
+    let f = File::open(patch_file_path)?;
+    parse_bps_sizes(f).map_err(|e| format!("{:?} : {}", patch_file_path, e).into())
+}
+
+/// Décode l'en-tête d'un patch BPS : magique « BPS1 », puis les tailles source et cible.
+pub fn parse_bps_sizes(mut reader: impl Read) -> Result<(u64, u64), Box<dyn Error>> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"BPS1" {
+        return Err("ce n'est pas un patch BPS (en-tête invalide).".into());
+    }
+    let source_size = read_varint(&mut reader)?;
+    let target_size = read_varint(&mut reader)?;
+    Ok((source_size, target_size))
+}
+
+/// Lit la taille du fichier produit par un patch BPS dans son en-tête.
+pub fn read_bps_target_size(patch_file_path: &Path) -> Result<u64, Box<dyn Error>> {
+    read_bps_sizes(patch_file_path).map(|(_, target_size)| target_size)
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use crate::{calculate_crc32_stream, fetch_patch_index, messages, platform_info_for, select_platform};
+
+fn format_crc(crc: Option<u32>) -> String {
+    match crc {
+        Some(crc) => format!("{:#010X}", crc),
+        None => "?".to_string(),
+    }
+}
+
+/// Affiche, pour chaque patch de l'index, le CRC32 réel du fichier du jeu à côté des CRC32
+/// source et cible du patch (ceux du pied de page BPS, repris dans l'index), avec un verdict.
+/// Lecture seule : rien n'est modifié dans le répertoire du jeu.
+pub fn run_check_crc(game_dir: &Path, platform: Option<&str>, index_url: &str) -> Result<(), Box<dyn Error>> {
+    let patch_index = fetch_patch_index(index_url)?;
+    let platform_key = match platform {
+        Some(platform) => platform.to_string(),
+        None => select_platform(game_dir),
+    };
+    let platform_info = platform_info_for(&patch_index, &platform_key)?;
+
+    println!("{}", messages::check_crc_header(&platform_key));
+    let file_label = messages::check_crc_col_file();
+    let path_width = platform_info.patchs.iter().map(|d| d.source_path.len()).max().unwrap_or(0).max(file_label.len());
+    println!(
+        "{:<path_width$}  {:<10}  {:<10}  {:<10}  {}",
+        file_label, messages::check_crc_col_actual(), messages::check_crc_col_source(), messages::check_crc_col_target(),
+        messages::check_crc_col_verdict()
+    );
+
+    let mut unknown = 0;
+    for detail in &platform_info.patchs {
+        let source_file_path = game_dir.join(&detail.source_path);
+        let actual = match File::open(&source_file_path) {
+            Ok(file) => Some(calculate_crc32_stream(file)
+                .map_err(|e| messages::read_error(&source_file_path, &e))?),
+            Err(_) => None,
+        };
+        let verdict = match (actual, detail.source_crc, detail.target_crc) {
+            (None, _, _) => messages::check_crc_not_found(),
+            (Some(crc), Some(source), _) if crc == source => messages::check_crc_patchable(),
+            (Some(crc), _, Some(target)) if crc == target => messages::check_crc_patched(),
+            (Some(_), None, None) => {
+                unknown += 1;
+                messages::check_crc_unknown_no_crc()
+            }
+            (Some(_), _, _) => {
+                unknown += 1;
+                messages::check_crc_unknown()
+            }
+        };
+        println!(
+            "{:<path_width$}  {:<10}  {:<10}  {:<10}  {}",
+            detail.source_path, format_crc(actual), format_crc(detail.source_crc), format_crc(detail.target_crc), verdict
+        );
+    }
+
+    if unknown > 0 {
+        println!("{}", messages::check_crc_unknown_summary(unknown));
+    }
+    Ok(())
+}
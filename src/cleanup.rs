@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+use crate::{confirm, format_size, fsutil, messages};
+
+/// Âge à partir duquel un `.tmp` est orphelin : aucune écriture du patcher ne dure aussi longtemps.
+const TEMP_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Âge au-delà duquel un `.part` n'est plus repris : l'archive publiée a pu changer entre-temps.
+const PARTIAL_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrphanKind {
+    /// Écriture atomique interrompue (`.tmp`).
+    Temp,
+    /// Téléchargement interrompu (`.part`).
+    Partial,
+}
+
+/// Fichier temporaire laissé par une exécution interrompue (plantage, coupure, Ctrl+C).
+#[derive(Debug)]
+pub struct Orphan {
+    pub path: PathBuf,
+    pub kind: OrphanKind,
+    pub size: u64,
+    pub age: Duration,
+}
+
+impl Orphan {
+    /// Vrai pour un téléchargement que la prochaine installation reprendra au lieu de le recommencer.
+    pub fn is_resumable(&self) -> bool {
+        self.kind == OrphanKind::Partial && self.age < PARTIAL_MAX_AGE
+    }
+
+    // Assez ancien pour ne plus appartenir à une exécution en cours ni mériter d'être repris.
+    fn is_stale(&self) -> bool {
+        match self.kind {
+            OrphanKind::Temp => self.age >= TEMP_MIN_AGE,
+            OrphanKind::Partial => self.age >= PARTIAL_MAX_AGE,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let hours = self.age.as_secs() / 3600;
+        let age = if hours >= 48 { messages::orphan_age_days(hours / 24) } else { messages::orphan_age_hours(hours) };
+        let note = match self.kind {
+            OrphanKind::Temp => messages::orphan_temp(),
+            OrphanKind::Partial if self.is_resumable() => messages::orphan_partial_resumable(),
+            OrphanKind::Partial => messages::orphan_partial_stale(),
+        };
+        messages::orphan_description(&self.path, &format_size(self.size), &age, &note)
+    }
+}
+
+fn orphan_kind(path: &Path) -> Option<OrphanKind> {
+    let name = path.file_name()?.to_str()?;
+    if name.ends_with(fsutil::TEMP_SUFFIX) {
+        Some(OrphanKind::Temp)
+    } else if name.ends_with(fsutil::PARTIAL_SUFFIX) {
+        Some(OrphanKind::Partial)
+    } else {
+        None
+    }
+}
+
+/// Cherche les fichiers `.tmp` et `.part` dans ces répertoires et leurs sous-répertoires.
+/// Un répertoire absent est ignoré.
+pub fn find_orphans(dirs: &[&Path]) -> Vec<Orphan> {
+    let now = SystemTime::now();
+    let mut orphans = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(kind) = orphan_kind(entry.path()) else { continue };
+            let Ok(metadata) = entry.metadata() else { continue };
+            let age = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            orphans.push(Orphan { path: entry.into_path(), kind, size: metadata.len(), age });
+        }
+    }
+    orphans
+}
+
+/// Nettoyage léger au démarrage des commandes qui modifient le jeu : les fichiers temporaires
+/// assez anciens pour ne plus servir sont supprimés, les téléchargements qui seront repris sont signalés.
+/// Un échec n'interrompt pas la commande.
+pub fn clean_stale(dirs: &[&Path]) {
+    for orphan in find_orphans(dirs) {
+        if orphan.is_stale() {
+            match fs::remove_file(&orphan.path) {
+                Ok(()) => println!("{}", messages::orphan_removed(&orphan.describe())),
+                Err(e) => eprintln!("{}", messages::orphan_remove_failed(&orphan.path, &e)),
+            }
+        } else if orphan.is_resumable() {
+            println!("{}", messages::orphan_resumable_found(&orphan.describe()));
+        } else {
+            tracing::debug!("Fichier temporaire récent conservé : {}", orphan.describe());
+        }
+    }
+}
+
+/// Liste les fichiers temporaires orphelins du jeu et du répertoire de téléchargement, puis les
+/// supprime après confirmation. Les téléchargements encore repris font l'objet d'une question à part.
+pub fn run_clean_temp(dirs: &[&Path], list_only: bool, assume_yes: bool) -> Result<(), Box<dyn Error>> {
+    let orphans = find_orphans(dirs);
+    if orphans.is_empty() {
+        println!("{}", messages::no_orphans());
+        return Ok(());
+    }
+
+    let (resumable, others): (Vec<Orphan>, Vec<Orphan>) = orphans.into_iter().partition(Orphan::is_resumable);
+    println!("{}", messages::orphans_found());
+    for orphan in others.iter().chain(&resumable) {
+        println!("  {}", orphan.describe());
+    }
+    if list_only {
+        return Ok(());
+    }
+
+    let mut to_remove = Vec::new();
+    if !others.is_empty() && (assume_yes || confirm(&messages::orphans_remove_prompt(others.len()))?) {
+        to_remove.extend(others);
+    }
+    if !resumable.is_empty() && (assume_yes || confirm(&messages::orphans_remove_resumable_prompt(resumable.len()))?) {
+        to_remove.extend(resumable);
+    }
+
+    let mut freed = 0;
+    let mut error_count = 0;
+    for orphan in &to_remove {
+        match fs::remove_file(&orphan.path) {
+            Ok(()) => freed += orphan.size,
+            Err(e) => {
+                eprintln!("{}", messages::remove_failed(&orphan.path, &e));
+                error_count += 1;
+            }
+        }
+    }
+    if error_count > 0 {
+        return Err(messages::orphans_remove_errors(error_count).into());
+    }
+    println!("{}", messages::orphans_removed(to_remove.len(), &format_size(freed)));
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+
+use crate::messages;
+
+/// Configuration utilisateur, lue dans un fichier TOML. Exemple :
+///
+/// ```toml
+/// [aliases]
+/// steam = "/home/moi/.local/share/Steam/steamapps/common/DELTARUNE"
+/// gog = "/opt/GOG Games/DELTARUNE"
+///
+/// [network]
+/// max_idle_connections = 8
+///
+/// [index]
+/// url = "https://miroir.example.org/patch-files/linux/patch_index.json"
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Raccourcis de répertoire du jeu, utilisables avec --game-dir @nom.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, PathBuf>,
+
+    #[serde(default)]
+    pub network: NetworkConfig,
+
+    #[serde(default)]
+    pub index: IndexConfig,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Réglages réseau, tous facultatifs.
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Connexions inactives gardées ouvertes par serveur pour être réutilisées (4 par défaut, 0 pour aucune).
+    pub max_idle_connections: Option<usize>,
+}
+
+/// Index des patchs.
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct IndexConfig {
+    /// Index à utiliser à la place de l'index officiel (miroir, serveur de test), comme --index-url.
+    pub url: Option<String>,
+}
+
+/// ~/.config/patcher_drfr/config.toml (ou $XDG_CONFIG_HOME/patcher_drfr/config.toml).
+pub fn default_config_path() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("patcher_drfr").join("config.toml")
+}
+
+/// Charge la configuration. Sans `path`, le fichier par défaut est facultatif ;
+/// un fichier donné explicitement (--config) doit exister.
+pub fn load(path: Option<&Path>) -> Result<Config, Box<dyn Error>> {
+    let config_path = path.map(Path::to_path_buf).unwrap_or_else(default_config_path);
+    let content = match fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && path.is_none() => {
+            return Ok(Config { path: config_path, ..Default::default() });
+        }
+        Err(e) => return Err(messages::config_unreadable(&config_path, &e).into()),
+    };
+    let mut config: Config = toml::from_str(&content)
+        .map_err(|e| messages::config_invalid(&config_path, &e))?;
+    config.path = config_path;
+    Ok(config)
+}
+
+impl Config {
+    /// Remplace un répertoire de la forme `@nom` par le chemin de l'alias correspondant.
+    /// Les autres chemins sont renvoyés tels quels.
+    pub fn resolve_game_dir(&self, game_dir: PathBuf) -> Result<PathBuf, Box<dyn Error>> {
+        let Some(name) = game_dir.to_str().and_then(|s| s.strip_prefix('@')) else {
+            return Ok(game_dir);
+        };
+        match self.aliases.get(name) {
+            Some(path) => {
+                println!("{}", messages::alias_target(name, path));
+                Ok(path.clone())
+            }
+            None if self.aliases.is_empty() => Err(messages::alias_unknown_none_defined(name, &self.path).into()),
+            None => Err(messages::alias_unknown(
+                name,
+                &self.path,
+                &self.aliases.keys().map(|alias| format!("@{}", alias)).collect::<Vec<_>>().join(", ")
+            ).into()),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Accepte un octet en décimal (255) ou en hexadécimal (0xFF).
+pub fn parse_byte(value: &str) -> Result<u8, String> {
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("octet invalide : '{}' (attendu 0-255 ou 0x00-0xFF)", value))
+}
+
+/// Outil de test (feature `testing`) : remplace l'octet à `offset` de `file` par `byte`,
+/// pour vérifier que les contrôles CRC du patcher refusent bien un fichier corrompu.
+pub fn run_corrupt_test(file: &Path, offset: u64, byte: u8) -> Result<(), Box<dyn Error>> {
+    let mut handle = OpenOptions::new().read(true).write(true).open(file)
+        .map_err(|e| format!("Impossible d'ouvrir {:?} : {}", file, e))?;
+    let len = handle.metadata()?.len();
+    if offset >= len {
+        return Err(format!("Position {} hors du fichier {:?} ({} octets).", offset, file, len).into());
+    }
+
+    let mut previous = [0u8];
+    handle.seek(SeekFrom::Start(offset))?;
+    handle.read_exact(&mut previous)?;
+    handle.seek(SeekFrom::Start(offset))?;
+    handle.write_all(&[byte])?;
+    handle.sync_all()?;
+
+    println!("{:?} : octet {} modifié ({:#04X} -> {:#04X}).", file, offset, previous[0], byte);
+    if previous[0] == byte {
+        println!("ATTENTION : L'octet avait déjà cette valeur, le fichier n'est pas corrompu.");
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::messages;
+
+/// Code de sortie quand --deadline est dépassé (même convention que la commande `timeout`).
+pub const EXIT_CODE: i32 = 124;
+
+/// Limite de durée de toute l'opération, vérifiée entre les phases et entre les fichiers.
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    end: Option<Instant>,
+    seconds: u64,
+}
+
+/// L'opération a dépassé sa durée maximale (--deadline).
+#[derive(Debug)]
+pub struct DeadlineExceeded {
+    pub seconds: u64,
+}
+
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", messages::deadline_exceeded(self.seconds))
+    }
+}
+
+impl Error for DeadlineExceeded {}
+
+impl Deadline {
+    pub fn new(seconds: Option<u64>) -> Self {
+        Deadline {
+            end: seconds.map(|s| Instant::now() + Duration::from_secs(s)),
+            seconds: seconds.unwrap_or(0),
+        }
+    }
+
+    /// Temps restant, ou `None` sans limite.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.end.map(|end| end.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn expired(&self) -> bool {
+        self.remaining().is_some_and(|remaining| remaining.is_zero())
+    }
+
+    pub fn check(&self) -> Result<(), DeadlineExceeded> {
+        if self.expired() {
+            return Err(DeadlineExceeded { seconds: self.seconds });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use walkdir::WalkDir;
+
+use crate::{backup, check_game_dir, check_is_deltarune, download_dir_for, ensure_writable, format_size, fsutil, manifest, messages, net, process, select_platform};
+
+/// Espace conseillé dans le répertoire temporaire : l'archive du patch et son extraction.
+const TEMP_SPACE_HINT: u64 = 1024 * 1024 * 1024;
+
+/// Résultat des vérifications : les erreurs empêchent l'installation, les avertissements non.
+#[derive(Default)]
+struct Report {
+    errors: usize,
+    warnings: usize,
+}
+
+impl Report {
+    fn ok(&self, message: &str) {
+        println!("{}", messages::doctor_ok(message));
+    }
+
+    fn warning(&mut self, message: &str, advice: &str) {
+        eprintln!("{}", messages::doctor_warning(message, advice));
+        self.warnings += 1;
+    }
+
+    fn error(&mut self, message: &str, advice: &str) {
+        eprintln!("{}", messages::doctor_error(message, advice));
+        self.errors += 1;
+    }
+}
+
+/// Taille des fichiers de données du jeu (data.win de chaque chapitre) : c'est l'espace que
+/// prennent les sauvegardes à l'installation, les fichiers patchés ayant une taille voisine.
+fn game_data_size(game_dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(game_dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| fs::metadata(entry.path().join("data.win")).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn check_network(report: &mut Report, index_url: &str) {
+    match net::head(index_url) {
+        Ok(_) => report.ok(&messages::doctor_network_ok(index_url)),
+        Err(e) => report.error(&messages::doctor_network_error(&e), &messages::doctor_network_advice()),
+    }
+}
+
+fn check_game_files(report: &mut Report, game_dir: &Path) -> bool {
+    if let Err(e) = check_game_dir(game_dir) {
+        report.error(&e.to_string(), &messages::doctor_game_dir_advice());
+        return false;
+    }
+    let platform_key = select_platform(game_dir);
+    match check_is_deltarune(game_dir, &platform_key) {
+        Ok(true) => report.ok(&messages::doctor_game_files_ok(&platform_key)),
+        // L'avertissement et sa raison viennent d'être affichés.
+        Ok(false) => report.warnings += 1,
+        Err(e) => report.error(&e.to_string(), &messages::doctor_game_files_advice()),
+    }
+    true
+}
+
+fn check_writable(report: &mut Report, label: &str, dir: &Path) {
+    let result = match fs::create_dir_all(dir) {
+        Ok(()) => ensure_writable(dir),
+        Err(e) => Err(messages::doctor_cannot_create(dir, &e).into()),
+    };
+    match result {
+        Ok(()) => report.ok(&messages::doctor_writable(label, dir)),
+        Err(e) => report.error(&e.to_string(), &messages::doctor_writable_advice()),
+    }
+}
+
+fn check_space(report: &mut Report, label: &str, dir: &Path, needed: u64, advice: &str) {
+    let Some((mount, available)) = fsutil::volume_of(dir) else {
+        println!("{}", messages::doctor_space_unknown(label, dir));
+        return;
+    };
+    if available < needed {
+        report.warning(&messages::doctor_space_low(label, &format_size(available), &mount, &format_size(needed)), advice);
+    } else {
+        report.ok(&messages::doctor_space_ok(label, &format_size(available), &mount));
+    }
+}
+
+fn check_game_not_running(report: &mut Report, game_dir: &Path) {
+    let running = process::find_running_game(game_dir);
+    if running.is_empty() {
+        report.ok(&messages::doctor_game_not_running());
+    } else {
+        report.warning(&messages::doctor_game_running(&process::describe(&running)), &messages::doctor_game_running_advice());
+    }
+}
+
+/// Les sauvegardes présentes doivent correspondre à une installation enregistrée : sinon, elles
+/// viennent d'une autre copie du patcher ou d'un autre état (--state-dir) et uninstall les ignorera.
+fn check_backups(report: &mut Report, game_dir: &Path, state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let backups: Vec<_> = WalkDir::new(game_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && backup::original_path_for(entry.path()).is_some())
+        .map(|entry| entry.into_path())
+        .collect();
+    let installed = manifest::latest_manifest(state_dir, game_dir)?;
+
+    if manifest::load_pending(state_dir, game_dir).is_some() {
+        report.warning(&messages::doctor_pending_install(), &messages::doctor_pending_install_advice());
+    }
+    match (backups.is_empty(), &installed) {
+        (true, None) => report.ok(&messages::doctor_original_game()),
+        (true, Some(_)) => report.warning(&messages::doctor_backups_missing(), &messages::doctor_backups_missing_advice()),
+        (false, Some((_, install_manifest))) => report.ok(&messages::doctor_backups_ok(backups.len(), &install_manifest.platform)),
+        (false, None) => {
+            report.warning(&messages::doctor_backups_unknown(backups.len()), &messages::doctor_backups_unknown_advice());
+            for path in &backups {
+                eprintln!("  {:?}", path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Diagnostic de l'environnement, à joindre à une demande d'aide : accès au serveur des patchs,
+/// présence du jeu, droits d'écriture, espace libre, jeu lancé, sauvegardes existantes.
+/// Chaque problème est suivi de la marche à suivre. Renvoie une erreur si l'installation ne peut pas réussir.
+pub fn run_doctor(game_dir: &Path, state_dir: &Path, temp_dir: Option<&Path>, index_url: &str) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::doctor_header());
+    println!("{}", messages::doctor_version(env!("CARGO_PKG_VERSION"), std::env::consts::OS, std::env::consts::ARCH));
+    let mut report = Report::default();
+    let download_dir = download_dir_for(temp_dir);
+
+    check_network(&mut report, index_url);
+    let game_dir_ok = check_game_files(&mut report, game_dir);
+    if game_dir_ok {
+        check_writable(&mut report, &messages::doctor_label_game_dir(), game_dir);
+    }
+    check_writable(&mut report, &messages::doctor_label_temp_dir(), &download_dir);
+    check_writable(&mut report, &messages::doctor_label_state_dir(), state_dir);
+    check_space(
+        &mut report,
+        &messages::doctor_label_temp_dir(),
+        &download_dir,
+        TEMP_SPACE_HINT,
+        &messages::doctor_temp_space_advice(),
+    );
+    if game_dir_ok {
+        check_space(
+            &mut report,
+            &messages::doctor_label_game_dir(),
+            game_dir,
+            game_data_size(game_dir),
+            &messages::doctor_game_space_advice(),
+        );
+        check_game_not_running(&mut report, game_dir);
+        check_backups(&mut report, game_dir, state_dir)?;
+    }
+
+    println!("{}", messages::doctor_summary(report.errors, report.warnings));
+    if report.errors > 0 {
+        return Err(messages::doctor_failed().into());
+    }
+    Ok(())
+}
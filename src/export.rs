@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::{bps, calculate_crc32, gamemaker, index_style_path, messages, PatchDetail, PatchIndex, PlatformInfo};
+
+/// Cherche dans `source_dir` le fichier auquel s'applique un patch.
+/// Le nom attendu est celui du patch sans `.bps` (chapter1_windows/data.win.bps -> chapter1_windows/data.win) ;
+/// à défaut, on cherche n'importe quel fichier de `source_dir` dont le CRC32 correspond.
+fn guess_source_path(
+    source_dir: &Path,
+    relative_patch_path: &Path,
+    source_crc: u32,
+    crc_cache: &mut HashMap<PathBuf, u32>,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let mut crc_of = |path: &Path| -> Result<u32, Box<dyn Error>> {
+        if let Some(&crc) = crc_cache.get(path) {
+            return Ok(crc);
+        }
+        let crc = calculate_crc32(&fs::read(path)?);
+        crc_cache.insert(path.to_path_buf(), crc);
+        Ok(crc)
+    };
+
+    let expected = relative_patch_path.with_extension("");
+    let expected_path = source_dir.join(&expected);
+    if expected_path.is_file() {
+        if crc_of(&expected_path)? == source_crc {
+            return Ok(Some(expected));
+        }
+        eprintln!("{}", messages::export_source_crc_mismatch(&expected_path, source_crc));
+    }
+
+    for entry in WalkDir::new(source_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path == expected_path {
+            continue;
+        }
+        if crc_of(path)? == source_crc {
+            return Ok(Some(path.strip_prefix(source_dir)?.to_path_buf()));
+        }
+    }
+    Ok(None)
+}
+
+/// Génère un `patch_index.json` à partir d'un répertoire de `.bps` et des fichiers d'origine du jeu.
+pub fn run_export_index(
+    patches_dir: &Path,
+    source_dir: &Path,
+    output: &Path,
+    platform: &str,
+    file_url: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::export_header());
+    if !patches_dir.is_dir() {
+        return Err(messages::export_patches_dir_invalid(patches_dir).into());
+    }
+    if !source_dir.is_dir() {
+        return Err(messages::export_source_dir_invalid(source_dir).into());
+    }
+
+    let mut patch_paths: Vec<PathBuf> = WalkDir::new(patches_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "bps"))
+        .collect();
+    patch_paths.sort();
+    if patch_paths.is_empty() {
+        return Err(messages::export_no_patches(patches_dir).into());
+    }
+
+    let mut crc_cache = HashMap::new();
+    let mut patchs = Vec::new();
+    let mut unmatched = 0;
+    for patch_path in &patch_paths {
+        let relative_patch_path = patch_path.strip_prefix(patches_dir)?;
+        let footer = bps::read_bps_footer(patch_path)?;
+
+        match guess_source_path(source_dir, relative_patch_path, footer.source_crc, &mut crc_cache)? {
+            Some(source_path) => {
+                println!("{}", messages::export_patch_matched(
+                    &index_style_path(relative_patch_path), &index_style_path(&source_path),
+                    footer.source_crc, footer.target_crc
+                ));
+                patchs.push(PatchDetail {
+                    patch_path: index_style_path(relative_patch_path),
+                    source_path: index_style_path(&source_path),
+                    source_crc: Some(footer.source_crc),
+                    target_crc: Some(footer.target_crc),
+                    condition: None,
+                    known_sources: Vec::new(),
+                    gen8_version: gamemaker::read_gen8_version(&source_dir.join(&source_path))
+                        .ok()
+                        .map(|version| version.to_string()),
+                });
+            }
+            None => {
+                eprintln!("{}", messages::export_patch_unmatched(source_dir, footer.source_crc, relative_patch_path));
+                unmatched += 1;
+            }
+        }
+    }
+
+    if file_url.is_none() {
+        eprintln!("{}", messages::export_no_file_url());
+    }
+    let mut index = PatchIndex::new();
+    index.insert(platform.to_string(), PlatformInfo {
+        file_url: file_url.unwrap_or_default().to_string(),
+        file_size: None,
+        file_crc: None,
+        file_volumes: Vec::new(),
+        variants: Vec::new(),
+        torrent_url: None,
+        archive_type: None,
+        patchs,
+        post_install: Vec::new(),
+        file_crcs: None,
+        known_executables: HashMap::new(),
+    });
+
+    fs::write(output, serde_json::to_vec_pretty(&index)?)
+        .map_err(|e| messages::export_write_failed(output, &e))?;
+    println!("{}", messages::export_index_written(output, patch_paths.len() - unmatched, platform));
+
+    if unmatched > 0 {
+        return Err(messages::export_unmatched(unmatched).into());
+    }
+    Ok(())
+}
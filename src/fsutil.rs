@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use sysinfo::Disks;
+
+use crate::messages;
+
+static DURABLE: AtomicBool = AtomicBool::new(false);
+
+/// Active (--fsync) la synchronisation sur disque des fichiers critiques : archive téléchargée
+/// et fichiers écrits par `write_atomic` (fichiers patchés, manifestes).
+///
+/// Sans elle, le système peut garder les données en cache quelques secondes après un « succès » :
+/// une coupure de courant ou un plantage système à ce moment peut laisser un fichier vide ou
+/// tronqué. Avec elle, chaque fichier attend son écriture physique, ce qui ralentit nettement
+/// l'installation sur disque dur ou carte SD, pour une garantie utile seulement sur une machine instable.
+pub fn set_durable(durable: bool) {
+    DURABLE.store(durable, Ordering::Relaxed);
+}
+
+/// Force l'écriture sur disque de `file` si --fsync est actif.
+pub fn sync_if_durable(file: &File) -> io::Result<()> {
+    if DURABLE.load(Ordering::Relaxed) {
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
+// Le renommage n'est lui-même durable qu'une fois le répertoire parent synchronisé.
+// Windows ne permet pas d'ouvrir un répertoire ainsi : le renommage y est journalisé par NTFS.
+fn sync_parent_if_durable(path: &Path) -> io::Result<()> {
+    if cfg!(unix)
+        && DURABLE.load(Ordering::Relaxed)
+        && let Some(parent) = path.parent()
+    {
+        let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+        File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Suffixe des temporaires d'écriture atomique.
+pub const TEMP_SUFFIX: &str = ".tmp";
+/// Suffixe d'un téléchargement en cours, renommé une fois complet.
+pub const PARTIAL_SUFFIX: &str = ".part";
+
+fn with_suffix(target: &Path, suffix: &str) -> PathBuf {
+    let mut name = target.file_name().map(OsString::from).unwrap_or_default();
+    name.push(suffix);
+    target.with_file_name(name)
+}
+
+/// Temporaire utilisé pour écrire `target` : toujours dans le même répertoire que
+/// la cible, pour que le renommage final reste sur le même système de fichiers.
+pub fn temp_path_for(target: &Path) -> PathBuf {
+    with_suffix(target, TEMP_SUFFIX)
+}
+
+/// Fichier partiel d'un téléchargement vers `target`, conservé en cas d'interruption pour être repris.
+pub fn partial_path_for(target: &Path) -> PathBuf {
+    with_suffix(target, PARTIAL_SUFFIX)
+}
+
+/// Déplace un fichier. Si les deux chemins sont sur des systèmes de fichiers
+/// différents (EXDEV, ex. /tmp en tmpfs), on se rabat sur copie + suppression.
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            eprintln!("{}", messages::cross_device_move(from, to));
+            // Copier par-dessus `to` l'écraserait en place, sauvegarde par lien physique comprise.
+            match fs::remove_file(to) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+            fs::copy(from, to)?;
+            fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+/// Copie un fichier pour en faire une sauvegarde. Sur les systèmes de fichiers qui le permettent
+/// (Btrfs, XFS, APFS), la copie est un reflink : instantanée et sans espace supplémentaire
+/// tant que l'un des deux fichiers n'est pas modifié. Sinon, copie classique.
+pub fn copy_file(from: &Path, to: &Path) -> io::Result<()> {
+    // Un reflink ne remplace pas un fichier existant.
+    match fs::remove_file(to) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    match reflink_copy::reflink_or_copy(from, to)? {
+        None => tracing::info!("{:?} copié par reflink (copie-sur-écriture).", to),
+        Some(_) => tracing::info!("{:?} copié intégralement (reflink non pris en charge).", to),
+    }
+    Ok(())
+}
+
+/// Fichier réellement désigné par `path`, qui doit rester dans `root` : un lien symbolique (ex. un
+/// data.win partagé entre plusieurs installations) est résolu, pour que l'écriture atomique remplace
+/// le fichier cible et non le lien lui-même. Un lien, ou un répertoire lié, menant hors de `root`
+/// est refusé : le patcher ne modifie rien en dehors du répertoire qu'on lui a confié.
+pub fn resolve_link_within(root: &Path, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let real = match fs::canonicalize(path) {
+        Ok(real) => real,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && fs::symlink_metadata(path).is_err() => return Ok(path.to_path_buf()),
+        Err(e) => return Err(messages::link_unresolvable(path, &e).into()),
+    };
+    let real_root = fs::canonicalize(root)?;
+    if !real.starts_with(&real_root) {
+        return Err(messages::link_outside(path, &real, root).into());
+    }
+    if fs::symlink_metadata(path)?.file_type().is_symlink() {
+        tracing::info!("{:?} est un lien symbolique vers {:?} : c'est ce fichier qui sera modifié.", path, real);
+        return Ok(real);
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Copie récursivement `from` dans `to` (créé au besoin) et renvoie le nombre de fichiers copiés.
+/// `reflink` : copie par `copy_file`, qui partage les données avec l'original quand le système de
+/// fichiers le permet ; sinon copie intégrale, physiquement indépendante de l'original.
+/// Les liens symboliques sont recréés tels quels sous Unix, et remplacés par leur cible ailleurs.
+pub fn copy_tree(from: &Path, to: &Path, reflink: bool) -> Result<u64, Box<dyn Error>> {
+    let mut copied = 0;
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(from)?;
+        let target = to.join(relative);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        #[cfg(unix)]
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)
+                .map_err(|e| messages::link_recreate_failed(&target, &e))?;
+            copied += 1;
+            continue;
+        }
+        let result = if reflink {
+            copy_file(entry.path(), &target)
+                .and_then(|_| fs::set_permissions(&target, fs::metadata(entry.path())?.permissions()))
+        } else {
+            fs::copy(entry.path(), &target).map(|_| ())
+        };
+        result.map_err(|e| messages::copy_failed(entry.path(), &target, &e))?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Écrit `data` dans `target` sans jamais laisser de fichier à moitié écrit :
+/// le contenu passe par un temporaire renommé par-dessus la cible.
+/// Les permissions de la cible existante sont reprises (bit exécutable d'un binaire patché).
+/// Avec --fsync, le contenu puis le renommage sont synchronisés sur disque avant de rendre la main.
+pub fn write_atomic(target: &Path, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    let temp_path = temp_path_for(target);
+    let permissions = fs::metadata(target).ok().map(|m| m.permissions());
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            sync_if_durable(&file)
+        })
+        .and_then(|_| match permissions {
+            Some(permissions) => fs::set_permissions(&temp_path, permissions),
+            None => Ok(()),
+        })
+        .and_then(|_| move_file(&temp_path, target))
+        .and_then(|_| sync_parent_if_durable(target));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(messages::write_failed(target, &e).into());
+    }
+    Ok(())
+}
+
+/// Volume contenant `path` : point de montage et espace libre, si on sait le déterminer.
+pub fn volume_of(path: &Path) -> Option<(PathBuf, u64)> {
+    let path = fs::canonicalize(path).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| (disk.mount_point().to_path_buf(), disk.available_space()))
+}
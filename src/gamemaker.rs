@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::messages;
+
+/// Chunk d'un fichier de données GameMaker (data.win, game.unx, game.ios).
+pub struct Chunk<'a> {
+    pub name: &'a str,
+    pub data: &'a [u8],
+}
+
+/// Vrai si `path` commence par l'en-tête FORM des fichiers de données GameMaker
+/// (lecture des 4 premiers octets seulement).
+pub fn is_data_file(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(path).and_then(|mut file| file.read_exact(&mut magic)).is_ok() && &magic == b"FORM"
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Découpe un fichier GameMaker en chunks. Le fichier est un chunk « FORM » dont le contenu
+/// est une suite de chunks : nom sur 4 octets ASCII, taille sur 4 octets (petit-boutiste), contenu.
+pub fn chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, Box<dyn Error>> {
+    if data.get(..4) != Some(b"FORM".as_slice()) {
+        return Err(messages::gm_not_data_file().into());
+    }
+    let form_size = read_u32(data, 4).ok_or_else(messages::gm_truncated)? as usize;
+    let end = 8usize.saturating_add(form_size).min(data.len());
+
+    let mut chunks = Vec::new();
+    let mut position = 8;
+    while position + 8 <= end {
+        let name = std::str::from_utf8(&data[position..position + 4])
+            .map_err(|_| messages::gm_chunk_name_invalid(position))?;
+        let size = read_u32(data, position + 4).ok_or_else(messages::gm_truncated)? as usize;
+        let offset = position + 8;
+        let data = data
+            .get(offset..offset.saturating_add(size))
+            .ok_or_else(|| messages::gm_chunk_truncated(name, size))?;
+        chunks.push(Chunk { name, data });
+        position = offset + size;
+    }
+    Ok(chunks)
+}
+
+/// Informations du chunk GEN8 utiles pour identifier la version du jeu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gen8Version {
+    pub major: u32,
+    pub minor: u32,
+    pub release: u32,
+    pub build: u32,
+}
+
+impl fmt::Display for Gen8Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.release, self.build)
+    }
+}
+
+// Début du contenu de GEN8 : drapeau de débogage et version du bytecode (1 octet chacun),
+// 2 octets de remplissage, puis des champs de 4 octets. La version (major, minor, release, build)
+// suit le nom du jeu, après l'identifiant de 16 octets.
+const GEN8_VERSION_OFFSET: usize = 44;
+const GEN8_MIN_SIZE: usize = GEN8_VERSION_OFFSET + 16;
+
+/// Lit la version du chunk GEN8, toujours placé en tête : seuls les premiers octets du fichier
+/// sont lus, sans charger un data.win de plusieurs centaines de Mo.
+pub fn read_gen8_version(path: &Path) -> Result<Gen8Version, Box<dyn Error>> {
+    let mut header = vec![0u8; 16 + GEN8_MIN_SIZE];
+    File::open(path)?
+        .read_exact(&mut header)
+        .map_err(|_| messages::gm_gen8_too_short_file())?;
+    if &header[..4] != b"FORM" {
+        return Err(messages::gm_not_data_file().into());
+    }
+    if &header[8..12] != b"GEN8" {
+        return Err(messages::gm_first_not_gen8().into());
+    }
+    let size = read_u32(&header, 12).unwrap_or(0) as usize;
+    if size < GEN8_MIN_SIZE {
+        return Err(messages::gm_gen8_too_short(size).into());
+    }
+    let gen8 = &header[16..];
+    let field = |i: usize| read_u32(gen8, GEN8_VERSION_OFFSET + i * 4).unwrap_or(0);
+    Ok(Gen8Version {
+        major: field(0),
+        minor: field(1),
+        release: field(2),
+        build: field(3),
+    })
+}
+
+/// Chunks sans lesquels aucun runner GameMaker n'ouvre le fichier : GEN8 (informations générales,
+/// toujours en tête), STRG (chaînes) et CODE (scripts compilés).
+const REQUIRED_CHUNKS: [&str; 3] = ["GEN8", "STRG", "CODE"];
+
+/// Vérifie la cohérence du conteneur : en-tête FORM dont la taille annoncée correspond à celle du
+/// fichier, chunks jointifs qui le remplissent exactement, noms uniques, chunks indispensables présents.
+/// Validation de structure seulement : le contenu des chunks n'est pas interprété.
+pub fn validate(data: &[u8]) -> Result<(), Box<dyn Error>> {
+    let chunks = chunks(data)?;
+    let form_size = read_u32(data, 4).unwrap_or(0) as u64;
+    if form_size + 8 != data.len() as u64 {
+        return Err(messages::gm_form_size_mismatch(form_size + 8, data.len()).into());
+    }
+    let covered: u64 = chunks.iter().map(|chunk| chunk.data.len() as u64 + 8).sum();
+    if covered != form_size {
+        return Err(messages::gm_chunks_coverage(covered, form_size).into());
+    }
+    if let Some(first) = chunks.first()
+        && first.name != "GEN8"
+    {
+        return Err(messages::gm_first_chunk(first.name).into());
+    }
+    for (i, chunk) in chunks.iter().enumerate() {
+        if chunks[..i].iter().any(|previous| previous.name == chunk.name) {
+            return Err(messages::gm_chunk_duplicate(chunk.name).into());
+        }
+    }
+    let missing: Vec<&str> = REQUIRED_CHUNKS
+        .iter()
+        .copied()
+        .filter(|name| !chunks.iter().any(|chunk| chunk.name == *name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(messages::gm_chunks_missing(&missing.join(", ")).into());
+    }
+    Ok(())
+}
+
+/// Chaînes du chunk STRG, dans l'ordre de leur table : un nombre de chaînes, puis leurs positions
+/// (absolues dans le fichier), chacune pointant sur une longueur suivie du texte UTF-8.
+pub fn strings(data: &[u8]) -> Result<Vec<String>, Box<dyn Error>> {
+    let chunks = chunks(data)?;
+    let strg = chunks
+        .iter()
+        .find(|chunk| chunk.name == "STRG")
+        .ok_or_else(messages::gm_strg_missing)?;
+
+    let count = read_u32(strg.data, 0).ok_or_else(messages::gm_strg_truncated)? as usize;
+    let mut strings = Vec::with_capacity(count.min(strg.data.len() / 4));
+    for i in 0..count {
+        let entry = read_u32(strg.data, 4 + i * 4).ok_or_else(messages::gm_strg_table_truncated)? as usize;
+        let length = read_u32(data, entry).ok_or_else(messages::gm_strg_out_of_file)? as usize;
+        let text = data
+            .get(entry + 4..entry + 4 + length)
+            .ok_or_else(messages::gm_strg_out_of_file)?;
+        strings.push(String::from_utf8_lossy(text).into_owned());
+    }
+    Ok(strings)
+}
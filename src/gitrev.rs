@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::messages;
+
+/// Révision git d'un répertoire de patchs, lue directement dans `.git` (sans appeler git).
+pub struct GitRevision {
+    pub branch: Option<String>,
+    pub commit: String,
+    pub tags: Vec<String>,
+}
+
+impl GitRevision {
+    /// Description sur une ligne, ex. « branche main, commit 1a2b3c4d5e6f, tag v1.2 ».
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        match &self.branch {
+            Some(branch) => parts.push(messages::git_branch(branch)),
+            None => parts.push(messages::git_detached_head()),
+        }
+        parts.push(format!("commit {}", &self.commit[..self.commit.len().min(12)]));
+        if !self.tags.is_empty() {
+            parts.push(format!("tag {}", self.tags.join(", ")));
+        }
+        parts.join(", ")
+    }
+}
+
+// `.git` est un répertoire, ou un fichier « gitdir: <chemin> » pour les worktrees et sous-modules.
+fn git_dir(repo: &Path) -> Option<PathBuf> {
+    let dot_git = repo.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let target = PathBuf::from(content.strip_prefix("gitdir:")?.trim());
+    Some(if target.is_absolute() { target } else { repo.join(target) })
+}
+
+// Les worktrees partagent les refs du dépôt principal, indiqué par le fichier `commondir`.
+fn common_dir(git_dir: &Path) -> PathBuf {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(content) => git_dir.join(content.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Lignes de `packed-refs` : (commit, nom de la ref), les tags annotés étant remplacés
+/// par le commit qu'ils désignent (lignes « ^<commit> »).
+fn packed_refs(common_dir: &Path) -> Vec<(String, String)> {
+    let Ok(content) = fs::read_to_string(common_dir.join("packed-refs")) else {
+        return Vec::new();
+    };
+    let mut refs: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some(peeled) = line.strip_prefix('^') {
+            if let Some(last) = refs.last_mut() {
+                last.0 = peeled.trim().to_string();
+            }
+        } else if let Some((commit, name)) = line.split_once(' ') {
+            refs.push((commit.to_string(), name.trim().to_string()));
+        }
+    }
+    refs
+}
+
+fn resolve_ref(git_dir: &Path, common_dir: &Path, name: &str) -> Option<String> {
+    for dir in [git_dir, common_dir] {
+        if let Ok(content) = fs::read_to_string(dir.join(name)) {
+            return Some(content.trim().to_string());
+        }
+    }
+    packed_refs(common_dir).into_iter().find(|(_, ref_name)| ref_name == name).map(|(commit, _)| commit)
+}
+
+// Tags pointant sur `commit`. Les tags annotés non compactés désignent un objet tag et non
+// le commit : ils ne sont reconnus qu'une fois dans packed-refs (git gc, ou clone récent).
+fn tags_for(common_dir: &Path, commit: &str) -> Vec<String> {
+    let mut tags: Vec<String> = packed_refs(common_dir)
+        .into_iter()
+        .filter(|(target, name)| target == commit && name.starts_with("refs/tags/"))
+        .map(|(_, name)| name.trim_start_matches("refs/tags/").to_string())
+        .collect();
+    if let Ok(entries) = fs::read_dir(common_dir.join("refs/tags")) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let points_here = fs::read_to_string(entry.path()).is_ok_and(|content| content.trim() == commit);
+            if points_here && !tags.contains(&name) {
+                tags.push(name);
+            }
+        }
+    }
+    tags.sort();
+    tags
+}
+
+/// Révision courante du dépôt git `repo`, ou `None` si ce n'est pas un dépôt git
+/// (ou s'il est illisible : l'information n'est qu'indicative).
+pub fn current_revision(repo: &Path) -> Option<GitRevision> {
+    let git_dir = git_dir(repo)?;
+    let common_dir = common_dir(&git_dir);
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let (branch, commit) = match head.strip_prefix("ref:") {
+        Some(ref_name) => {
+            let ref_name = ref_name.trim();
+            // Branche sans commit (dépôt vide) : rien d'utile à afficher.
+            let commit = resolve_ref(&git_dir, &common_dir, ref_name)?;
+            (Some(ref_name.trim_start_matches("refs/heads/").to_string()), commit)
+        }
+        None => (None, head.to_string()),
+    };
+    if commit.is_empty() {
+        return None;
+    }
+    let tags = tags_for(&common_dir, &commit);
+    Some(GitRevision { branch, commit, tags })
+}
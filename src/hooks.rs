@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::backup::{backup_path_for, create_backup};
+use crate::fsutil;
+use crate::manifest::ManifestEntry;
+use crate::messages;
+
+/// Opération déclarative exécutée après la copie des fichiers supplémentaires.
+/// Volontairement limitée : aucune commande arbitraire ne peut être lancée depuis l'index.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "op")]
+pub enum PostInstallOp {
+    /// Crée (ou remplace) un fichier texte.
+    #[serde(rename = "createFile")]
+    CreateFile { path: String, contents: String },
+
+    /// Définit une valeur dans un fichier INI, en créant la section ou la clé si besoin.
+    #[serde(rename = "setIniValue")]
+    SetIniValue {
+        path: String,
+        section: String,
+        key: String,
+        value: String,
+    },
+}
+
+impl PostInstallOp {
+    pub fn path(&self) -> &str {
+        match self {
+            PostInstallOp::CreateFile { path, .. } => path,
+            PostInstallOp::SetIniValue { path, .. } => path,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            PostInstallOp::CreateFile { path, .. } => messages::hook_create_file(path),
+            PostInstallOp::SetIniValue { path, section, key, value } => {
+                messages::hook_set_ini_value(path, section, key, value)
+            }
+        }
+    }
+}
+
+// Refuse les chemins absolus ou remontant au-dessus du répertoire du jeu.
+fn relative_target(path: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let relative = PathBuf::from(path);
+    let is_safe = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !is_safe || relative.as_os_str().is_empty() {
+        return Err(messages::hook_path_refused(path).into());
+    }
+    Ok(relative)
+}
+
+fn set_ini_value(contents: &str, section: &str, key: &str, value: &str) -> String {
+    let newline = if contents.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    let new_line = format!("{}={}", key, value);
+
+    let is_header = |line: &str| line.trim().starts_with('[') && line.trim().ends_with(']');
+    let section_start = lines.iter().position(|line| {
+        is_header(line) && line.trim()[1..line.trim().len() - 1].trim().eq_ignore_ascii_case(section)
+    });
+
+    match section_start {
+        Some(start) => {
+            let section_end = lines[start + 1..]
+                .iter()
+                .position(|line| is_header(line))
+                .map_or(lines.len(), |offset| start + 1 + offset);
+
+            let existing = lines[start + 1..section_end].iter().position(|line| {
+                line.split_once('=')
+                    .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+            });
+            match existing {
+                Some(offset) => lines[start + 1 + offset] = new_line,
+                None => {
+                    // Insère après la dernière ligne non vide de la section.
+                    let mut insert_at = section_end;
+                    while insert_at > start + 1 && lines[insert_at - 1].trim().is_empty() {
+                        insert_at -= 1;
+                    }
+                    lines.insert(insert_at, new_line);
+                }
+            }
+        }
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", section));
+            lines.push(new_line);
+        }
+    }
+
+    let mut result = lines.join(newline);
+    result.push_str(newline);
+    result
+}
+
+/// Exécute les opérations post-installation et complète `entries`, les entrées du manifeste de
+/// l'installation en cours. Un fichier que cette installation a déjà écrit garde son entrée.
+pub fn run_post_install(ops: &[PostInstallOp], game_dir: &Path, entries: &mut Vec<ManifestEntry>) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::hooks_header());
+
+    for op in ops {
+        let relative_path = relative_target(op.path())?;
+        let target_path = game_dir.join(&relative_path);
+        println!("{}", messages::hook_operation(&op.describe()));
+
+        let existing = fs::read(&target_path).ok();
+        let new_contents = match op {
+            PostInstallOp::CreateFile { contents, .. } => contents.clone(),
+            PostInstallOp::SetIniValue { section, key, value, .. } => {
+                let current = existing.as_deref().map(String::from_utf8_lossy).unwrap_or_default();
+                set_ini_value(&current, section, key, value)
+            }
+        };
+
+        let installed = entries.iter().position(|entry| entry.path == relative_path);
+        let mut backup = None;
+        if installed.is_some() {
+            // Fichier supplémentaire, fichier patché ou opération précédente : l'entrée existante
+            // dit déjà comment revenir à l'origine (sauvegarde ou suppression).
+        } else if existing.is_some() && backup_path_for(&target_path).is_file() {
+            // Installation précédente (--incremental) : la sauvegarde existante contient la
+            // version d'origine, la remplacer y mettrait la version modifiée.
+            println!("{}", messages::backup_already_present(&backup_path_for(&target_path)));
+            backup = Some(backup_path_for(&relative_path));
+        } else if existing.is_some() {
+            let backup_path = create_backup(&target_path)
+                .map_err(|e| messages::hook_backup_failed(&target_path, &backup_path_for(&target_path), &e))?;
+            println!("{}", messages::backup_path_created(&backup_path));
+            backup = Some(backup_path_for(&relative_path));
+        } else if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fsutil::write_atomic(&target_path, new_contents.as_bytes())?;
+        match installed {
+            // Le contenu a changé : le CRC noté pour le fichier ne vaut plus.
+            Some(index) => entries[index].crc = None,
+            None => entries.push(ManifestEntry {
+                path: relative_path,
+                created: backup.is_none(),
+                backup,
+                ..Default::default()
+            }),
+        }
+    }
+
+    println!("{}", messages::hooks_done());
+    Ok(())
+}
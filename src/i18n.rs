@@ -0,0 +1,37 @@
+use std::sync::OnceLock;
+use clap::ValueEnum;
+
+/// Langue de l'interface du patcher (et non du patch appliqué au jeu).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// Français (par défaut)
+    Fr,
+    /// English
+    En,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Langue de la locale système, d'après LC_ALL, LC_MESSAGES puis LANG (première variable définie,
+/// comme gettext). Français si la locale n'est ni française ni anglaise.
+fn detect() -> Lang {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    if locale.starts_with("en") {
+        Lang::En
+    } else {
+        Lang::Fr
+    }
+}
+
+/// Fixe la langue de l'interface : celle de --lang-ui, sinon celle du système.
+pub fn init(forced: Option<Lang>) {
+    let _ = LANG.set(forced.unwrap_or_else(detect));
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(detect)
+}
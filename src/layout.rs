@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+use crate::{fetch_patch_index, is_patch_metadata, messages, platform_info_for};
+
+/// Rôle d'un fichier du jeu dans l'installation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    /// Doit exister : modifié par un patch.
+    Patched,
+    /// Copié depuis l'archive (remplacé s'il existe déjà).
+    Added,
+    /// Créé ou modifié par une opération post-installation (--allow-hooks).
+    Hook,
+}
+
+impl Role {
+    fn label(self) -> String {
+        match self {
+            Role::Patched => messages::layout_role_patched(),
+            Role::Added => messages::layout_role_added(),
+            Role::Hook => messages::layout_role_hook(),
+        }
+    }
+}
+
+/// Affiche l'arborescence des fichiers que le patch s'attend à trouver et à modifier
+/// dans le répertoire du jeu pour `platform`, d'après l'index.
+pub fn run_expected_layout(platform: &str, index_url: &str) -> Result<(), Box<dyn Error>> {
+    let patch_index = fetch_patch_index(index_url)?;
+    let platform_info = platform_info_for(&patch_index, platform)?;
+
+    let mut files: BTreeMap<String, Role> = BTreeMap::new();
+    for detail in &platform_info.patchs {
+        files.insert(detail.source_path.clone(), Role::Patched);
+    }
+    // Les extras ne sont connus que si l'index liste le contenu de l'archive (fileCrcs).
+    let extras_known = platform_info.file_crcs.is_some();
+    for path in platform_info.file_crcs.iter().flat_map(|crcs| crcs.keys()) {
+        if !path.ends_with(".bps") && !is_patch_metadata(Path::new(path)) {
+            files.entry(path.clone()).or_insert(Role::Added);
+        }
+    }
+    for op in &platform_info.post_install {
+        files.entry(op.path().to_string()).or_insert(Role::Hook);
+    }
+
+    println!("{}", messages::layout_header(platform));
+    println!("{}", messages::layout_root());
+    let mut printed_dirs: Vec<&str> = Vec::new();
+    for (path, role) in &files {
+        let components: Vec<&str> = path.split('/').collect();
+        let (dirs, name) = components.split_at(components.len() - 1);
+        // Répertoires parents pas encore affichés, avec leur niveau d'indentation.
+        let common = printed_dirs.iter().zip(dirs).take_while(|(a, b)| a == b).count();
+        printed_dirs.truncate(common);
+        for dir in &dirs[common..] {
+            println!("{}{}/", "  ".repeat(printed_dirs.len() + 1), dir);
+            printed_dirs.push(dir);
+        }
+        println!("{}{}  [{}]", "  ".repeat(dirs.len() + 1), name[0], role.label());
+    }
+
+    let count = |role| files.values().filter(|r| **r == role).count();
+    println!("{}", messages::layout_summary(count(Role::Patched), count(Role::Added), count(Role::Hook)));
+    if !extras_known {
+        println!("{}", messages::layout_extras_unknown());
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+
+use crate::{manifest, messages};
+
+/// Verrou exclusif sur un répertoire de jeu, tenu le temps d'une opération qui le modifie.
+/// C'est un verrou du système : il est libéré à la fin du processus, même en cas de plantage.
+pub struct GameDirLock {
+    _file: Option<File>,
+}
+
+/// Le répertoire est déjà verrouillé par une autre instance : erreur passagère, qui disparaît
+/// quand celle-ci se termine (voir install --retry-install).
+#[derive(Debug)]
+pub struct LockHeld {
+    /// PID de l'instance qui tient le verrou, s'il a pu être lu.
+    pub owner: Option<String>,
+}
+
+impl fmt::Display for LockHeld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owner = self.owner.as_ref().map(|pid| format!(" (PID {})", pid)).unwrap_or_default();
+        f.write_str(&messages::lock_held(&owner))
+    }
+}
+
+impl Error for LockHeld {}
+
+/// Empêche deux instances du patcher de modifier le même répertoire de jeu en même temps.
+/// Le fichier de verrou est dans `state_dir/locks`, un par répertoire de jeu.
+pub fn lock_game_dir(state_dir: &Path, game_dir: &Path) -> Result<GameDirLock, Box<dyn Error>> {
+    let dir = state_dir.join("locks");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.lock", manifest::game_dir_key(game_dir)));
+    // Pas de troncature avant d'avoir le verrou : le PID de l'instance qui le tient doit rester lisible.
+    let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
+
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            let owner = fs::read_to_string(&path).unwrap_or_default();
+            let owner = Some(owner.trim().to_string()).filter(|pid| !pid.is_empty());
+            return Err(LockHeld { owner }.into());
+        }
+        // Système de fichiers sans verrous (certains partages réseau) : on continue sans protection.
+        Err(TryLockError::Error(e)) => {
+            eprintln!("{}", messages::lock_unavailable(&path, &e));
+            return Ok(GameDirLock { _file: None });
+        }
+    }
+
+    file.set_len(0)?;
+    write!(file, "{}", std::process::id())?;
+    Ok(GameDirLock { _file: Some(file) })
+}
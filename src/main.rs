@@ -1,11 +1,70 @@
-use std::collections::HashMap;
-use std::io::{BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Read, Write};
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::error::Error; 
+use std::fmt;
+use std::sync::OnceLock;
 use walkdir::WalkDir;
-use clap::{Parser, Subcommand};
-use serde::Deserialize; 
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+// En premier : ses macros println!/eprintln! remplacent celles de std dans tout le crate.
+#[macro_use]
+mod output;
+
+mod archive;
+mod audit;
+mod backup;
+#[cfg(feature = "benchmark")]
+mod benchmark;
+mod bps;
+mod check_crc;
+mod cleanup;
+mod config;
+#[cfg(feature = "testing")]
+mod corrupt;
+mod deadline;
+mod doctor;
+mod export;
+mod fsutil;
+mod gamemaker;
+mod gitrev;
+mod hooks;
+mod i18n;
+mod layout;
+mod lock;
+mod manifest;
+mod messages;
+mod net;
+mod patchfmt;
+mod ping;
+mod process;
+mod profiles;
+mod sandbox;
+mod selection;
+mod serve;
+mod show_changes;
+mod status;
+mod state_archive;
+mod torrent;
+mod update;
+mod validate_index;
+mod verify;
+mod verify_backups;
+mod versions;
+mod wizard;
+mod progress;
+#[cfg(test)]
+mod testutil;
+#[cfg(test)]
+mod tests;
+
+use backup::backup_path_for;
+use manifest::{InstallManifest, ManifestEntry};
+use progress::{Phase, Progress, ProgressMode};
+use selection::Selection;
+use deadline::Deadline;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -17,119 +76,1193 @@ use serde::Deserialize;
         Permet également de désinstaller le patch en restaurant les fichiers originaux."
 )]
 struct Args {
+    /// Sans commande, l'assistant interactif est lancé (double-clic sur le patcher).
     #[command(subcommand)]
-    command: Command,
+    command: Option<Command>,
+
+    /// Répertoire où sont conservés les manifestes d'installation (par défaut ~/.local/share/patcher_drfr)
+    #[arg(long = "state-dir", value_name = "REPERTOIRE_ETAT", global = true)]
+    state_dir: Option<PathBuf>,
+
+    /// Fichier de configuration TOML (par défaut ~/.config/patcher_drfr/config.toml)
+    #[arg(long = "config", value_name = "FICHIER", global = true)]
+    config: Option<PathBuf>,
+
+    /// Répond oui à toutes les questions, sans rien demander (utilisation dans un script)
+    #[arg(short = 'y', long = "yes", visible_alias = "non-interactive", global = true)]
+    yes: bool,
+
+    /// N'affiche que les avertissements et les erreurs (sur la sortie d'erreur)
+    #[arg(short = 'q', long = "quiet", global = true)]
+    quiet: bool,
+
+    /// Affiche plus de détails : -v pour les étapes, -vv pour chaque requête HTTP
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Format des journaux : text, ou json (un objet par ligne, pour un agrégateur de logs)
+    #[arg(long = "log-format", value_enum, value_name = "FORMAT", default_value_t = output::LogFormat::Text, global = true)]
+    log_format: output::LogFormat,
+
+    /// Couleurs de la sortie (auto : seulement vers un terminal et si NO_COLOR n'est pas défini)
+    #[arg(long = "color", value_enum, value_name = "QUAND", default_value_t = output::ColorMode::Auto, global = true)]
+    color: output::ColorMode,
+
+    /// Index des patchs à utiliser à la place de l'index officiel (miroir, serveur de test) ; à défaut, variable PATCHER_DRFR_INDEX_URL puis [index] url de la configuration
+    #[arg(long = "index-url", value_name = "URL", global = true)]
+    index_url: Option<String>,
+
+    /// Langue des messages du patcher (par défaut celle du système, d'après LANG ; sinon le français)
+    #[arg(long = "lang-ui", value_enum, value_name = "LANGUE", global = true)]
+    lang_ui: Option<i18n::Lang>,
+}
+
+/// Conduite à tenir quand le fichier auquel s'applique un patch est absent du jeu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum MissingSource {
+    /// Ignore le patch sans rien afficher (ex. chapitre non installé)
+    Skip,
+    /// Ignore le patch en l'affichant
+    Warn,
+    /// Arrête l'installation avant toute modification
+    Fail,
+}
+
+/// Options de la commande `install`.
+#[derive(Clone)]
+struct InstallOptions {
+    assume_yes: bool,
+    allow_hooks: bool,
+    compress_backups: bool,
+    strict: bool,
+    progress: ProgressMode,
+    suspend_running_game: bool,
+    resume: bool,
+    temp_dir: Option<PathBuf>,
+    skip_exe_check: bool,
+    skip_game_check: bool,
+    mirror: Option<String>,
+    deadline: Option<u64>,
+    patch_version: Option<String>,
+    from_dir: Option<PathBuf>,
+    /// Archive du patch déjà téléchargée, installée sans accès réseau.
+    from_file: Option<PathBuf>,
+    /// Index à utiliser avec --from-file.
+    local_index: Option<PathBuf>,
+    download_chunk_size: usize,
+    selection: Selection,
+    preview: bool,
+    /// Parcourt toute l'installation jusqu'au plan, sans rien écrire dans le jeu.
+    dry_run: bool,
+    torrent: bool,
+    fsync: bool,
+    use_cache: bool,
+    offline: bool,
+    max_extras: usize,
+    incremental: bool,
+    validate_gamemaker: bool,
+    on_missing_source: MissingSource,
+    /// Nombre de nouvelles tentatives de toute l'installation après une erreur passagère.
+    retry_install: u32,
+    /// Plateforme imposée par le profil ou --platform, au lieu de la détection automatique.
+    platform: Option<String>,
+}
+
+impl InstallOptions {
+    /// Options d'une installation sans option particulière, comme un install sans argument
+    /// (mêmes valeurs par défaut que la ligne de commande). Sert à update.
+    fn defaults(assume_yes: bool) -> Self {
+        InstallOptions {
+            assume_yes,
+            allow_hooks: false,
+            compress_backups: false,
+            strict: false,
+            progress: ProgressMode::Detailed,
+            suspend_running_game: false,
+            resume: false,
+            temp_dir: None,
+            skip_exe_check: false,
+            skip_game_check: false,
+            mirror: None,
+            deadline: None,
+            patch_version: None,
+            from_dir: None,
+            from_file: None,
+            local_index: None,
+            download_chunk_size: 1024 * 1024,
+            selection: Selection::new(Vec::new(), false, None),
+            preview: false,
+            dry_run: false,
+            torrent: false,
+            fsync: false,
+            use_cache: false,
+            offline: false,
+            max_extras: 500,
+            incremental: false,
+            validate_gamemaker: false,
+            on_missing_source: MissingSource::Warn,
+            retry_install: 0,
+            platform: None,
+        }
+    }
 }
 
 // --- Sous-commandes ---
 #[derive(Subcommand, Debug)]
+#[allow(clippy::large_enum_variant)]
 enum Command {
+    /// Assistant interactif : demande le répertoire du jeu puis propose d'installer ou de désinstaller (lancé aussi sans argument).
+    Wizard,
     /// Télécharge et installe la dernière version du patch FR.
     Install {
+        /// Chemin vers le répertoire contenant Deltarune.exe ; à répéter avec plusieurs plateformes, dans le même ordre
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present_any = ["profile", "all_platforms"])]
+        game_dir: Vec<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with_all = ["game_dir", "all_platforms"])]
+        profile: Option<String>,
+
+        /// Plateforme(s) de l'index à installer, sans détection automatique (ex. --platform full,demo) ; chacune sur le --game-dir de même rang, ou à défaut sur le profil enregistré pour elle
+        #[arg(long = "platform", value_name = "PLATEFORME", value_delimiter = ',')]
+        platform: Vec<String>,
+
+        /// Installe toutes les plateformes de l'index, chacune sur son --game-dir ou son profil
+        #[arg(long = "all-platforms", conflicts_with_all = ["platform", "output_dir"])]
+        all_platforms: bool,
+
+        /// Exécute les opérations post-installation déclarées par l'index (création de fichiers, modification d'INI)
+        #[arg(long = "allow-hooks")]
+        allow_hooks: bool,
+
+        /// Compresse les sauvegardes des fichiers patchés (.bak.zst) pour économiser de l'espace disque
+        #[arg(long = "compress-backups")]
+        compress_backups: bool,
+
+        /// Arrête l'installation à la moindre divergence lors des vérifications d'intégrité
+        #[arg(long = "strict")]
+        strict: bool,
+
+        /// Affichage de la progression : une barre par phase, une seule barre globale, ou rien
+        #[arg(long = "progress", value_enum, value_name = "MODE", default_value_t = ProgressMode::Detailed)]
+        progress: ProgressMode,
+
+        /// Progression pour un lanceur externe : uniquement des lignes « PROGRESS <0.0 à 1.0> » sur la sortie standard (équivaut à --progress machine)
+        #[arg(long = "machine-progress", conflicts_with = "progress")]
+        machine_progress: bool,
+
+        /// (Avancé) Si le jeu est lancé, le suspend pendant le patch puis l'arrête, au lieu de refuser d'installer
+        #[arg(long = "suspend-running-game")]
+        suspend_running_game: bool,
+
+        /// Reprend une installation interrompue : garde les fichiers déjà patchés et restaure ceux laissés à moitié écrits
+        #[arg(long = "resume")]
+        resume: bool,
+
+        /// Répertoire où télécharger et extraire le patch (par défaut /tmp), utile si /tmp est un petit tmpfs
+        #[arg(long = "temp-dir", value_name = "REPERTOIRE_TEMP")]
+        temp_dir: Option<PathBuf>,
+
+        /// Ne compare pas l'exécutable du jeu aux versions officielles connues de l'index
+        #[arg(long = "skip-exe-check")]
+        skip_exe_check: bool,
+
+        /// Installe même si le répertoire ne ressemble pas à une installation Steam de Deltarune (option avancée)
+        #[arg(long = "skip-game-check")]
+        skip_game_check: bool,
+
+        /// Télécharge l'archive du patch depuis cette URL plutôt que celle de l'index (ses redirections ne sont pas filtrées)
+        #[arg(long = "mirror", value_name = "URL")]
+        mirror: Option<String>,
+
+        /// Durée maximale de toute l'installation, en secondes ; au-delà, les modifications sont annulées (code de sortie 124)
+        #[arg(long = "deadline", value_name = "SECONDES")]
+        deadline: Option<u64>,
+
+        /// Installe cette version du patch plutôt que la dernière (voir la commande versions)
+        #[arg(long = "patch-version", value_name = "VERSION")]
+        patch_version: Option<String>,
+
+        /// Installe depuis un répertoire local contenant patch_index.json et les .bps (ex. clone git du dépôt des patchs), sans rien télécharger
+        #[arg(long = "from-dir", value_name = "REPERTOIRE_PATCHS", conflicts_with_all = ["mirror", "patch_version"])]
+        from_dir: Option<PathBuf>,
+
+        /// Installe depuis une archive du patch déjà téléchargée (ZIP, tar.gz ou 7z), sans accès réseau
+        #[arg(long = "from-file", value_name = "ARCHIVE", conflicts_with_all = ["from_dir", "mirror", "patch_version", "torrent", "use_cache"])]
+        from_file: Option<PathBuf>,
+
+        /// Avec --from-file : index des patchs à utiliser (par défaut celui contenu dans l'archive, sinon celui de la dernière installation en ligne)
+        #[arg(long = "local-index", value_name = "FICHIER", requires = "from_file")]
+        local_index: Option<PathBuf>,
+
+        /// Taille des blocs lus puis écrits pendant le téléchargement, en Kio
+        #[arg(long = "download-chunk-size", value_name = "KIO", default_value_t = 1024, value_parser = clap::value_parser!(u32).range(4..=65536))]
+        download_chunk_size: u32,
+
+        /// Ne patche que ces chapitres (ex. --chapter 1,3) ; les autres ne sont ni extraits ni modifiés
+        #[arg(long = "chapter", value_name = "CHAPITRE", value_delimiter = ',')]
+        chapter: Vec<u32>,
+
+        /// Applique seulement les patchs, sans copier les fichiers supplémentaires de l'archive
+        #[arg(long = "patches-only")]
+        patches_only: bool,
+
+        /// Télécharge, extrait et valide tout, puis affiche ce qui serait fait avant de demander confirmation ; rien n'est écrit dans le jeu d'ici là
+        #[arg(long = "preview")]
+        preview: bool,
+
+        /// Télécharge, extrait et valide tout, affiche ce qui serait sauvegardé, patché et copié, puis s'arrête sans rien écrire dans le jeu
+        #[arg(long = "dry-run", conflicts_with_all = ["preview", "atomic_swap", "output_dir", "retry_install"])]
+        dry_run: bool,
+
+        /// Télécharge l'archive en pair-à-pair si l'index fournit un torrent (client aria2c requis), sinon en HTTP
+        #[arg(long = "torrent", conflicts_with = "mirror")]
+        torrent: bool,
+
+        /// Attend l'écriture physique sur le disque de l'archive et de chaque fichier patché : plus lent, mais rien n'est perdu si le système plante juste après
+        #[arg(long = "fsync")]
+        fsync: bool,
+
+        /// Réutilise l'archive extraite lors d'une installation précédente si elle contient tous les patchs attendus, au lieu de la retélécharger
+        #[arg(long = "use-cache", conflicts_with_all = ["from_dir", "mirror"])]
+        use_cache: bool,
+
+        /// Avec --use-cache : aucun accès réseau (index et patchs lus dans le cache), échec si le cache est absent ou incomplet
+        #[arg(long = "offline", requires = "use_cache", conflicts_with_all = ["patch_version", "torrent"])]
+        offline: bool,
+
+        /// Nombre maximal de fichiers supplémentaires à copier ; au-delà, l'archive est jugée suspecte et rien n'est installé
+        #[arg(long = "max-extras", value_name = "N", default_value_t = 500)]
+        max_extras: usize,
+
+        /// Mise à jour d'une installation existante : seuls les fichiers qui ne sont pas déjà dans la version cible de l'index sont repatchés, depuis leur sauvegarde
+        #[arg(long = "incremental", conflicts_with_all = ["resume", "output_dir"])]
+        incremental: bool,
+
+        /// Vérifie la structure de chaque fichier GameMaker patché (data.win...) ; un fichier invalide annule l'installation
+        #[arg(long = "validate-gamemaker")]
+        validate_gamemaker: bool,
+
+        /// Si un fichier à patcher est absent du jeu : l'ignorer sans rien dire (skip), l'ignorer en le signalant (warn) ou arrêter l'installation (fail)
+        #[arg(long = "on-missing-source", value_enum, value_name = "CONDUITE", default_value_t = MissingSource::Warn)]
+        on_missing_source: MissingSource,
+
+        /// Après une erreur passagère (réseau, répertoire verrouillé par une autre instance), annule les modifications et recommence toute l'installation, jusqu'à N fois, avec un délai croissant
+        #[arg(long = "retry-install", value_name = "N", default_value_t = 0)]
+        retry_install: u32,
+
+        /// Copie le jeu dans ce répertoire (absent ou vide) et patche la copie, sans toucher à l'original
+        #[arg(long = "output-dir", value_name = "REPERTOIRE_SORTIE", conflicts_with = "resume")]
+        output_dir: Option<PathBuf>,
+
+        /// Variante des fichiers supplémentaires à installer (ex. fr-FR, fr-CA), parmi celles de l'index ; par défaut la variante principale
+        #[arg(long = "variant", value_name = "VARIANTE")]
+        variant: Option<String>,
+
+        /// Patche une copie du jeu créée à côté de lui, puis échange les deux répertoires : le jeu n'est jamais à moitié patché (nécessite temporairement le double d'espace)
+        #[arg(long = "atomic-swap", conflicts_with_all = ["resume", "output_dir", "incremental"])]
+        atomic_swap: bool,
+
+        /// Avec --output-dir : copie intégrale de chaque fichier, sans partage de données par reflink avec l'original
+        #[arg(long = "copy-game", requires = "output_dir")]
+        copy_game: bool,
+    },
+    /// Installe la dernière version du patch si elle est plus récente que celle installée (rien à faire sinon).
+    Update {
         /// Chemin vers le répertoire contenant Deltarune.exe
-        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required = true)]
-        game_dir: PathBuf,
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
     },
     /// Désinstalle le patch et restaure les fichiers anglais.
     Uninstall {
          /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Affiche les fichiers qui seraient restaurés, sans rien modifier
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+    /// Annule uniquement la dernière installation, à partir de son manifeste.
+    RollbackLast {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+    },
+    /// Génère un patch_index.json à partir d'un répertoire de patchs .bps (pour les contributeurs).
+    ExportIndex {
+        /// Répertoire contenant les .bps, organisé comme l'archive publiée
+        #[arg(value_name = "REPERTOIRE_PATCHS")]
+        patches_dir: PathBuf,
+
+        /// Répertoire contenant les fichiers d'origine du jeu auxquels s'appliquent les patchs
+        #[arg(value_name = "REPERTOIRE_SOURCE")]
+        source_dir: PathBuf,
+
+        /// Fichier d'index à écrire
+        #[arg(short = 'o', long = "output", value_name = "FICHIER", default_value = "patch_index.json")]
+        output: PathBuf,
+
+        /// Clé de plateforme de l'index (full, demo...)
+        #[arg(long = "platform", value_name = "PLATEFORME", default_value = "full")]
+        platform: String,
+
+        /// URL de l'archive ZIP publiée, écrite dans fileUrl
+        #[arg(long = "file-url", value_name = "URL")]
+        file_url: Option<String>,
+    },
+    /// Vérifie que les sauvegardes (.bak) correspondent à la version d'origine des fichiers, d'après l'index.
+    VerifyBackups {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Recrée une sauvegarde invalide quand le fichier du jeu est encore d'origine
+        #[arg(long = "repair")]
+        repair: bool,
+
+        /// Plateforme de l'index à utiliser (ex. full, demo), sans détection automatique
+        #[arg(long = "platform", value_name = "PLATEFORME")]
+        platform: Option<String>,
+    },
+    /// Indique si le patch est installé, en quelle version, depuis quand, et si des fichiers installés ont été modifiés depuis.
+    Status {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+    },
+    /// Vérifie que chaque fichier patché a le CRC32 attendu par l'index après patch (OK, non patché, corrompu ou manquant).
+    Verify {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Plateforme de l'index à utiliser (ex. full, demo), sans détection automatique
+        #[arg(long = "platform", value_name = "PLATEFORME")]
+        platform: Option<String>,
+    },
+    /// Vérifie que les fichiers installés par le patch n'ont pas changé depuis, d'après le manifeste de la dernière installation.
+    Audit {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+    },
+    /// Affiche le CRC32 réel de chaque fichier à patcher à côté des CRC32 source et cible attendus, sans rien modifier.
+    CheckCrc {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Plateforme de l'index à utiliser (ex. full, demo), sans détection automatique
+        #[arg(long = "platform", value_name = "PLATEFORME")]
+        platform: Option<String>,
+    },
+    /// Vérifie qu'un index correspond aux patchs : CRC32 source et cible déclarés contre le footer de chaque .bps (pour les mainteneurs).
+    ValidateIndex {
+        /// Répertoire contenant les .bps, organisé comme l'archive publiée
+        #[arg(value_name = "REPERTOIRE_PATCHS")]
+        patches_dir: PathBuf,
+
+        /// Index à vérifier (par défaut patch_index.json dans le répertoire de patchs)
+        #[arg(long = "index", value_name = "FICHIER")]
+        index: Option<PathBuf>,
+
+        /// Ne vérifie que cette plateforme de l'index
+        #[arg(long = "platform", value_name = "PLATEFORME")]
+        platform: Option<String>,
+    },
+    /// Affiche l'arborescence des fichiers que le patch s'attend à trouver et à modifier dans le répertoire du jeu.
+    ExpectedLayout {
+        /// Plateforme de l'index (full, demo)
+        #[arg(long = "platform", value_name = "PLATEFORME", default_value = "full")]
+        platform: String,
+    },
+    /// Applique un patch en mémoire et affiche les textes du jeu qu'il modifie (anglais -> français).
+    ShowChanges {
+        /// Fichier d'origine (ex. data.win), jamais modifié
+        #[arg(long = "source", value_name = "FICHIER")]
+        source: PathBuf,
+
+        /// Patch BPS à examiner
+        #[arg(long = "patch", value_name = "PATCH")]
+        patch: PathBuf,
+    },
+    /// (Tests) Modifie un octet d'un fichier pour vérifier que les contrôles CRC détectent la corruption.
+    #[cfg(feature = "testing")]
+    #[command(hide = true)]
+    CorruptTest {
+        /// Fichier à corrompre
+        #[arg(long = "file", value_name = "FICHIER")]
+        file: PathBuf,
+
+        /// Position de l'octet à modifier
+        #[arg(long = "at", value_name = "POSITION")]
+        at: u64,
+
+        /// Nouvelle valeur de l'octet (décimal ou 0x..)
+        #[arg(long = "byte", value_name = "OCTET", value_parser = corrupt::parse_byte)]
+        byte: u8,
+    },
+    /// (Développement) Mesure la durée de chaque étape de l'application d'un patch BPS.
+    #[cfg(feature = "benchmark")]
+    #[command(hide = true)]
+    Benchmark {
+        /// Fichier source (jamais modifié)
+        #[arg(long = "source", value_name = "FICHIER")]
+        source: PathBuf,
+
+        /// Patch BPS à appliquer
+        #[arg(long = "patch", value_name = "PATCH")]
+        patch: PathBuf,
+
+        /// Nombre de répétitions
+        #[arg(short = 'n', long = "iterations", value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+    },
+    /// Gère les profils : des installations du jeu mémorisées sous un nom.
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    /// Liste les versions publiées du patch, avec leur date et leurs notes.
+    #[command(visible_alias = "list-versions")]
+    Versions,
+    /// Vérifie que le service de patch répond, sans télécharger l'index (code de sortie 0 si disponible).
+    Ping,
+    /// Diagnostique l'environnement (réseau, fichiers du jeu, droits d'écriture, espace libre, jeu lancé, sauvegardes) et indique quoi corriger.
+    Doctor {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Répertoire temporaire qui sera passé à install, s'il n'est pas celui par défaut
+        #[arg(long = "temp-dir", value_name = "REPERTOIRE_TEMP")]
+        temp_dir: Option<PathBuf>,
+    },
+    /// Lance un serveur HTTP local (127.0.0.1) pour piloter le patcher depuis un lanceur : install, status, verify, audit, verify-backups, versions et ping, avec la progression en Server-Sent Events.
+    Serve {
+        /// Port d'écoute
+        #[arg(long = "port", value_name = "PORT", default_value_t = 8437)]
+        port: u16,
+    },
+    /// Empaquette l'état d'installation (manifestes, sauvegardes et leurs CRC) dans une archive tar portable.
+    ExportState {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Archive à créer
+        #[arg(short = 'o', long = "output", value_name = "ARCHIVE", default_value = "state.tar")]
+        output: PathBuf,
+    },
+    /// Restaure un état exporté par export-state dans un répertoire de jeu déjà patché (migration).
+    ImportState {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Archive produite par export-state
+        #[arg(short = 'i', long = "input", value_name = "ARCHIVE")]
+        input: PathBuf,
+    },
+    /// Liste et supprime les fichiers temporaires (.tmp, .part) laissés par une exécution interrompue.
+    CleanTemp {
+        /// Chemin vers le répertoire contenant Deltarune.exe
+        #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required_unless_present = "profile")]
+        game_dir: Option<PathBuf>,
+
+        /// Utilise le répertoire du jeu et l'état enregistrés dans ce profil (voir la commande profile)
+        #[arg(long = "profile", value_name = "PROFIL", conflicts_with = "game_dir")]
+        profile: Option<String>,
+
+        /// Répertoire temporaire passé à install, s'il n'était pas celui par défaut
+        #[arg(long = "temp-dir", value_name = "REPERTOIRE_TEMP")]
+        temp_dir: Option<PathBuf>,
+
+        /// Liste les fichiers sans rien supprimer
+        #[arg(long = "list")]
+        list: bool,
+    },
+    /// Convertit un patch d'un format à l'autre (BPS, IPS, UPS), d'après les extensions des fichiers.
+    Convert {
+        /// Patch à convertir
+        #[arg(long = "from", value_name = "PATCH")]
+        from: PathBuf,
+
+        /// Patch à écrire, dans le format de son extension (.bps ou .ips : flips ne crée pas de patch UPS)
+        #[arg(long = "to", value_name = "PATCH")]
+        to: PathBuf,
+
+        /// Fichier d'origine auquel s'applique le patch
+        #[arg(long = "source", value_name = "FICHIER")]
+        source: PathBuf,
+    },
+}
+
+
+const PATCH_INDEX_URL: &str = "https://deltarune-fr.com/patch-files/linux/patch_index.json";
+
+/// Historique des versions publiées du patch, chacune avec son propre index.
+const VERSIONS_URL: &str = "https://deltarune-fr.com/patch-files/linux/versions.json";
+
+/// Variable d'environnement équivalente à --index-url.
+const INDEX_URL_ENV: &str = "PATCHER_DRFR_INDEX_URL";
+
+/// Index choisi au démarrage (--index-url, variable d'environnement ou configuration).
+static INDEX_URL: OnceLock<String> = OnceLock::new();
+
+/// URL de l'index des patchs : l'index officiel, sauf si un autre a été choisi au démarrage.
+fn index_url() -> &'static str {
+    INDEX_URL.get().map_or(PATCH_INDEX_URL, String::as_str)
+}
+
+/// Historique des versions, publié à côté de l'index (même répertoire, versions.json).
+fn versions_url() -> String {
+    match INDEX_URL.get() {
+        Some(url) => match url.rsplit_once('/') {
+            Some((base, _)) => format!("{}/versions.json", base),
+            None => VERSIONS_URL.to_string(),
+        },
+        None => VERSIONS_URL.to_string(),
+    }
+}
+
+/// Choisit l'index des patchs : --index-url, puis PATCHER_DRFR_INDEX_URL, puis la configuration.
+fn init_index_url(from_args: Option<String>, config: &config::Config) -> Result<(), Box<dyn Error>> {
+    let chosen = from_args
+        .map(|url| (url, "--index-url".to_string()))
+        .or_else(|| std::env::var(INDEX_URL_ENV).ok().filter(|url| !url.is_empty()).map(|url| (url, INDEX_URL_ENV.to_string())))
+        .or_else(|| config.index.url.clone().map(|url| (url, "configuration".to_string())));
+    let Some((url, origin)) = chosen else { return Ok(()) };
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(messages::index_url_invalid(&origin, &url).into());
+    }
+    if url != PATCH_INDEX_URL {
+        eprintln!("{}", messages::index_url_unofficial(&origin, &url));
+    }
+    let _ = INDEX_URL.set(url);
+    Ok(())
+}
+#[derive(Subcommand, Debug)]
+enum ProfileAction {
+    /// Liste les profils et l'état de leur installation.
+    List,
+    /// Crée un profil.
+    Add {
+        /// Nom du profil (lettres, chiffres, '-' et '_')
+        name: String,
+
+        /// Chemin vers le répertoire contenant Deltarune.exe
         #[arg(short = 'd', long = "game-dir", value_name = "REPERTOIRE_JEU", required = true)]
         game_dir: PathBuf,
+
+        /// Plateforme de l'index à utiliser (full, demo) au lieu de la détection automatique
+        #[arg(long = "platform", value_name = "PLATEFORME")]
+        platform: Option<String>,
+    },
+    /// Supprime un profil (son historique d'installation est conservé).
+    Remove {
+        /// Nom du profil
+        name: String,
     },
 }
 
+/// Jeu visé par une commande : donné directement ou via un profil.
+#[derive(Clone)]
+struct Target {
+    game_dir: PathBuf,
+    state_dir: PathBuf,
+    platform: Option<String>,
+}
+
+/// `game_dir` peut être un alias de la configuration (@nom).
+fn resolve_target(game_dir: Option<PathBuf>, profile: Option<String>, state_dir: &Path, config: &config::Config) -> Result<Target, Box<dyn Error>> {
+    match (profile, game_dir) {
+        (Some(name), _) => {
+            let profile = profiles::get_profile(state_dir, &name)?;
+            println!("{}", messages::profile_target(&name, &profile.game_dir));
+            Ok(Target {
+                game_dir: profile.game_dir,
+                state_dir: profiles::profile_state_dir(state_dir, &name),
+                platform: profile.platform,
+            })
+        }
+        (None, Some(game_dir)) => Ok(Target {
+            game_dir: config.resolve_game_dir(game_dir)?,
+            state_dir: state_dir.to_path_buf(),
+            platform: None,
+        }),
+        (None, None) => Err(messages::target_missing().into()),
+    }
+}
 
 type PatchIndex = HashMap<String, PlatformInfo>;
 
-#[derive(Deserialize, Debug)]
+/// Volume d'une archive découpée pour les hébergeurs qui limitent la taille des fichiers.
+#[derive(Serialize, Deserialize, Debug)]
+struct ArchiveVolume {
+    url: String,
+
+    /// CRC32 du volume, vérifié à la fin de son téléchargement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crc: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct PatchDetail {
     #[serde(rename = "patchPath")]
     patch_path: String, 
 
     #[serde(rename = "sourcePath")] 
     source_path: String,
+
+    /// CRC32 du fichier d'origine, tel qu'écrit dans le footer du patch.
+    #[serde(rename = "sourceCrc", default, skip_serializing_if = "Option::is_none")]
+    source_crc: Option<u32>,
+
+    /// CRC32 du fichier produit par le patch.
+    #[serde(rename = "targetCrc", default, skip_serializing_if = "Option::is_none")]
+    target_crc: Option<u32>,
+
+    /// Condition d'application : absente, le patch s'applique toujours.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<PatchCondition>,
+
+    /// Version inscrite dans le chunk GEN8 du fichier d'origine (major.minor.release.build),
+    /// comparée avant le téléchargement pour nommer la version attendue en cas d'incompatibilité.
+    #[serde(rename = "gen8Version", default, skip_serializing_if = "Option::is_none")]
+    gen8_version: Option<String>,
+
+    /// Autres versions connues du fichier d'origine, que ce patch ne couvre pas : un BPS ne
+    /// s'applique qu'à une seule version. Elles servent seulement à nommer la version trouvée.
+    #[serde(rename = "knownSources", default, skip_serializing_if = "Vec::is_empty")]
+    known_sources: Vec<KnownSource>,
 }
 
-#[derive(Deserialize, Debug)]
+/// Version connue d'un fichier du jeu, identifiée par son CRC32.
+#[derive(Serialize, Deserialize, Debug)]
+struct KnownSource {
+    crc: u32,
+
+    /// Nom de la version, affiché à l'utilisateur (ex. « Steam 1.04 sans correctif »).
+    #[serde(default)]
+    label: String,
+}
+
+/// Nom de la version connue (knownSources) qui a ce CRC32, parmi les patchs d'un même fichier.
+fn known_source_label(details: &[&PatchDetail], crc: u32) -> Option<String> {
+    let known = details.iter().flat_map(|detail| &detail.known_sources).find(|known| known.crc == crc)?;
+    Some(if known.label.is_empty() { format!("{:#010X}", crc) } else { known.label.clone() })
+}
+
+/// Condition d'application d'un patch, pour qu'un même index couvre plusieurs éditions du jeu.
+/// Toutes les conditions renseignées doivent être remplies.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct PatchCondition {
+    /// Éditions (clés de plateforme : full, demo...) auxquelles le patch s'applique.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    editions: Vec<String>,
+
+    /// Fichier, relatif au jeu, qui doit être présent (ex. les données d'un chapitre acheté).
+    #[serde(rename = "requiresFile", default, skip_serializing_if = "Option::is_none")]
+    requires_file: Option<String>,
+}
+
+impl PatchCondition {
+    /// Raison pour laquelle le patch ne s'applique pas à ce jeu, ou None si la condition est remplie.
+    fn unmet_reason(&self, game_dir: &Path, platform_key: &str) -> Option<String> {
+        if !self.editions.is_empty() && !self.editions.iter().any(|edition| edition == platform_key) {
+            return Some(messages::condition_edition(platform_key, &self.editions.join(", ")));
+        }
+        if let Some(required) = &self.requires_file {
+            let present = archive::enclosed_path(required).is_some_and(|path| game_dir.join(path).exists());
+            if !present {
+                return Some(messages::condition_missing_file(required));
+            }
+        }
+        None
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct PlatformInfo {
     #[serde(rename = "fileUrl")] 
     file_url: String, 
+
+    /// Taille du ZIP en octets, si l'index la fournit.
+    #[serde(rename = "fileSize", default, skip_serializing_if = "Option::is_none")]
+    file_size: Option<u64>,
+
+    /// CRC32 de l'archive, vérifié pendant le téléchargement.
+    #[serde(rename = "fileCrc", default, skip_serializing_if = "Option::is_none")]
+    file_crc: Option<u32>,
+
+    /// Volumes de l'archive découpée (patch.zip.001, patch.zip.002...), à la place de `fileUrl`
+    /// s'ils sont fournis : téléchargés dans l'ordre puis concaténés avant l'extraction.
+    #[serde(rename = "fileVolumes", default, skip_serializing_if = "Vec::is_empty")]
+    file_volumes: Vec<ArchiveVolume>,
+
+    /// Lien magnet ou URL d'un .torrent de l'archive, utilisé avec --torrent.
+    #[serde(rename = "torrentUrl", default, skip_serializing_if = "Option::is_none")]
+    torrent_url: Option<String>,
+
+    /// Format de l'archive (zip, tar.gz, 7z), utilisé si sa signature n'est pas reconnue.
+    #[serde(rename = "archiveType", default, skip_serializing_if = "Option::is_none")]
+    archive_type: Option<archive::ArchiveFormat>,
+
     patchs: Vec<PatchDetail>,
+
+    /// Opérations exécutées après la copie des fichiers supplémentaires (avec --allow-hooks).
+    #[serde(rename = "postInstall", default, skip_serializing_if = "Vec::is_empty")]
+    post_install: Vec<hooks::PostInstallOp>,
+
+    /// CRC32 attendu de chaque fichier de l'archive, par chemin relatif.
+    #[serde(rename = "fileCrcs", default, skip_serializing_if = "Option::is_none")]
+    file_crcs: Option<HashMap<String, u32>>,
+
+    /// CRC32 des versions officielles connues de l'exécutable du jeu, par chemin relatif (ex. DELTARUNE.exe).
+    #[serde(rename = "knownExecutables", default, skip_serializing_if = "HashMap::is_empty")]
+    known_executables: HashMap<String, Vec<u32>>,
+
+    /// Variantes des fichiers supplémentaires (--variant) ; la première est la variante principale.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<selection::Variant>,
+}
+
+/// Manifeste optionnel à la racine de l'archive, listant le CRC32 de chaque fichier.
+/// Jamais copié dans le répertoire du jeu.
+const ARCHIVE_MANIFEST_NAME: &str = "manifest.json";
+// Sous-répertoire du répertoire de téléchargement où l'archive est extraite.
+const EXTRACT_DIR_NAME: &str = "patch_files";
+
+/// Sous-répertoire de téléchargement où sont extraites les seules métadonnées d'une archive --from-file.
+const ARCHIVE_METADATA_DIR_NAME: &str = "archive_metadata";
+// Marqueur écrit à côté du répertoire d'extraction une fois l'extraction terminée sans erreur.
+// Hors de ce répertoire pour ne jamais être pris pour un fichier supplémentaire de l'archive.
+const EXTRACTION_COMPLETE_MARKER: &str = ".extraction_complete";
+// Nom de l'index dans un répertoire de patchs local (--from-dir) et dans le cache (--offline).
+const LOCAL_INDEX_NAME: &str = "patch_index.json";
+
+/// Patch vérifié pendant la phase en lecture seule, prêt à être appliqué.
+struct PlannedPatch<'a> {
+    detail: &'a PatchDetail,
+    patch_file_path: PathBuf,
+    source_file_path: PathBuf,
+    /// Reprise (--resume) : sauvegarde existante (relative au jeu) et son CRC32,
+    /// à restaurer avant d'appliquer le patch au lieu d'en créer une nouvelle.
+    resume_backup: Option<(PathBuf, Option<u32>)>,
+}
+
+/// Décision prise pour un fichier lors d'une reprise (--resume).
+enum ResumeAction<'a> {
+    /// Déjà patché avant l'interruption : repris tel quel dans le manifeste.
+    AlreadyPatched(ManifestEntry),
+    /// Ni d'origine ni patché (écriture interrompue) : restauré depuis sa sauvegarde puis patché.
+    Restore(PlannedPatch<'a>),
+}
+
+// Chemin relatif avec des '/', pour comparer aux chemins de l'index quel que soit l'OS.
+pub(crate) fn index_style_path(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
-fn unzip_file(archive_path: &Path, target_dir: &Path) -> Result<(), Box<dyn Error>> {
-    println!("Décompression de {:?} vers {:?}...", archive_path, target_dir);
-    let archive_data = std::fs::read(archive_path)?;
-    zip_extract::extract(Cursor::new(archive_data), target_dir, false)?;
+/// Vérifie chaque fichier extrait contre les CRC32 attendus (index ou manifest.json de l'archive).
+/// Sans `strict`, les divergences sont seulement signalées.
+fn verify_extracted_files(extract_dir: &Path, expected_crcs: &HashMap<String, u32>, strict: bool) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::extracted_files_check_header());
+
+    let mut seen = Vec::new();
+    let mut mismatches = Vec::new();
+    let mut unexpected = Vec::new();
+    for entry in WalkDir::new(extract_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let relative_path = path.strip_prefix(extract_dir)?;
+        if is_patch_metadata(relative_path) {
+            continue;
+        }
+        let relative_path = index_style_path(relative_path);
+
+        match expected_crcs.get(&relative_path) {
+            Some(&expected_crc) => {
+                let actual_crc = calculate_crc32(&fs::read(path)?);
+                if actual_crc != expected_crc {
+                    eprintln!("{}", messages::extracted_crc_mismatch(&relative_path, actual_crc, expected_crc));
+                    mismatches.push(relative_path.clone());
+                }
+                seen.push(relative_path);
+            }
+            None => unexpected.push(relative_path),
+        }
+    }
+
+    let missing: Vec<&String> = expected_crcs.keys().filter(|path| !seen.contains(*path)).collect();
+    for path in &missing {
+        eprintln!("{}", messages::extracted_missing(path));
+    }
+    for path in &unexpected {
+        println!("{}", messages::extracted_unexpected(path));
+    }
+
+    if mismatches.is_empty() && missing.is_empty() {
+        println!("{}", messages::extracted_all_ok(seen.len()));
+        return Ok(());
+    }
 
-    println!("Décompression terminée.");
+    let message = messages::extracted_problems(mismatches.len(), missing.len());
+    if strict {
+        return Err(message.into());
+    }
+    eprintln!("{}", messages::continuing_without_strict(&message));
     Ok(())
 }
 
+static CRC32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+
 fn calculate_crc32(data: &[u8]) -> u32 {
-    let algorithm = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
-    algorithm.checksum(data)
+    CRC32.checksum(data)
 }
 
-fn can_apply_bps(source_file_path: &Path, patch_file_path: &Path) -> Result<bool, Box<dyn Error>> {
-    println!("Vérification de la compatibilité du patch {:?} avec le fichier source {:?}...", patch_file_path, source_file_path);
+/// CRC32 d'un flux, lu par blocs : le fichier n'est jamais chargé entièrement en mémoire.
+fn calculate_crc32_stream(mut reader: impl std::io::Read) -> std::io::Result<u32> {
+    let mut digest = CRC32.digest();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(digest.finalize());
+        }
+        digest.update(&buffer[..read]);
+    }
+}
 
-    // Lit le footer du bps pour récupérer le CRC32 prévu (octets -7 à -11)
-    let mut f = File::open(patch_file_path)?;
-    f.seek(SeekFrom::End(-12))?;
-    let mut buf: [u8; 4] = [0; 4];
-    f.read(&mut buf)?;
-    let expected_crc = u32::from_le_bytes(buf);
+fn can_apply_bps(source_file_path: &Path, patch_file_path: &Path) -> Result<bool, Box<dyn Error>> {
+    println!("{}", messages::checking_patch_compatibility(patch_file_path, source_file_path));
 
+    // Lit le footer du bps pour récupérer le CRC32 prévu
+    let expected_crc = bps::read_bps_footer(patch_file_path)?.source_crc;
 
     // Lit le fichier à patcher 
     let source_data = fs::read(source_file_path)
-         .map_err(|e| format!("Erreur lecture source {:?}: {}", source_file_path.display(), e))?;
+         .map_err(|e| messages::source_read_failed(source_file_path, &e))?;
 
     // Calcule le CRC32 réel du fichier source
     let actual_crc = calculate_crc32(&source_data);
     if actual_crc == expected_crc {
-        println!("OK : Le CRC32 du fichier source ({:#010X}) correspond au CRC32 attendu par le patch.", actual_crc);
+        println!("{}", messages::source_crc_matches(actual_crc));
         Ok(true)
     } else {
-        println!("ERREUR : Le CRC32 du fichier source ({:#010X}) ne correspond PAS au CRC32 attendu par le patch ({:#010X}).", actual_crc, expected_crc);
+        println!("{}", messages::source_crc_differs(actual_crc, expected_crc));
         Ok(false)
     }
 }
 
+/// Choisit, parmi plusieurs patchs visant le même fichier (un par version connue du jeu),
+/// celui dont le CRC32 source correspond au fichier réellement présent.
+fn select_patch_candidate<'a>(
+    source_file_path: &Path,
+    candidates: &[&'a PatchDetail],
+    extract_dir: &Path,
+) -> Result<&'a PatchDetail, Box<dyn Error>> {
+    let source_data = fs::read(source_file_path)
+         .map_err(|e| messages::source_read_failed(source_file_path, &e))?;
+    let actual_crc = calculate_crc32(&source_data);
+    println!("{}", messages::source_crc(source_file_path, actual_crc));
+
+    for &detail in candidates {
+        let patch_file_path = extract_dir.join(&detail.patch_path);
+        if !patch_file_path.exists() {
+            eprintln!("{}", messages::candidate_missing(&patch_file_path));
+            continue;
+        }
+
+        let footer = bps::read_bps_footer(&patch_file_path)?;
+        if footer.source_crc == actual_crc {
+            println!("{}", messages::candidate_selected(&detail.patch_path, footer.source_crc, footer.target_crc));
+            return Ok(detail);
+        }
+        println!("{}", messages::candidate_rejected(&detail.patch_path, footer.source_crc));
+    }
+
+    if let Some(label) = known_source_label(candidates, actual_crc) {
+        return Err(messages::source_known_uncovered(source_file_path, &label).into());
+    }
+    Err(messages::no_candidate_matches(candidates.len(), source_file_path, actual_crc).into())
+}
+
+// Sauvegarde existante d'un fichier (chemin relatif au jeu et CRC32 connu), d'après le manifeste
+// de l'installation interrompue ou, à défaut, d'après les fichiers .bak présents.
+fn existing_backup(game_dir: &Path, source_path: &str, pending: Option<&InstallManifest>) -> Option<(PathBuf, Option<u32>)> {
+    let from_manifest = pending
+        .and_then(|m| m.files.iter().find(|entry| entry.path == Path::new(source_path)))
+        .and_then(|entry| Some((entry.backup.clone()?, entry.backup_crc)));
+    if from_manifest.is_some() {
+        return from_manifest;
+    }
+
+    [backup::compressed_backup_path_for(Path::new(source_path)), backup_path_for(Path::new(source_path))]
+        .into_iter()
+        .find(|relative| game_dir.join(relative).is_file())
+        .map(|relative| (relative, None))
+}
+
+/// Examine un fichier lors d'une reprise. Renvoie `None` quand le flux normal s'applique
+/// (fichier d'origine, ou état qu'on ne sait pas réparer et que la vérification signalera).
+fn check_resume<'a>(
+    game_dir: &Path,
+    source_path: &str,
+    candidates: &[&'a PatchDetail],
+    extract_dir: &Path,
+    pending: Option<&InstallManifest>,
+) -> Result<Option<ResumeAction<'a>>, Box<dyn Error>> {
+    let source_file_path = game_dir.join(source_path);
+    if !source_file_path.is_file() {
+        return Ok(None);
+    }
+    let actual_crc = calculate_crc32(&fs::read(&source_file_path)?);
+
+    let mut footers = Vec::new();
+    for &detail in candidates {
+        let patch_file_path = extract_dir.join(&detail.patch_path);
+        if patch_file_path.is_file() {
+            footers.push((detail, bps::read_bps_footer(&patch_file_path)?));
+        }
+    }
+    if footers.iter().any(|(_, footer)| footer.source_crc == actual_crc) {
+        return Ok(None);
+    }
+
+    let backup = existing_backup(game_dir, source_path, pending);
+    if footers.iter().any(|(_, footer)| footer.target_crc == actual_crc) {
+        println!("{}", messages::resume_already_patched(&source_file_path, actual_crc));
+        let (backup, backup_crc) = backup.unzip();
+        return Ok(Some(ResumeAction::AlreadyPatched(ManifestEntry {
+            path: PathBuf::from(source_path),
+            backup,
+            backup_crc: backup_crc.flatten(),
+            crc: Some(actual_crc),
+            ..Default::default()
+        })));
+    }
+
+    let Some((relative_backup, backup_crc)) = backup else {
+        return Ok(None);
+    };
+    let backup_data = backup::read_backup(&game_dir.join(&relative_backup))?;
+    let backup_actual_crc = calculate_crc32(&backup_data);
+    let Some(&(detail, _)) = footers.iter().find(|(_, footer)| footer.source_crc == backup_actual_crc) else {
+        return Ok(None);
+    };
+    println!("{}", messages::resume_inconsistent(&source_file_path, actual_crc, &relative_backup));
+    Ok(Some(ResumeAction::Restore(PlannedPatch {
+        detail,
+        patch_file_path: extract_dir.join(&detail.patch_path),
+        source_file_path,
+        resume_backup: Some((relative_backup, backup_crc)),
+    })))
+}
+
+/// Le fichier d'origine a changé entre la vérification de son CRC32 et l'écriture du fichier patché
+/// (mise à jour du jeu par Steam pendant l'installation, par exemple).
+#[derive(Debug)]
+struct SourceChanged {
+    path: PathBuf,
+    expected: u32,
+    actual: u32,
+}
+
+impl fmt::Display for SourceChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&messages::source_changed(&self.path, self.expected, self.actual))
+    }
+}
+
+impl Error for SourceChanged {}
+
 fn apply_bps(
     source_file_path: &Path,
     patch_file_path: &Path,
     output_file_path: &Path,
 ) -> Result<(), Box<dyn Error>> {
-    let source_data = std::fs::read(&source_file_path)?;
-    let patch_data = std::fs::read(&patch_file_path)?;
+    // Tailles annoncées par l'en-tête (entiers 64 bits) : un source tronqué est refusé avant d'être lu.
+    let (expected_source_size, target_size) = bps::read_bps_sizes(patch_file_path)?;
+    let source_size = fs::metadata(source_file_path)?.len();
+    if source_size != expected_source_size {
+        return Err(messages::source_size_mismatch(source_file_path, source_size, patch_file_path, expected_source_size).into());
+    }
+    // flips travaille en mémoire : sur une plateforme 32 bits, un fichier de plus de 4 Go
+    // ne tient pas dans un tampon et serait tronqué ou ferait échouer l'allocation.
+    if usize::try_from(source_size.max(target_size)).is_err() {
+        return Err(messages::file_too_large(source_file_path, usize::MAX).into());
+    }
+
+    let source_data = std::fs::read(source_file_path)?;
+    let patch_data = std::fs::read(patch_file_path)?;
+
+    // Un patch dont les CRC source et cible sont égaux ne change rien : probablement le mauvais patch.
+    let footer = bps::read_bps_footer(patch_file_path)?;
+    let patch_changes_file = footer.source_crc != footer.target_crc;
+    if !patch_changes_file {
+        eprintln!("{}", messages::patch_changes_nothing(patch_file_path, footer.source_crc));
+    }
+    // Le CRC32 du fichier a été vérifié lors du choix du patch : s'il diffère maintenant, le fichier
+    // a changé depuis, et le patch produirait un résultat faux.
+    let source_crc = calculate_crc32(&source_data);
+    if source_crc != footer.source_crc {
+        return Err(SourceChanged { path: source_file_path.to_path_buf(), expected: footer.source_crc, actual: source_crc }.into());
+    }
 
     let output = flips::BpsPatch::new(patch_data)
         .apply(source_data)
-        .map_err(|e| format!("Erreur lors de l'application du patch BPS: {}", e.to_string()))?;
-    std::fs::write(&output_file_path, output.to_bytes())?;
+        .map_err(|e| messages::bps_apply_failed(&e))?;
+    let output_data = output.to_bytes();
+
+    // Contrôle de cohérence avant d'écrire quoi que ce soit : le résultat doit avoir changé
+    // si le patch l'annonçait, et correspondre au CRC cible du footer.
+    let output_crc = calculate_crc32(&output_data);
+    if patch_changes_file && output_crc == source_crc {
+        return Err(messages::patch_output_unchanged(patch_file_path, source_file_path, output_crc).into());
+    }
+    if output_crc != footer.target_crc {
+        return Err(messages::patch_output_crc_mismatch(patch_file_path, output_crc, footer.target_crc).into());
+    }
+    // Dernière vérification juste avant l'écriture : le fichier a pu changer pendant l'application du patch.
+    let current_crc = calculate_crc32_stream(File::open(source_file_path)?)?;
+    if current_crc != footer.source_crc {
+        return Err(SourceChanged { path: source_file_path.to_path_buf(), expected: footer.source_crc, actual: current_crc }.into());
+    }
+    fsutil::write_atomic(output_file_path, &output_data)?;
 
     Ok(())
 }
 
+/// Affiche (-v) la taille d'un patch appliqué face aux tailles source et cible de son en-tête.
+/// Un ratio patch/cible élevé trahit un delta mal calculé (patch généré contre le mauvais fichier).
+/// Renvoie (taille du patch, taille cible) pour le total de fin d'installation.
+fn log_patch_stats(patch_file_path: &Path) -> Option<(u64, u64)> {
+    let patch_size = fs::metadata(patch_file_path).ok()?.len();
+    let (source_size, target_size) = bps::read_bps_sizes(patch_file_path).ok()?;
+    let ratio = if target_size > 0 { patch_size as f64 * 100.0 / target_size as f64 } else { 0.0 };
+    tracing::info!(
+        "{:?} : patch de {}, source de {}, cible de {} (ratio patch/cible {:.1} %).",
+        patch_file_path, format_size(patch_size), format_size(source_size), format_size(target_size), ratio
+    );
+    Some((patch_size, target_size))
+}
+
 fn select_platform(game_dir: &Path) -> String {
     let chapter3_data = game_dir.join("chapter3_windows/data.win");
     let chapter2_data = game_dir.join("chapter2_windows/data.win");
     if chapter3_data .exists() && chapter3_data.is_file() {
-        println!("Jeu complet détecté. Téléchargement du patch.");
+        println!("{}", messages::platform_full_detected());
         "full".to_string()
     } else if chapter2_data .exists() && chapter2_data.is_file() {
-        println!("Demo détectée ! Téléchargement du patch demo.");
+        println!("{}", messages::platform_demo_detected());
         "demo".to_string()
     } else {
-        println!("Le dossier du chapitre 2 n'a pas été détecté !");
+        println!("{}", messages::platform_none_detected());
         "none".to_string()
     }
 }
 
-fn copy_extra_files(extract_dir: &Path, game_dir: &Path) -> Result<(), Box<dyn Error>> {
-    println!("\n--- Copie des fichiers supplémentaires (non-BPS) ---\n");
+// Fichiers propres à l'archive ou au répertoire de patchs, jamais copiés dans le jeu :
+// manifeste d'archive, index et dépôt git d'un répertoire local (--from-dir).
+pub(crate) fn is_patch_metadata(relative_path: &Path) -> bool {
+    relative_path == Path::new(ARCHIVE_MANIFEST_NAME)
+        || relative_path == Path::new(LOCAL_INDEX_NAME)
+        || relative_path.starts_with(".git")
+}
+
+// Fichiers de l'archive à copier tels quels : ni patch BPS, ni métadonnées, ni exclus par la sélection.
+fn is_extra_file(extract_dir: &Path, path_in_zip: &Path, selection: &Selection) -> bool {
+    path_in_zip.is_file()
+        && path_in_zip.extension().is_none_or(|ext| ext != "bps")
+        && path_in_zip.strip_prefix(extract_dir).is_ok_and(|p| !is_patch_metadata(p) && selection.keeps_extra(p))
+}
+
+/// Refuse une archive qui déverserait un nombre anormal de fichiers dans le jeu (archive
+/// corrompue ou malveillante). Compté avant toute modification, pour ne rien installer à moitié.
+fn check_extra_count(extract_dir: &Path, selection: &Selection, max_extras: usize) -> Result<(), Box<dyn Error>> {
+    let count = WalkDir::new(extract_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_extra_file(extract_dir, e.path(), selection))
+        .count();
+    if count > max_extras {
+        return Err(messages::too_many_extras(count, max_extras).into());
+    }
+    Ok(())
+}
+
+/// Taille totale des fichiers supplémentaires, pour pondérer la progression.
+fn extra_files_size(extract_dir: &Path, selection: &Selection) -> u64 {
+    WalkDir::new(extract_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_extra_file(extract_dir, e.path(), selection))
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn copy_extra_files(extract_dir: &Path, game_dir: &Path, selection: &Selection, progress: &mut Progress) -> Result<Vec<ManifestEntry>, Box<dyn Error>> {
+    println!("{}", messages::extras_header());
+
+    let mut entries = Vec::new();
+    progress.start_phase(Phase::Extras, extra_files_size(extract_dir, selection));
 
     for entry_result in WalkDir::new(extract_dir).into_iter().filter_map(|e| e.ok()) {
         let path_in_zip = entry_result.path();
@@ -138,296 +1271,2418 @@ fn copy_extra_files(extract_dir: &Path, game_dir: &Path) -> Result<(), Box<dyn E
             continue;
         }
 
-        if path_in_zip.extension().map_or(false, |ext| ext == "bps") {
-            continue;
+        if path_in_zip.extension().is_some_and(|ext| ext == "bps") {
+            continue;
+        }
+
+        let relative_path = match path_in_zip.strip_prefix(extract_dir) {
+            Ok(p) => p,
+            Err(_) => {
+                eprintln!("{}", messages::extra_relative_path_unknown(path_in_zip));
+                continue;
+            }
+        };
+
+        if is_patch_metadata(relative_path) {
+            continue;
+        }
+        if selection.patchignores_extra(relative_path) {
+            println!("{}", messages::patchignore_skipped(&index_style_path(relative_path)));
+            continue;
+        }
+        if !selection.keeps_extra(relative_path) {
+            continue;
+        }
+
+        let dest_path = game_dir.join(relative_path);
+        println!("{}", messages::extra_copying(path_in_zip, &dest_path));
+
+        if let Some(dest_parent) = dest_path.parent() {
+            if !dest_parent.exists() {
+                println!("{}", messages::creating_parent_dir(dest_parent));
+                fs::create_dir_all(dest_parent)?; 
+            }
+        } else {
+            eprintln!("{}", messages::extra_parent_unknown(&dest_path));
+            continue;
+        }
+
+        // Sauvegarde par copie, comme pour les fichiers patchés : l'original reste en place
+        // jusqu'à ce que la nouvelle version le remplace d'un seul renommage.
+        let mut backup = None;
+        if dest_path.exists() && backup_path_for(&dest_path).is_file() {
+            // Installation précédente (--incremental) : la sauvegarde existante contient la
+            // version d'origine, la remplacer y mettrait la version patchée.
+            println!("{}", messages::backup_already_present(&backup_path_for(&dest_path)));
+            backup = Some(backup_path_for(relative_path));
+        } else if dest_path.exists() {
+            println!("{}", messages::existing_file_backup(&dest_path, &backup_path_for(&dest_path)));
+            match backup::create_linked_backup(&dest_path) {
+                Ok(backup_path) => println!("{}", messages::backup_path_created(&backup_path)),
+                Err(e) => {
+                    eprintln!("{}", messages::extra_backup_failed(&dest_path, &e));
+                    continue;
+                }
+            }
+            backup = Some(backup_path_for(relative_path));
+        }
+
+        let temp_path = fsutil::temp_path_for(&dest_path);
+        let copy_result = fs::copy(path_in_zip, &temp_path).and_then(|copied| {
+            fsutil::move_file(&temp_path, &dest_path)?;
+            Ok(copied)
+        });
+        match copy_result {
+            Ok(copied) => {
+                progress.advance(copied);
+                println!("{}", messages::extra_copied(&dest_path));
+                entries.push(ManifestEntry {
+                    path: relative_path.to_path_buf(),
+                    created: backup.is_none(),
+                    backup,
+                    crc: File::open(&dest_path).ok().and_then(|file| calculate_crc32_stream(file).ok()),
+                    ..Default::default()
+                });
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp_path);
+                eprintln!("{}", messages::extra_copy_failed(path_in_zip, &dest_path, &e));
+                continue; 
+            }
+        }
+    }
+
+    progress.finish_phase();
+    println!("{}", messages::extras_done());
+    Ok(entries)
+}
+
+/// AppID Steam de DELTARUNE (jeu complet) et de sa démo Chapter 1&2.
+const DELTARUNE_STEAM_APP_IDS: [&str; 2] = ["1671210", "1690940"];
+/// Fichiers propres aux installations Steam (et aux copies qui en sont faites).
+const STEAM_MARKERS: [&str; 4] = ["steam_appid.txt", "steam_api.dll", "steam_api64.dll", "libsteam_api.so"];
+/// Taille minimale plausible du data.win d'un chapitre (plusieurs dizaines de Mo en réalité).
+const MIN_CHAPTER_DATA_SIZE: u64 = 1024 * 1024;
+
+/// Vérifie que `game_dir` contient bien DELTARUNE avant de télécharger quoi que ce soit :
+/// un autre jeu GameMaker (même structure, même steam_api.dll) ferait échouer tous les patchs.
+/// Seule une installation Steam, dont les fichiers sont connus, est refusée. Ailleurs (itch.io,
+/// copie, portage), un exécutable renommé ou un data.win inhabituel n'est qu'un avertissement :
+/// renvoie alors faux.
+fn check_is_deltarune(game_dir: &Path, platform_key: &str) -> Result<bool, Box<dyn Error>> {
+    // L'AppID d'un autre jeu ne laisse aucun doute, quelle que soit l'installation.
+    if let Ok(app_id) = fs::read_to_string(game_dir.join("steam_appid.txt"))
+        && !DELTARUNE_STEAM_APP_IDS.contains(&app_id.trim())
+    {
+        return Err(messages::not_deltarune(&messages::not_deltarune_app_id(app_id.trim())).into());
+    }
+
+    let mut reasons = Vec::new();
+    let has_executable = fs::read_dir(game_dir)?.filter_map(|e| e.ok()).any(|entry| {
+        Path::new(&entry.file_name()).file_stem().is_some_and(|stem| stem.eq_ignore_ascii_case("deltarune"))
+    });
+    if !has_executable {
+        reasons.push(messages::not_deltarune_no_executable());
+    }
+    let chapter_data = match platform_key {
+        "demo" => "chapter2_windows/data.win",
+        _ => "chapter3_windows/data.win",
+    };
+    let chapter_data_path = game_dir.join(chapter_data);
+    let size = fs::metadata(&chapter_data_path).map(|m| m.len()).unwrap_or(0);
+    if size < MIN_CHAPTER_DATA_SIZE || !gamemaker::is_data_file(&chapter_data_path) {
+        reasons.push(messages::not_deltarune_data(chapter_data, &format_size(size)));
+    }
+    if reasons.is_empty() {
+        return Ok(true);
+    }
+
+    let reason = reasons.join(" ; ");
+    if STEAM_MARKERS.iter().any(|marker| game_dir.join(marker).is_file()) {
+        return Err(messages::not_deltarune(&reason).into());
+    }
+    eprintln!("{}", messages::not_deltarune_warning(&reason));
+    Ok(false)
+}
+
+/// Compare l'exécutable du jeu aux versions officielles listées par l'index.
+/// Simple avertissement : une nouvelle version officielle peut sortir avant la mise à jour de l'index.
+fn check_known_executables(game_dir: &Path, known_executables: &HashMap<String, Vec<u32>>) -> Result<(), Box<dyn Error>> {
+    for (relative_path, known_crcs) in known_executables {
+        let exe_path = game_dir.join(relative_path);
+        if !exe_path.is_file() {
+            continue;
+        }
+        let actual_crc = calculate_crc32(&fs::read(&exe_path)?);
+        if known_crcs.contains(&actual_crc) {
+            println!("{}", messages::executable_known(relative_path, actual_crc));
+        } else {
+            eprintln!("{}", messages::executable_unknown(relative_path, actual_crc));
+        }
+    }
+    Ok(())
+}
+
+/// Vérifie l'espace libre du répertoire temporaire (ZIP + extraction) et du répertoire du jeu
+/// (sauvegardes + patchs), avec un message distinct pour chacun. Renvoie faux s'il en manque.
+/// Un besoin nul n'est pas vérifié ; un volume dont l'espace est inconnu est considéré suffisant.
+fn check_free_space(temp_dir: &Path, temp_needed: u64, game_dir: &Path, game_needed: u64) -> bool {
+    let temp_volume = if temp_needed > 0 { fsutil::volume_of(temp_dir) } else { None };
+    let game_volume = if game_needed > 0 { fsutil::volume_of(game_dir) } else { None };
+
+    // Même volume : les deux besoins s'additionnent.
+    if let (Some((temp_mount, available)), Some((game_mount, _))) = (&temp_volume, &game_volume)
+        && temp_mount == game_mount
+    {
+        let needed = temp_needed + game_needed;
+        if needed > *available {
+            eprintln!("{}", messages::space_low_shared(temp_mount, &format_size(needed), &format_size(*available)));
+            return false;
+        }
+        return true;
+    }
+
+    let mut enough = true;
+    if let Some((mount, available)) = temp_volume
+        && temp_needed > available
+    {
+        eprintln!("{}", messages::space_low_temp(temp_dir, &mount, &format_size(temp_needed), &format_size(available)));
+        eprintln!("{}", messages::temp_dir_hint(game_dir));
+        enough = false;
+    }
+    if let Some((mount, available)) = game_volume
+        && game_needed > available
+    {
+        eprintln!("{}", messages::space_low_game(game_dir, &mount, &format_size(game_needed), &format_size(available)));
+        enough = false;
+    }
+    enough
+}
+
+/// Chemin absolu d'un répertoire de jeu déjà validé (check_game_dir), liens symboliques résolus :
+/// la suite ne dépend plus du répertoire courant et les messages montrent le chemin complet.
+fn absolute_game_dir(game_dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    fs::canonicalize(game_dir)
+        .map_err(|e| messages::game_dir_unresolvable(game_dir, &e).into())
+}
+
+/// Vérifie que `game_dir` est un répertoire accessible, en distinguant un chemin inexistant
+/// d'un chemin existant mais inaccessible (droits, ou bac à sable Flatpak/Snap).
+fn check_game_dir(game_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let sandbox = sandbox::detect();
+    let hint = |path: &Path| sandbox.map(|s| format!("\n{}", sandbox::access_hint(s, path))).unwrap_or_default();
+
+    match fs::metadata(game_dir) {
+        Ok(metadata) if metadata.is_dir() => match fs::read_dir(game_dir) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(messages::game_dir_unreadable(game_dir, &e, &hint(game_dir)).into()),
+        },
+        Ok(_) => Err(messages::game_dir_is_file(game_dir).into()),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            Err(messages::game_dir_permission_denied(game_dir, &hint(game_dir)).into())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            // Dans un bac à sable, un dossier non partagé apparaît simplement comme inexistant.
+            match sandbox {
+                Some(s) => Err(messages::game_dir_not_found_sandboxed(game_dir, &sandbox::access_hint(s, game_dir)).into()),
+                None => Err(messages::game_dir_not_found(game_dir).into()),
+            }
+        }
+        Err(e) => Err(messages::game_dir_inaccessible(game_dir, &e, &hint(game_dir)).into()),
+    }
+}
+
+/// Copie le jeu dans `output_dir`, qui devient le répertoire patché : l'installation d'origine
+/// n'est pas modifiée et la copie peut être empaquetée (AppImage, image disque, conteneur).
+/// `full_copy` (--copy-game) : copie intégrale plutôt que par reflink, pour une sortie qui ne
+/// partage aucune donnée avec l'original, même sur Btrfs ou XFS.
+fn prepare_output_dir(game_dir: &Path, output_dir: &Path, full_copy: bool) -> Result<(), Box<dyn Error>> {
+    check_game_dir(game_dir)?;
+    if fs::read_dir(output_dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(messages::output_dir_not_empty(output_dir).into());
+    }
+    fs::create_dir_all(output_dir)
+        .map_err(|e| messages::output_dir_create_failed(output_dir, &e))?;
+    // Une sortie à l'intérieur du jeu se copierait elle-même indéfiniment.
+    if fs::canonicalize(output_dir)?.starts_with(fs::canonicalize(game_dir)?) {
+        return Err(messages::output_dir_inside_game(output_dir).into());
+    }
+
+    println!("{}", messages::copying_game(game_dir, output_dir));
+    let copied = fsutil::copy_tree(game_dir, output_dir, !full_copy)?;
+    println!("{}", messages::game_copied(copied));
+    Ok(())
+}
+
+// Répertoire voisin de `game_dir` (même parent, donc a priori même système de fichiers) : DELTARUNE.drfr-new...
+fn sibling_dir(game_dir: &Path, suffix: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let name = game_dir
+        .file_name()
+        .ok_or_else(|| messages::game_dir_no_parent(game_dir))?;
+    let mut sibling = name.to_os_string();
+    sibling.push(".");
+    sibling.push(suffix);
+    Ok(game_dir.with_file_name(sibling))
+}
+
+/// --atomic-swap : le jeu est copié à côté de lui-même, la copie est patchée et vérifiée comme une
+/// installation normale, puis les deux répertoires sont échangés par deux renommages. En cas d'échec
+/// avant l'échange, seule la copie est supprimée ; le jeu n'a jamais été touché.
+fn run_atomic_swap_install(game_dir: &Path, state_dir: &Path, options: &InstallOptions) -> Result<(), Box<dyn Error>> {
+    check_game_dir(game_dir)?;
+    let game_dir = &fs::canonicalize(game_dir)?;
+    let staging_dir = sibling_dir(game_dir, "drfr-new")?;
+    let previous_dir = sibling_dir(game_dir, "drfr-old")?;
+    if previous_dir.exists() {
+        return Err(messages::swap_leftover(&previous_dir, game_dir).into());
+    }
+    if staging_dir.exists() {
+        println!("{}", messages::swap_removing_staging(&staging_dir));
+        fs::remove_dir_all(&staging_dir)?;
+    }
+
+    prepare_output_dir(game_dir, &staging_dir, false)?;
+    let staging_dir = fs::canonicalize(&staging_dir)?;
+    let discard_staging = || {
+        manifest::clear_pending(state_dir, &staging_dir);
+        if let Err(e) = fs::remove_dir_all(&staging_dir) {
+            eprintln!("{}", messages::swap_staging_remove_failed(&staging_dir, &e));
+        }
+    };
+    if let Err(e) = run_install_process(&staging_dir, state_dir, options) {
+        eprintln!("{}", messages::swap_install_failed(&staging_dir));
+        discard_staging();
+        return Err(e);
+    }
+    // Installation refusée à l'aperçu, ou déjà à jour : rien à échanger.
+    if manifest::latest_manifest(state_dir, &staging_dir)?.is_none() {
+        discard_staging();
+        return Ok(());
+    }
+
+    let running = process::find_running_game(game_dir);
+    if !running.is_empty() {
+        eprintln!("{}", messages::swap_game_running(&process::describe(&running)));
+        let _ = manifest::clear_history(state_dir, &staging_dir);
+        discard_staging();
+        return Err(messages::game_running(&process::describe(&running)).into());
+    }
+
+    println!("{}", messages::swapping(game_dir, &staging_dir));
+    if let Err(e) = fs::rename(game_dir, &previous_dir) {
+        let _ = manifest::clear_history(state_dir, &staging_dir);
+        discard_staging();
+        if e.kind() == std::io::ErrorKind::CrossesDevices {
+            return Err(messages::swap_mount_point(game_dir).into());
+        }
+        return Err(messages::swap_rename_failed(game_dir, &e).into());
+    }
+    if let Err(e) = fs::rename(&staging_dir, game_dir) {
+        // Remet l'original en place : l'installation n'a alors rien changé.
+        if let Err(restore_err) = fs::rename(&previous_dir, game_dir) {
+            return Err(messages::swap_restore_failed(&e, &restore_err, &previous_dir, game_dir).into());
+        }
+        let _ = manifest::clear_history(state_dir, &staging_dir);
+        discard_staging();
+        return Err(messages::swap_move_failed(&e).into());
+    }
+
+    // Les manifestes écrits pendant l'installation désignent la copie : ils passent au jeu.
+    manifest::relocate_history(state_dir, &staging_dir, game_dir)?;
+    println!("{}", messages::swap_done(&previous_dir));
+    if let Err(e) = fs::remove_dir_all(&previous_dir) {
+        eprintln!("{}", messages::swap_old_remove_failed(&previous_dir, &e));
+    }
+    Ok(())
+}
+
+/// Vérifie qu'on peut écrire dans `dir` avant de commencer à le modifier.
+/// Seules les commandes qui modifient le jeu l'appellent : les vérifications
+/// (CRC, détection de plateforme...) n'ont besoin que d'un accès en lecture.
+fn ensure_writable(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let probe_path = dir.join(".patcher_drfr_write_test");
+    match File::create(&probe_path) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe_path);
+            Ok(())
+        }
+        Err(e) => Err(messages::not_writable(dir, &e).into()),
+    }
+}
+
+/// Entrée de l'index pour `platform_key`, en distinguant un index vide (problème côté serveur)
+/// d'une plateforme absente (les plateformes disponibles sont alors listées).
+fn platform_info_for<'a>(patch_index: &'a PatchIndex, platform_key: &str) -> Result<&'a PlatformInfo, Box<dyn Error>> {
+    if patch_index.is_empty() {
+        return Err(messages::index_empty().into());
+    }
+    patch_index.get(platform_key).ok_or_else(|| {
+        let mut available: Vec<&str> = patch_index.keys().map(String::as_str).collect();
+        available.sort();
+        messages::platform_not_in_index(platform_key, &available.join(", ")).into()
+    })
+}
+
+/// Lit l'index d'un répertoire de patchs local et affiche sa révision git s'il s'agit d'un clone.
+fn read_local_index(patches_dir: &Path) -> Result<PatchIndex, Box<dyn Error>> {
+    if !patches_dir.is_dir() {
+        return Err(messages::patches_dir_invalid(patches_dir).into());
+    }
+    println!("{}", messages::installing_from_dir(patches_dir));
+    if let Some(revision) = gitrev::current_revision(patches_dir) {
+        println!("{}", messages::patches_git_revision(&revision.describe()));
+    }
+    read_index_file(&patches_dir.join(LOCAL_INDEX_NAME))
+}
+
+fn read_index_file(index_path: &Path) -> Result<PatchIndex, Box<dyn Error>> {
+    let data = fs::read(index_path)
+        .map_err(|e| messages::index_read_failed(index_path, &e))?;
+    let index: PatchIndex = serde_json::from_slice(&data)
+        .map_err(|e| messages::index_invalid(index_path, &e))?;
+    Ok(index)
+}
+
+/// Index d'une installation --from-file : celui donné par --local-index, sinon celui de l'archive,
+/// sinon celui mis en cache par la dernière installation en ligne.
+fn read_archive_index(archive_path: &Path, local_index: Option<&Path>, download_dir: &Path) -> Result<PatchIndex, Box<dyn Error>> {
+    if !archive_path.is_file() {
+        return Err(messages::archive_not_found(archive_path).into());
+    }
+    println!("{}", messages::installing_from_archive(archive_path));
+    if let Some(index_path) = local_index {
+        println!("{}", messages::local_index(index_path));
+        return read_index_file(index_path);
+    }
+
+    // Seules les métadonnées de l'archive (index, manifeste) sont extraites à cette étape.
+    let metadata_dir = download_dir.join(ARCHIVE_METADATA_DIR_NAME);
+    if metadata_dir.exists() {
+        fs::remove_dir_all(&metadata_dir)?;
+    }
+    fs::create_dir_all(&metadata_dir)?;
+    let format = archive::resolve_format(archive_path, None)?;
+    archive::extract_archive(archive_path, format, &metadata_dir, &HashSet::new(), &Selection::new(Vec::new(), true, None))?;
+    let index_path = metadata_dir.join(LOCAL_INDEX_NAME);
+    if index_path.is_file() {
+        println!("{}", messages::index_from_archive());
+        return read_index_file(&index_path);
+    }
+    eprintln!("{}", messages::archive_without_index(LOCAL_INDEX_NAME));
+    read_cached_index(download_dir)
+}
+
+fn fetch_patch_index(url: &str) -> Result<PatchIndex, Box<dyn Error>> {
+    println!("{}", messages::fetching_index(url));
+    let index: PatchIndex = net::get_json(url)?;
+    println!("{}", messages::index_fetched());
+    Ok(index)
+}
+
+/// Demande la taille d'un fichier distant sans le télécharger.
+/// Essaie d'abord une requête HEAD, puis un GET dont on ne lit que les en-têtes
+/// pour les serveurs qui ne gèrent pas HEAD.
+fn fetch_download_size(url: &str, url_from_user: bool) -> Option<u64> {
+    fn content_length(response: &reqwest::blocking::Response) -> Option<u64> {
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    let client = if url_from_user { net::user_client() } else { net::client() }.ok()?;
+    let started = std::time::Instant::now();
+    let head = client.head(url).send();
+    net::trace_result("HEAD", url, &head, started);
+    if let Ok(response) = head
+        && response.status().is_success()
+        && let Some(size) = content_length(&response)
+    {
+        return Some(size);
+    }
+
+    // Le corps n'est jamais lu : la connexion est fermée quand la réponse est libérée.
+    let started = std::time::Instant::now();
+    let response = client.get(url).send();
+    net::trace_result("GET", url, &response, started);
+    let response = response.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    content_length(&response)
+}
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f >= KIB * KIB * KIB {
+        messages::size_gib(bytes_f / (KIB * KIB * KIB))
+    } else if bytes_f >= KIB * KIB {
+        messages::size_mib(bytes_f / (KIB * KIB))
+    } else if bytes_f >= KIB {
+        messages::size_kib(bytes_f / KIB)
+    } else {
+        messages::size_bytes(bytes)
+    }
+}
+
+/// Pose une question oui/non sur le terminal. Non par défaut.
+fn confirm(question: &str) -> Result<bool, Box<dyn Error>> {
+    output::prompt(format_args!("{} {} ", question, messages::confirm_suffix()))?;
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer)? == 0 {
+        output::prompt(format_args!("\n"))?;
+        return Err(messages::no_answer_possible().into());
+    }
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "o" | "oui" | "y" | "yes"))
+}
+
+/// `url_from_user` : URL fournie explicitement (--mirror), dont les redirections ne sont pas filtrées.
+/// Le téléchargement ne peut pas dépasser `deadline` : le temps restant sert de délai à la requête.
+/// Les blocs de `chunk_size` octets sont écrits directement dans le fichier et passent au CRC32
+/// au fil de l'eau : le CRC de l'archive, renvoyé, est connu sans relire le fichier.
+fn download_file(
+    url: &str,
+    output_path: &Path,
+    url_from_user: bool,
+    chunk_size: usize,
+    deadline: &Deadline,
+    progress: &mut Progress,
+) -> Result<u32, Box<dyn Error>> {
+    println!("{}", messages::downloading(url, output_path));
+    // Le fichier partiel d'un téléchargement interrompu est repris là où il s'était arrêté
+    // (clean-temp, ou le nettoyage au démarrage s'il est trop ancien, le supprime).
+    let part_path = fsutil::partial_path_for(output_path);
+    let mut resume_from = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+    let mut response = loop {
+        match net::with_retry(|| net::get_with_timeout(url, url_from_user, deadline.remaining(), resume_from)) {
+            // Fichier partiel déjà complet ou plus long que le fichier publié : on repart de zéro.
+            Err(net::NetError::Status { status, .. })
+                if resume_from > 0 && status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE =>
+            {
+                resume_from = 0;
+            }
+            Err(e) => {
+                deadline.check()?;
+                return Err(e.into());
+            }
+            Ok(response) => break response,
+        }
+    };
+    let resumed = resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut digest = CRC32.digest();
+    let mut output_file = if resumed {
+        println!("{}", messages::download_resuming(&format_size(resume_from)));
+        // Le CRC32 couvre toute l'archive : la partie déjà reçue est relue une fois.
+        let mut existing = File::open(&part_path)?;
+        let mut buffer = vec![0; chunk_size];
+        loop {
+            let read = existing.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            digest.update(&buffer[..read]);
+        }
+        fs::OpenOptions::new().append(true).open(&part_path)?
+    } else {
+        if resume_from > 0 {
+            println!("{}", messages::download_restarting());
+        }
+        File::create(&part_path)?
+    };
+    let already = if resumed { resume_from } else { 0 };
+    progress.start_phase(Phase::Download, response.content_length().map_or(0, |len| len + already));
+    progress.advance(already);
+
+    let mut buffer = vec![0; chunk_size];
+    loop {
+        let read = match response.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                deadline.check()?;
+                return Err(net::NetError::Body { url: url.to_string(), source: e.into() }.into());
+            }
+        };
+        output_file.write_all(&buffer[..read])?;
+        digest.update(&buffer[..read]);
+        progress.advance(read as u64);
+    }
+    output_file.flush()?;
+    fsutil::sync_if_durable(&output_file)
+        .map_err(|e| messages::sync_failed(output_path, &e))?;
+    drop(output_file);
+    fsutil::move_file(&part_path, output_path)?;
+    progress.finish_phase();
+
+    println!("{}", messages::download_done(url));
+    Ok(digest.finalize())
+}
+
+/// Télécharge les volumes d'une archive découpée puis les concatène dans `archive_path`,
+/// et renvoie le CRC32 de l'archive réunie. Un volume en échec n'interrompt pas les suivants :
+/// tous les volumes manquants ou corrompus sont listés ensemble.
+fn download_volumes(
+    volumes: &[ArchiveVolume],
+    archive_path: &Path,
+    options: &InstallOptions,
+    deadline: &Deadline,
+    progress: &mut Progress,
+) -> Result<u32, Box<dyn Error>> {
+    let mut volume_paths = Vec::new();
+    let mut missing = Vec::new();
+    for (i, volume) in volumes.iter().enumerate() {
+        let mut volume_name = archive_path.as_os_str().to_os_string();
+        volume_name.push(format!(".{:03}", i + 1));
+        let volume_path = PathBuf::from(volume_name);
+        println!("{}", messages::volume_header(i + 1, volumes.len()));
+        let result = download_file(&volume.url, &volume_path, false, options.download_chunk_size, deadline, progress)
+            .and_then(|crc| match volume.crc {
+                Some(expected) if expected != crc => {
+                    Err(messages::volume_corrupted(crc, expected).into())
+                }
+                _ => Ok(()),
+            });
+        match result {
+            Ok(()) => volume_paths.push(volume_path),
+            Err(e) if e.downcast_ref::<deadline::DeadlineExceeded>().is_some() => return Err(e),
+            Err(e) => {
+                eprintln!("{}", messages::volume_failed(i + 1, &volume.url, &e));
+                let _ = fs::remove_file(&volume_path);
+                missing.push(messages::volume_missing(i + 1, &volume.url));
+            }
+        }
+    }
+    if !missing.is_empty() {
+        for path in &volume_paths {
+            let _ = fs::remove_file(path);
+        }
+        return Err(messages::volumes_incomplete(missing.len(), volumes.len(), &missing.join("\n")).into());
+    }
+
+    // Chaque volume est supprimé dès qu'il est recopié : l'espace occupé ne double pas.
+    let mut output_file = File::create(archive_path)?;
+    let mut digest = CRC32.digest();
+    let mut buffer = vec![0; options.download_chunk_size];
+    for path in &volume_paths {
+        let mut volume_file = File::open(path)?;
+        loop {
+            let read = volume_file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            output_file.write_all(&buffer[..read])?;
+            digest.update(&buffer[..read]);
+        }
+        fs::remove_file(path)?;
+    }
+    output_file.flush()?;
+    fsutil::sync_if_durable(&output_file)?;
+    println!("{}", messages::volumes_joined(volumes.len(), archive_path));
+    Ok(digest.finalize())
+}
+
+/// --deadline dépassé pendant la phase de modification : les fichiers déjà modifiés sont restaurés
+/// en passant par le manifeste, comme pour `rollback-last`.
+fn abort_on_deadline(
+    game_dir: &Path,
+    state_dir: &Path,
+    install_manifest: &InstallManifest,
+    deadline: &Deadline,
+) -> Result<(), Box<dyn Error>> {
+    let exceeded = deadline.check().unwrap_err();
+    eprintln!("{}", messages::deadline_rolling_back(&exceeded));
+    rollback_partial_install(game_dir, state_dir, install_manifest)?;
+    Err(exceeded.into())
+}
+
+// Annule une installation arrêtée en cours de route : son manifeste partiel est enregistré
+// puis annulé comme le ferait `rollback-last`.
+fn rollback_partial_install(game_dir: &Path, state_dir: &Path, install_manifest: &InstallManifest) -> Result<(), Box<dyn Error>> {
+    if !install_manifest.files.is_empty() {
+        manifest::save_manifest(state_dir, install_manifest)?;
+        run_rollback_last(game_dir, state_dir)?;
+    }
+    manifest::clear_pending(state_dir, game_dir);
+    Ok(())
+}
+
+/// Répertoire de téléchargement et d'extraction (dans --temp-dir s'il est fourni). Il est conservé
+/// après l'installation : --use-cache y retrouve l'archive extraite et l'index qui la décrit.
+fn download_dir_for(temp_dir: Option<&Path>) -> PathBuf {
+    match temp_dir {
+        Some(temp_dir) => temp_dir.join("patcher_drfr"),
+        None => PathBuf::from("/tmp/patcher_drfr/"),
+    }
+}
+
+/// Archive extraite lors d'une installation précédente, si elle contient tous les patchs attendus.
+/// Son contenu est ensuite vérifié comme une archive fraîchement extraite (fileCrcs).
+/// Hors ligne, un cache absent ou incomplet est une erreur ; sinon on retélécharge.
+fn cached_extract_dir(download_dir: &Path, patchs: &[&PatchDetail], offline: bool) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let extract_dir = download_dir.join(EXTRACT_DIR_NAME);
+    let problem = if !extract_dir.is_dir() {
+        Some(messages::cache_missing(&extract_dir))
+    } else if !download_dir.join(EXTRACTION_COMPLETE_MARKER).is_file() {
+        Some(messages::cache_interrupted(&extract_dir))
+    } else {
+        patchs
+            .iter()
+            .find(|detail| !extract_dir.join(&detail.patch_path).is_file())
+            .map(|detail| messages::cache_patch_missing(&detail.patch_path, &extract_dir))
+    };
+    match problem {
+        None => {
+            println!("{}", messages::cache_used(&extract_dir));
+            Ok(Some(extract_dir))
+        }
+        Some(problem) if offline => Err(messages::cache_unusable_offline(&problem).into()),
+        Some(problem) => {
+            eprintln!("{}", messages::cache_unusable(&problem));
+            Ok(None)
+        }
+    }
+}
+
+// L'index qui a servi à télécharger l'archive, pour --offline. Sans lui, le cache reste
+// utilisable en ligne : son absence n'est qu'un avertissement.
+fn save_cached_index(download_dir: &Path, patch_index: &PatchIndex) {
+    let index_path = download_dir.join(LOCAL_INDEX_NAME);
+    if let Err(e) = serde_json::to_vec_pretty(patch_index)
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|data| fsutil::write_atomic(&index_path, &data))
+    {
+        eprintln!("{}", messages::cache_index_save_failed(&index_path, &e));
+    }
+}
+
+fn read_cached_index(download_dir: &Path) -> Result<PatchIndex, Box<dyn Error>> {
+    let index_path = download_dir.join(LOCAL_INDEX_NAME);
+    println!("{}", messages::offline_cached_index(&index_path));
+    let data = fs::read(&index_path)
+        .map_err(|e| messages::offline_index_unreadable(&index_path, &e))?;
+    Ok(serde_json::from_slice(&data).map_err(|e| messages::cached_index_invalid(&index_path, &e))?)
+}
+
+/// Télécharge l'archive du patch de la plateforme, puis l'extrait dans le répertoire temporaire.
+/// Renvoie le répertoire d'extraction.
+#[allow(clippy::too_many_arguments)]
+fn download_and_extract(
+    game_dir: &Path,
+    platform_key: &str,
+    platform_info: &PlatformInfo,
+    patchs: &[&PatchDetail],
+    selection: &Selection,
+    options: &InstallOptions,
+    deadline: &Deadline,
+    progress: &mut Progress,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let download_dir = download_dir_for(options.temp_dir.as_deref());
+    std::fs::create_dir_all(&download_dir)?;
+    let zip_filename = "patch_download"; 
+
+    let zip_url = match &options.mirror {
+        Some(mirror) => {
+            println!("{}", messages::downloading_from_mirror(mirror));
+            mirror
+        }
+        None => {
+            println!("{}", messages::patch_url_found(platform_key, &platform_info.file_url));
+            &platform_info.file_url
+        }
+    };
+    let url_from_user = options.mirror.is_some();
+
+    let use_volumes = !platform_info.file_volumes.is_empty() && options.mirror.is_none();
+    let download_size = if use_volumes {
+        platform_info.file_volumes.iter().map(|volume| volume.size).sum::<Option<u64>>()
+    } else {
+        platform_info.file_size.or_else(|| fetch_download_size(zip_url, url_from_user))
+    };
+    match download_size {
+        Some(size) => {
+            println!("{}", messages::patch_size(&format_size(size)));
+            if !options.assume_yes && !confirm(&messages::continue_prompt())? {
+                return Err(messages::download_cancelled().into());
+            }
+        }
+        None => println!("{}", messages::patch_size_unknown()),
+    }
+
+    let source_sizes: Vec<u64> = patchs
+        .iter()
+        .filter_map(|detail| fs::metadata(game_dir.join(&detail.source_path)).ok())
+        .map(|m| m.len())
+        .collect();
+
+    // Estimation avant téléchargement : l'extraction est comptée comme deux fois le ZIP ; côté jeu,
+    // une sauvegarde par fichier patché, le temporaire d'écriture du plus gros et les extras (au plus le ZIP).
+    let zip_size = download_size.unwrap_or(0);
+    let game_needed = source_sizes.iter().sum::<u64>() + source_sizes.iter().max().copied().unwrap_or(0) + zip_size;
+    if !check_free_space(&download_dir, zip_size * 3, game_dir, game_needed)
+        && !options.assume_yes
+        && !confirm(&messages::continue_anyway_prompt())?
+    {
+        return Err(messages::space_cancelled().into());
+    }
+
+    // Poids de chaque phase dans la barre globale, affinés quand les tailles réelles sont connues :
+    // le patch est estimé d'après les fichiers actuels du jeu, les extras d'après le ZIP.
+    let zip_weight = download_size.unwrap_or(1);
+    progress.set_weight(Phase::Download, zip_weight);
+    progress.set_weight(Phase::Extraction, zip_weight);
+    progress.set_weight(Phase::Patch, source_sizes.iter().sum());
+    progress.set_weight(Phase::Extras, zip_weight / 4);
+
+    let zip_output_path = download_dir.join(zip_filename);
+
+    // Le pair-à-pair est facultatif : en cas d'échec, l'archive est téléchargée en HTTP.
+    let torrent_crc = match (&platform_info.torrent_url, options.torrent) {
+        (Some(torrent_url), true) => {
+            progress.start_phase(Phase::Download, 0);
+            let result = torrent::download(torrent_url, &download_dir, zip_filename, deadline);
+            progress.finish_phase();
+            match result {
+                Ok(crc) => Some(crc),
+                Err(e) if e.downcast_ref::<deadline::DeadlineExceeded>().is_some() => return Err(e),
+                Err(e) => {
+                    eprintln!("{}", messages::torrent_failed(&e));
+                    None
+                }
+            }
+        }
+        (None, true) => {
+            println!("{}", messages::torrent_unavailable());
+            None
+        }
+        (_, false) => None,
+    };
+    let zip_crc = match torrent_crc {
+        Some(crc) => crc,
+        None if use_volumes => download_volumes(&platform_info.file_volumes, &zip_output_path, options, deadline, progress)?,
+        None => download_file(zip_url, &zip_output_path, url_from_user, options.download_chunk_size, deadline, progress)?,
+    };
+
+    println!("{}", messages::archive_downloaded(&zip_output_path));
+    match platform_info.file_crc {
+        Some(expected) if expected != zip_crc => {
+            let _ = fs::remove_file(&zip_output_path);
+            return Err(messages::archive_corrupted(zip_crc, expected).into());
+        }
+        Some(_) => println!("{}", messages::archive_crc_ok(zip_crc)),
+        None => println!("{}", messages::archive_crc_unchecked(zip_crc)),
+    }
+
+    let archive_format = archive::resolve_format(&zip_output_path, platform_info.archive_type)?;
+    extract_patch_archive(&zip_output_path, archive_format, &download_dir, game_dir, patchs, selection, options, deadline, progress)
+}
+
+/// Installe depuis une archive déjà téléchargée (--from-file) : elle est comparée au CRC32
+/// annoncé par l'index, s'il en donne un, puis extraite comme une archive téléchargée.
+#[allow(clippy::too_many_arguments)]
+fn extract_local_archive(
+    archive_path: &Path,
+    game_dir: &Path,
+    platform_info: &PlatformInfo,
+    patchs: &[&PatchDetail],
+    selection: &Selection,
+    options: &InstallOptions,
+    deadline: &Deadline,
+    progress: &mut Progress,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let download_dir = download_dir_for(options.temp_dir.as_deref());
+    fs::create_dir_all(&download_dir)?;
+
+    let archive_size = fs::metadata(archive_path)?.len();
+    progress.set_weight(Phase::Extraction, archive_size);
+    progress.set_weight(Phase::Extras, archive_size / 4);
+
+    if let Some(expected) = platform_info.file_crc {
+        let actual = calculate_crc32_stream(File::open(archive_path)?)?;
+        if actual == expected {
+            println!("{}", messages::archive_crc_ok(actual));
+        } else {
+            // L'archive peut être d'une autre version que l'index : les CRC32 des patchs le diront.
+            let message = messages::local_archive_crc_mismatch(archive_path, actual, expected);
+            if options.strict {
+                return Err(message.into());
+            }
+            eprintln!("{}", messages::continuing_without_strict(&message));
+        }
+    }
+
+    let archive_format = archive::resolve_format(archive_path, platform_info.archive_type)?;
+    extract_patch_archive(archive_path, archive_format, &download_dir, game_dir, patchs, selection, options, deadline, progress)
+}
+
+/// Extrait l'archive du patch dans le répertoire temporaire, après avoir vérifié l'espace disponible.
+/// Renvoie le répertoire d'extraction.
+#[allow(clippy::too_many_arguments)]
+fn extract_patch_archive(
+    zip_output_path: &Path,
+    archive_format: archive::ArchiveFormat,
+    download_dir: &Path,
+    game_dir: &Path,
+    patchs: &[&PatchDetail],
+    selection: &Selection,
+    options: &InstallOptions,
+    deadline: &Deadline,
+    progress: &mut Progress,
+) -> Result<PathBuf, Box<dyn Error>> {
+    // L'extraction précédente n'est plus celle de l'archive reçue : elle cesse d'être réutilisable
+    // (--use-cache) avant même la lecture de l'archive, qui peut échouer si elle est corrompue.
+    let complete_marker = download_dir.join(EXTRACTION_COMPLETE_MARKER);
+    if complete_marker.exists() {
+        fs::remove_file(&complete_marker)?;
+    }
+
+    // L'archive est là : la taille décompressée exacte est lue dans son répertoire central, si le format en a un.
+    match archive::uncompressed_size(zip_output_path, archive_format)? {
+        Some(extracted_size) => {
+            if !check_free_space(download_dir, extracted_size, game_dir, 0)
+                && !options.assume_yes
+                && !confirm(&messages::continue_anyway_prompt())?
+            {
+                return Err(messages::extraction_space_cancelled().into());
+            }
+        }
+        None => println!("{}", messages::uncompressed_size_unknown(&archive_format)),
+    }
+
+    // Extraction de l'archive 
+   let extract_dir = download_dir.join(EXTRACT_DIR_NAME); 
+    println!("{}", messages::preparing_extraction(&extract_dir));
+    if extract_dir.exists() {
+        println!("{}", messages::cleaning_extraction_dir());
+        std::fs::remove_dir_all(&extract_dir)?; 
+    }
+    std::fs::create_dir_all(&extract_dir)?; 
+    progress.start_phase(Phase::Extraction, 1);
+    deadline.check()?;
+    let patch_paths: HashSet<&str> = patchs.iter().map(|detail| detail.patch_path.as_str()).collect();
+    if let Err(e) = archive::extract_archive(zip_output_path, archive_format, &extract_dir, &patch_paths, selection) {
+        // Une extraction à moitié faite ne doit pas être reprise pour une extraction valide (--use-cache).
+        if let Err(cleanup_error) = fs::remove_dir_all(&extract_dir) {
+            eprintln!("{}", messages::incomplete_extraction_remove_failed(&extract_dir, &cleanup_error));
+        }
+        return Err(e);
+    }
+    fs::write(&complete_marker, b"")?;
+    progress.advance(1);
+    progress.finish_phase();
+    println!("{}", messages::archive_extracted(&extract_dir));
+    Ok(extract_dir)
+}
+
+/// Compare, avant tout téléchargement, le CRC32 des fichiers du jeu aux CRC32 source et cible
+/// annoncés par l'index. L'installation n'est engagée que si au moins un fichier est patchable ;
+/// sans CRC dans l'index, la vérification est laissée à l'après-téléchargement.
+fn check_compatibility(game_dir: &Path, patchs: &[&PatchDetail], resume: bool) -> Result<(), Box<dyn Error>> {
+    let mut by_source: Vec<(&str, Vec<&PatchDetail>)> = Vec::new();
+    for &detail in patchs {
+        match by_source.iter_mut().find(|(source, _)| *source == detail.source_path) {
+            Some((_, details)) => details.push(detail),
+            None => by_source.push((detail.source_path.as_str(), vec![detail])),
+        }
+    }
+    if patchs.iter().all(|detail| detail.source_crc.is_none() && detail.gen8_version.is_none()) {
+        return Ok(());
+    }
+
+    println!("{}", messages::compatibility_header());
+    let (mut compatible, mut patched, mut incompatible) = (0, 0, 0);
+    let mut version_mismatches = Vec::new();
+    for (source_path, details) in &by_source {
+        let Ok(file) = File::open(game_dir.join(source_path)) else {
+            println!("{}", messages::compat_missing(source_path));
+            incompatible += 1;
+            continue;
+        };
+        let crc = calculate_crc32_stream(file)?;
+        if details.iter().any(|d| d.source_crc == Some(crc)) {
+            println!("{}", messages::compat_ok(source_path, crc));
+            compatible += 1;
+        } else if details.iter().any(|d| d.target_crc == Some(crc)) {
+            println!("{}", messages::compat_patched(source_path, crc));
+            patched += 1;
+        } else if let Some(mismatch) = gen8_mismatch(&game_dir.join(source_path), details) {
+            println!("{}.", messages::compat_version_mismatch(source_path, &mismatch));
+            version_mismatches.push(messages::compat_version_mismatch(source_path, &mismatch));
+            incompatible += 1;
+        } else if details.iter().all(|d| d.source_crc.is_none()) {
+            println!("{}", messages::compat_no_crc(source_path));
+            compatible += 1;
+        } else {
+            match known_source_label(details, crc) {
+                Some(label) => println!("{}", messages::compat_known_uncovered(source_path, &label, crc)),
+                None => println!("{}", messages::compat_unknown(source_path, crc)),
+            }
+            incompatible += 1;
+        }
+    }
+
+    if compatible > 0 || (resume && patched > 0) {
+        return Ok(());
+    }
+    if patched > 0 && incompatible == 0 {
+        return Err(messages::already_installed().into());
+    }
+    if !version_mismatches.is_empty() {
+        return Err(messages::game_version_incompatible(&version_mismatches.join("\n")).into());
+    }
+    if patched > 0 {
+        return Err(messages::no_compatible_file_some_patched(incompatible, patched).into());
+    }
+    Err(messages::no_compatible_file(incompatible).into())
+}
+
+// Fichier source absent pendant la vérification des patchs, avant toute modification du jeu.
+fn handle_missing_source(source_file_path: &Path, policy: MissingSource) -> Result<(), Box<dyn Error>> {
+    match policy {
+        MissingSource::Skip => {
+            tracing::info!("{:?} absent du jeu : patch ignoré (--on-missing-source skip).", source_file_path);
+            Ok(())
+        }
+        MissingSource::Warn => {
+            eprintln!("{}", messages::source_missing(source_file_path));
+            Ok(())
+        }
+        MissingSource::Fail => Err(messages::source_missing_fatal(source_file_path).into()),
+    }
+}
+
+// Version GEN8 du fichier du jeu face à celle qu'attendent ses patchs, quand l'index la déclare.
+// Renvoie le message « le jeu est en version X, le patch cible la version Y » en cas de divergence.
+fn gen8_mismatch(source_file_path: &Path, details: &[&PatchDetail]) -> Option<String> {
+    let expected: Vec<&str> = details.iter().filter_map(|d| d.gen8_version.as_deref()).collect();
+    if expected.is_empty() {
+        return None;
+    }
+    let actual = match gamemaker::read_gen8_version(source_file_path) {
+        Ok(version) => version.to_string(),
+        Err(e) => {
+            tracing::info!("{:?} : version GEN8 illisible ({}).", source_file_path, e);
+            return None;
+        }
+    };
+    if expected.contains(&actual.as_str()) {
+        return None;
+    }
+    Some(messages::gen8_version_mismatch(&actual, &expected.join(&messages::gen8_or())))
+}
+
+/// Retire de `patchs` (--incremental) ceux dont le fichier est déjà dans sa version cible, d'après
+/// le CRC cible de l'index, et renvoie l'entrée du manifeste précédent de ces fichiers, reprise
+/// telle quelle : ils ne sont ni extraits ni touchés. Sans CRC cible, un patch est réappliqué.
+fn retain_changed(game_dir: &Path, patchs: &mut Vec<&PatchDetail>, previous: &InstallManifest) -> Result<Vec<ManifestEntry>, Box<dyn Error>> {
+    let mut unchanged: Vec<ManifestEntry> = Vec::new();
+    let mut unchanged_sources: HashSet<&str> = HashSet::new();
+    for &detail in patchs.iter() {
+        let source_path = detail.source_path.as_str();
+        if unchanged_sources.contains(source_path) {
+            continue;
+        }
+        let up_to_date = match (detail.target_crc, File::open(game_dir.join(source_path))) {
+            (Some(target_crc), Ok(file)) => calculate_crc32_stream(file)? == target_crc,
+            _ => false,
+        };
+        if up_to_date {
+            unchanged_sources.insert(source_path);
+            let mut entry = previous.files.iter()
+                .find(|entry| entry.path == Path::new(source_path))
+                .cloned()
+                .unwrap_or_else(|| ManifestEntry { path: PathBuf::from(source_path), ..Default::default() });
+            entry.crc = detail.target_crc;
+            unchanged.push(entry);
+        }
+    }
+    // Un fichier à jour pour un de ses patchs candidats l'est pour tous.
+    patchs.retain(|detail| !unchanged_sources.contains(detail.source_path.as_str()));
+    Ok(unchanged)
+}
+
+/// Taille des fichiers qui seront sauvegardés : sources des patchs (hors reprise, dont la
+/// sauvegarde existe déjà) et fichiers du jeu remplacés par un fichier supplémentaire.
+fn backups_size(game_dir: &Path, extract_dir: &Path, planned_patches: &[PlannedPatch], selection: &Selection) -> u64 {
+    let patched: u64 = planned_patches
+        .iter()
+        .filter(|planned| planned.resume_backup.is_none())
+        .filter_map(|planned| fs::metadata(&planned.source_file_path).ok())
+        .map(|m| m.len())
+        .sum();
+    if selection.patches_only {
+        return patched;
+    }
+    let replaced: u64 = WalkDir::new(extract_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_extra_file(extract_dir, e.path(), selection))
+        .filter_map(|e| e.path().strip_prefix(extract_dir).ok().and_then(|p| fs::metadata(game_dir.join(p)).ok()))
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum();
+    patched + replaced
+}
+
+/// --preview : plan détaillé de l'installation, établi après extraction et validation des patchs.
+/// Rien n'est écrit ; seuls les fichiers du jeu sont lus pour connaître leur taille.
+fn print_install_plan(
+    game_dir: &Path,
+    extract_dir: &Path,
+    planned_patches: &[PlannedPatch],
+    resumed: usize,
+    platform_info: &PlatformInfo,
+    selection: &Selection,
+    options: &InstallOptions,
+) {
+    println!("{}", messages::plan_header());
+
+    let mut backups_size = 0;
+    let mut largest_target = 0;
+    println!("{}", messages::plan_patched_files(planned_patches.len()));
+    for planned in planned_patches {
+        let source_size = fs::metadata(&planned.source_file_path).map(|m| m.len()).unwrap_or(0);
+        let target_size = bps::read_bps_target_size(&planned.patch_file_path).unwrap_or(source_size);
+        largest_target = largest_target.max(target_size);
+        let source_path = Path::new(&planned.detail.source_path);
+        let backup = match &planned.resume_backup {
+            Some((backup, _)) => messages::plan_restored_from(backup),
+            None if options.compress_backups => {
+                backups_size += source_size;
+                messages::plan_compressed_backup(&backup::compressed_backup_path_for(source_path))
+            }
+            None => {
+                backups_size += source_size;
+                messages::plan_backup(&backup_path_for(source_path))
+            }
+        };
+        println!(
+            "{}",
+            messages::plan_patched_file(&planned.detail.source_path, &format_size(source_size), &format_size(target_size), &backup)
+        );
+    }
+    if resumed > 0 {
+        println!("{}", messages::plan_resumed(resumed));
+    }
+
+    let mut extras_size = 0;
+    if selection.patches_only {
+        println!("{}", messages::plan_no_extras());
+    } else {
+        let extras: Vec<PathBuf> = WalkDir::new(extract_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| is_extra_file(extract_dir, e.path(), selection))
+            .filter_map(|e| e.path().strip_prefix(extract_dir).ok().map(Path::to_path_buf))
+            .collect();
+        println!("{}", messages::plan_extras(extras.len()));
+        for relative_path in &extras {
+            let size = fs::metadata(extract_dir.join(relative_path)).map(|m| m.len()).unwrap_or(0);
+            extras_size += size;
+            // Un fichier remplacé est renommé en .bak : l'espace de l'ancien reste occupé.
+            if game_dir.join(relative_path).exists() {
+                println!("{}", messages::plan_extra_replaced(&index_style_path(relative_path), &format_size(size), &backup_path_for(relative_path)));
+            } else {
+                println!("{}", messages::plan_extra_created(&index_style_path(relative_path), &format_size(size)));
+            }
+        }
+    }
+
+    if !platform_info.post_install.is_empty() {
+        let count = platform_info.post_install.len();
+        let hooks = if options.allow_hooks { messages::plan_hooks_run(count) } else { messages::plan_hooks_ignored(count) };
+        println!("{}", hooks);
+    }
+
+    // Sauvegardes, fichiers supplémentaires et temporaire d'écriture du plus gros fichier patché.
+    let needed = backups_size + extras_size + largest_target;
+    let space = if options.compress_backups { messages::plan_space_compressed(&format_size(needed)) } else { messages::plan_space(&format_size(needed)) };
+    println!("{}", space);
+}
+
+/// Index à installer selon les options : archive locale, répertoire local, cache hors ligne, version choisie ou dernière version.
+fn load_patch_index(options: &InstallOptions) -> Result<PatchIndex, Box<dyn Error>> {
+    if let Some(archive_path) = &options.from_file {
+        return read_archive_index(archive_path, options.local_index.as_deref(), &download_dir_for(options.temp_dir.as_deref()));
+    }
+    match &options.from_dir {
+        Some(patches_dir) => read_local_index(patches_dir),
+        None if options.offline => read_cached_index(&download_dir_for(options.temp_dir.as_deref())),
+        None => {
+            let index_url = match &options.patch_version {
+                Some(version) => {
+                    let url = versions::index_url_for(&versions_url(), version)?;
+                    println!("{}", messages::installing_version(version));
+                    url
+                }
+                None => index_url().to_string(),
+            };
+            fetch_patch_index(&index_url)
+        }
+    }
+}
+
+/// Associe chaque plateforme demandée (--platform a,b ou --all-platforms) à son répertoire de jeu :
+/// le --game-dir de même rang, ou à défaut l'unique profil enregistré pour cette plateforme.
+/// Sans plateforme ou avec une seule, c'est la cible habituelle (--game-dir ou --profile).
+fn resolve_platform_targets(
+    mut game_dirs: Vec<PathBuf>,
+    profile: Option<String>,
+    platforms: Vec<String>,
+    all_platforms: bool,
+    state_dir: &Path,
+    config: &config::Config,
+    options: &InstallOptions,
+) -> Result<Vec<Target>, Box<dyn Error>> {
+    if !all_platforms && platforms.len() <= 1 {
+        if game_dirs.len() > 1 {
+            return Err(messages::game_dirs_without_platforms().into());
+        }
+        let mut target = resolve_target(game_dirs.pop(), profile, state_dir, config)?;
+        if let Some(platform) = platforms.into_iter().next() {
+            target.platform = Some(platform);
         }
+        return Ok(vec![target]);
+    }
+    if profile.is_some() {
+        return Err(messages::profile_with_platforms().into());
+    }
 
-        let relative_path = match path_in_zip.strip_prefix(extract_dir) {
-            Ok(p) => p,
-            Err(_) => {
-                eprintln!("ATTENTION : Impossible de déterminer le chemin relatif pour {:?}. Fichier ignoré.", path_in_zip);
-                continue;
+    let platforms = if all_platforms {
+        let mut keys: Vec<String> = load_patch_index(options)?.into_keys().collect();
+        keys.sort();
+        keys
+    } else {
+        platforms
+    };
+    if platforms.is_empty() {
+        return Err(messages::index_without_platforms().into());
+    }
+    if !game_dirs.is_empty() && game_dirs.len() != platforms.len() {
+        return Err(messages::platforms_game_dirs_mismatch(platforms.len(), &platforms.join(", "), game_dirs.len()).into());
+    }
+
+    let mut targets = Vec::new();
+    for (i, platform) in platforms.into_iter().enumerate() {
+        let target = match game_dirs.get(i) {
+            Some(game_dir) => Target {
+                game_dir: config.resolve_game_dir(game_dir.clone())?,
+                state_dir: state_dir.to_path_buf(),
+                platform: Some(platform),
+            },
+            None => {
+                let mut matching = profiles::profiles_for_platform(state_dir, &platform)?;
+                if matching.len() != 1 {
+                    return Err(messages::platform_profiles_ambiguous(matching.len(), &platform).into());
+                }
+                let (name, profile) = matching.remove(0);
+                Target {
+                    game_dir: profile.game_dir,
+                    state_dir: profiles::profile_state_dir(state_dir, &name),
+                    platform: Some(platform),
+                }
             }
         };
+        targets.push(target);
+    }
 
-        let dest_path = game_dir.join(relative_path);
-        println!("Copie : {:?} -> {:?}", path_in_zip, dest_path);
-
-        if let Some(dest_parent) = dest_path.parent() {
-            if !dest_parent.exists() {
-                println!("Création du répertoire parent de destination : {:?}", dest_parent);
-                fs::create_dir_all(dest_parent)?; 
-            }
-        } else {
-            eprintln!("ATTENTION : Impossible de déterminer le répertoire parent pour {:?}. Fichier ignoré.", dest_path);
-            continue;
+    let mut seen = HashSet::new();
+    for target in &targets {
+        if !seen.insert(fs::canonicalize(&target.game_dir).unwrap_or_else(|_| target.game_dir.clone())) {
+            return Err(messages::game_dir_for_several_platforms(&target.game_dir).into());
         }
+    }
+    Ok(targets)
+}
 
-        // Création des sauvegardes (renomme fichier en fichier.bak)
-        if dest_path.exists() {
-             let backup_path = dest_path.with_extension(
-                format!("{}.bak", dest_path.extension().unwrap_or_default().to_str().unwrap_or(""))
-            );
-            println!("Fichier existant trouvé à {:?}. Sauvegardé en {:?}", dest_path, backup_path);
-
-            let _ = fs::remove_file(&backup_path);
+/// Installe une cible : verrouillage du répertoire, puis installation directe ou par échange (--atomic-swap).
+/// Avec --retry-install, une installation qui échoue pour une raison passagère est annulée puis
+/// recommencée, verrou compris, après un délai qui double à chaque fois.
+fn install_target(target: &Target, state_dir: &Path, options: &InstallOptions, atomic_swap: bool) -> Result<(), Box<dyn Error>> {
+    let options = InstallOptions { platform: target.platform.clone(), ..options.clone() };
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let retries_left = attempt <= options.retry_install;
+        if options.retry_install > 0 {
+            println!("{}", messages::install_attempt(attempt, options.retry_install + 1));
+        }
+        println!("{}", messages::install_start(&target.game_dir));
+        let result = lock::lock_game_dir(state_dir, &target.game_dir).and_then(|_lock| {
+            let result = if atomic_swap {
+                run_atomic_swap_install(&target.game_dir, &target.state_dir, &options)
+            } else {
+                run_install_process(&target.game_dir, &target.state_dir, &options)
+            };
+            if let Err(e) = &result
+                && retries_left
+                && is_transient_error(e.as_ref())
+            {
+                rollback_failed_attempt(&target.game_dir, &target.state_dir)?;
+            }
+            result
+        });
 
-            match fs::rename(&dest_path, &backup_path) {
-                Ok(_) => println!("Sauvegarde {:?} créée.", backup_path),
-                Err(e) => {
-                    eprintln!("ERREUR : Impossible de renommer {:?} en {:?}: {}. Copie annulée pour ce fichier.", dest_path, backup_path, e);
-                    continue;
+        let e = match result {
+            Ok(()) => {
+                if attempt > 1 {
+                    println!("{}", messages::install_attempt_succeeded(attempt));
                 }
+                return Ok(());
             }
+            Err(e) => e,
+        };
+        if !retries_left {
+            return Err(e);
         }
+        if !is_transient_error(e.as_ref()) {
+            eprintln!("{}", messages::install_error_permanent());
+            return Err(e);
+        }
+        let delay = std::time::Duration::from_secs((RETRY_INSTALL_BASE_DELAY_SECS << (attempt - 1).min(4)).min(RETRY_INSTALL_MAX_DELAY_SECS));
+        eprintln!("{}", messages::install_attempt_failed(attempt, e.as_ref(), delay.as_secs()));
+        std::thread::sleep(delay);
+    }
+}
 
-        match fs::copy(path_in_zip, &dest_path) {
-            Ok(_) => println!("Fichier {:?} copié avec succès.", dest_path),
-            Err(e) => {
-                eprintln!("ERREUR : Impossible de copier {:?} vers {:?}: {}.", path_in_zip, dest_path, e);
-                continue; 
-            }
+const RETRY_INSTALL_BASE_DELAY_SECS: u64 = 5;
+const RETRY_INSTALL_MAX_DELAY_SECS: u64 = 60;
+
+/// Erreur qui peut disparaître en recommençant toute l'installation : erreur réseau passagère
+/// ou répertoire verrouillé par une autre instance, à n'importe quel niveau de la chaîne des causes.
+/// Le reste (version incompatible, CRC, espace disque...) est définitif.
+fn is_transient_error(error: &(dyn Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(e) = current {
+        if let Some(net_error) = e.downcast_ref::<net::NetError>() {
+            return net_error.is_transient();
+        }
+        if e.is::<lock::LockHeld>() {
+            return true;
+        }
+        if let Some(io_error) = e.downcast_ref::<std::io::Error>()
+            && matches!(
+                io_error.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted
+            )
+        {
+            return true;
         }
+        current = e.source();
     }
+    false
+}
 
-    println!("\n--- Copie des fichiers supplémentaires terminée ---");
+// Remet le jeu dans son état d'avant la tentative échouée : les fichiers déjà modifiés, notés dans
+// le manifeste de l'installation en cours, sont restaurés comme pour rollback-last.
+fn rollback_failed_attempt(game_dir: &Path, state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(pending) = manifest::load_pending(state_dir, game_dir)
+        && !pending.files.is_empty()
+    {
+        eprintln!("{}", messages::rolling_back_install());
+        rollback_partial_install(game_dir, state_dir, &pending)?;
+    }
+    manifest::clear_pending(state_dir, game_dir);
     Ok(())
 }
 
+/// Installe chaque plateforme sur son répertoire, l'une après l'autre ; l'échec de l'une n'empêche
+/// pas les suivantes. Se termine par un récapitulatif par plateforme.
+fn run_multi_platform_install(targets: &[Target], state_dir: &Path, options: &InstallOptions, atomic_swap: bool) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::multi_platform_plan(targets.len()));
+    for target in targets {
+        println!("  {} -> {:?}", target.platform.as_deref().unwrap_or_default(), target.game_dir);
+    }
 
-fn fetch_patch_index(url: &str) -> Result<PatchIndex, Box<dyn Error>> {
-    println!("Téléchargement de l'index des patchs depuis {}...", url);
-    let response = reqwest::blocking::get(url)?;
-
-    response.error_for_status_ref()?;
+    let mut results = Vec::new();
+    for target in targets {
+        let platform = target.platform.as_deref().unwrap_or_default();
+        println!("{}", messages::multi_platform_start(platform, &target.game_dir));
+        let result = install_target(target, state_dir, options, atomic_swap);
+        if let Err(e) = &result {
+            eprintln!("{}", messages::multi_platform_install_failed(platform, e.as_ref()));
+        }
+        results.push((target, result));
+    }
 
-    let index: PatchIndex = response.json::<PatchIndex>()?;
-    println!("Index téléchargé et analysé avec succès.");
-    Ok(index)
+    println!("{}", messages::multi_platform_summary());
+    let mut failed = 0;
+    for (target, result) in &results {
+        let platform = target.platform.as_deref().unwrap_or_default();
+        match result {
+            Ok(()) => println!("{}", messages::multi_platform_ok(platform, &target.game_dir)),
+            Err(e) => {
+                failed += 1;
+                eprintln!("{}", messages::multi_platform_failed(platform, &target.game_dir, e.as_ref()));
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(messages::multi_platform_failures(failed, results.len()).into());
+    }
+    Ok(())
 }
 
-fn download_file(url: &str, output_path: &Path) -> Result<(), Box<dyn Error>> {
-    println!("Téléchargement de {} vers {:?}...", url, output_path);
-    let mut response = reqwest::blocking::get(url)?;
-
-    response.error_for_status_ref()?;
-
-    let output_file = File::create(output_path)?;
-    let mut dest_writer = BufWriter::new(output_file);
+fn run_install_process(game_dir: &Path, state_dir: &Path, options: &InstallOptions) -> Result<(), Box<dyn Error>> {
+    let deadline = Deadline::new(options.deadline);
+    fsutil::set_durable(options.fsync);
+    check_game_dir(game_dir)?;
+    let game_dir = &absolute_game_dir(game_dir)?;
+    println!("{}", messages::game_dir_chosen(game_dir));
+    let download_dir = download_dir_for(options.temp_dir.as_deref());
+    if options.dry_run {
+        println!("{}", messages::dry_run_start());
+        cleanup::clean_stale(&[state_dir, &download_dir]);
+    } else {
+        cleanup::clean_stale(&[game_dir, state_dir, &download_dir]);
+    }
 
-    response.copy_to(&mut dest_writer)?;
+    // Patcher un jeu lancé risque de le faire planter ou de mélanger anciennes et nouvelles données.
+    let running = process::find_running_game(game_dir);
+    if !running.is_empty() && !options.suspend_running_game && !options.dry_run {
+        return Err(messages::game_running(&process::describe(&running)).into());
+    }
+    let pending = manifest::load_pending(state_dir, game_dir);
+    if pending.is_some() && !options.resume {
+        println!("{}", messages::pending_install_found());
+    }
 
-    dest_writer.flush()?;
+    let patch_index = load_patch_index(options)?;
 
-    println!("Téléchargement de {} terminé.", url);
-    Ok(())
-}
+    let platform_key = match &options.platform {
+        Some(platform) => {
+            println!("{}", messages::platform_forced(platform));
+            platform.clone()
+        }
+        None => select_platform(game_dir),
+    };
+    if platform_key == "none" {
+        return Err(messages::invalid_game_dir().into());
+    }
 
-fn run_install_process(game_dir: &Path) -> Result<(), Box<dyn Error>> {
-     if !game_dir.is_dir() {
-        return Err(format!("Le chemin fourni {:?} n'est pas un répertoire valide.", game_dir).into());
+    let platform_info = platform_info_for(&patch_index, &platform_key)?;
+    tracing::info!(game_dir = ?game_dir, platform = %platform_key, "Début de l'installation");
+    if options.skip_game_check {
+        println!("{}", messages::game_check_skipped());
+    } else {
+        check_is_deltarune(game_dir, &platform_key)?;
+    }
+    if options.skip_exe_check {
+        println!("{}", messages::exe_check_skipped());
+    } else {
+        check_known_executables(game_dir, &platform_info.known_executables)?;
     }
-    println!("Répertoire du jeu choisi : {:?}", game_dir);
-    let index_url = "https://deltarune-fr.com/patch-files/linux/patch_index.json";
-    let download_dir = PathBuf::from("/tmp/patcher_drfr/");
-    std::fs::create_dir_all(&download_dir)?;
-    let zip_filename = "patch_download.zip"; 
 
-    let patch_index = fetch_patch_index(index_url)?;
+    let mut selection = options.selection.resolve_variant(&platform_info.variants)?;
+    selection.load_patchignore(game_dir)?;
+    let selection = &selection;
+    let mut patchignored: Vec<String> = platform_info
+        .patchs
+        .iter()
+        .filter(|detail| selection.patchignores_patch(detail))
+        .map(|detail| detail.source_path.clone())
+        .collect();
+    for path in &patchignored {
+        println!("{}", messages::patchignore_skipped(path));
+    }
+    let mut patchs: Vec<&PatchDetail> = platform_info.patchs.iter().filter(|detail| selection.keeps_patch(detail)).collect();
+    patchs.retain(|detail| {
+        let reason = detail.condition.as_ref().and_then(|condition| condition.unmet_reason(game_dir, &platform_key));
+        if let Some(reason) = &reason {
+            println!("{}", messages::patch_condition_unmet(&detail.patch_path, reason));
+        }
+        reason.is_none()
+    });
+    if !selection.chapters.is_empty() {
+        if patchs.is_empty() {
+            return Err(messages::no_patch_for_chapters(&selection.chapters).into());
+        }
+        println!("{}", messages::chapters_selected(&selection.chapters, patchs.len(), platform_info.patchs.len()));
+    }
 
-    let platform_key = select_platform(game_dir);
-    if platform_key == "none" {
-        return Err("Le dossier sélectionné semble invalide. Vérifiez que vous avez choisi le bon dossier. Si vous utilisez la version démo de DELTARUNE, vérifiez que vous avez bien activé la beta chapter1.2.lts.test sur Steam.".into());
+    // Mise à jour incrémentale : l'installation précédente tient lieu d'installation interrompue,
+    // ses sauvegardes servent de fichiers source aux patchs qui ont changé.
+    let previous = match options.incremental {
+        true => Some(
+            manifest::latest_manifest(state_dir, game_dir)?
+                .ok_or_else(messages::incremental_without_previous)?
+                .1,
+        ),
+        false => None,
+    };
+    let mut unchanged_entries = Vec::new();
+    if let Some(previous) = &previous {
+        unchanged_entries = retain_changed(game_dir, &mut patchs, previous)?;
+        let changed: HashSet<&str> = patchs.iter().map(|detail| detail.source_path.as_str()).collect();
+        println!("{}", messages::incremental_update(changed.len(), unchanged_entries.len()));
+        if patchs.is_empty() {
+            println!("{}", messages::incremental_up_to_date());
+            return Ok(());
+        }
+    } else {
+        check_compatibility(game_dir, &patchs, options.resume)?;
     }
 
-    let platform_info = patch_index.get(&platform_key).ok_or_else(|| {
-        format!("Plateforme '{}' non trouvée dans l'index JSON.", platform_key)
-    })?;
-    let zip_url = &platform_info.file_url;
-    println!(
-        "URL du patch trouvée pour la plateforme '{}': {}",
-        platform_key, zip_url
-    );
+    let mut progress = Progress::new(options.progress);
+    progress.set_observer(|event| tracing::trace!("Progression : {:?} ({:?} %)", event, event.percent()));
+    let cached_dir = match options.use_cache {
+        true => cached_extract_dir(&download_dir_for(options.temp_dir.as_deref()), &patchs, options.offline)?,
+        false => None,
+    };
+    let extract_dir = match options.from_dir.as_ref().or(cached_dir.as_ref()) {
+        Some(patches_dir) => {
+            // Rien à télécharger ni à extraire : les patchs sont lus directement dans le répertoire.
+            let source_sizes = patchs
+                .iter()
+                .filter_map(|detail| fs::metadata(game_dir.join(&detail.source_path)).ok())
+                .map(|m| m.len());
+            progress.set_weight(Phase::Patch, source_sizes.sum());
+            progress.set_weight(Phase::Extras, extra_files_size(patches_dir, selection));
+            patches_dir.clone()
+        }
+        None => {
+            let extract_dir = match &options.from_file {
+                Some(archive_path) => extract_local_archive(archive_path, game_dir, platform_info, &patchs, selection, options, &deadline, &mut progress)?,
+                None => download_and_extract(game_dir, &platform_key, platform_info, &patchs, selection, options, &deadline, &mut progress)?,
+            };
+            save_cached_index(&download_dir_for(options.temp_dir.as_deref()), &patch_index);
+            extract_dir
+        }
+    };
 
-    let zip_output_path = download_dir.join(zip_filename);
+    let archive_manifest_path = extract_dir.join(ARCHIVE_MANIFEST_NAME);
+    let expected_crcs = match &platform_info.file_crcs {
+        Some(crcs) => Some(crcs.clone()),
+        None if archive_manifest_path.is_file() => {
+            let data = fs::read(&archive_manifest_path)?;
+            Some(serde_json::from_slice::<HashMap<String, u32>>(&data)
+                .map_err(|e| messages::archive_manifest_invalid(ARCHIVE_MANIFEST_NAME, &e))?)
+        }
+        None => None,
+    };
+    // Les fichiers écartés par --chapter ou --patches-only n'ont pas été extraits.
+    let patch_paths: HashSet<&str> = patchs.iter().map(|detail| detail.patch_path.as_str()).collect();
+    let expected_crcs = expected_crcs.map(|mut crcs| {
+        crcs.retain(|path, _| selection.keeps_entry(Path::new(path), &patch_paths));
+        crcs
+    });
+    match expected_crcs {
+        Some(crcs) => verify_extracted_files(&extract_dir, &crcs, options.strict)?,
+        None => println!("{}", messages::no_file_crcs()),
+    }
+    // Le footer des patchs fait foi pour l'installation : un index qui le contredit est seulement
+    // signalé, sauf avec --strict.
+    let index_mismatches = validate_index::footer_mismatches(&extract_dir, patchs.iter().copied());
+    if !index_mismatches.is_empty() {
+        for mismatch in &index_mismatches {
+            eprintln!("  {}", mismatch);
+        }
+        let message = messages::index_footer_mismatches(index_mismatches.len());
+        if options.strict {
+            return Err(message.into());
+        }
+        eprintln!("{}", messages::continuing_without_strict(&message));
+    }
 
-    download_file(zip_url, &zip_output_path)?;
+    // Phase de vérification, en lecture seule : rien n'est écrit dans le
+    // répertoire du jeu tant que tous les patchs n'ont pas été validés.
+    deadline.check()?;
+    println!("{}", messages::verification_header());
+
+    // Plusieurs patchs peuvent viser le même fichier source (un par version du jeu) :
+    // on les regroupe en gardant l'ordre de l'index.
+    let mut candidates_by_source: Vec<(&str, Vec<&PatchDetail>)> = Vec::new();
+    for &detail in &patchs {
+        match candidates_by_source.iter_mut().find(|(source, _)| *source == detail.source_path) {
+            Some((_, candidates)) => candidates.push(detail),
+            None => candidates_by_source.push((detail.source_path.as_str(), vec![detail])),
+        }
+    }
 
-    println!("Le fichier ZIP a été téléchargé ici : {:?}", zip_output_path);
+    let mut planned_patches = Vec::new();
+    let mut resumed_entries = Vec::new();
+    for (source_path, candidates) in &candidates_by_source {
+        if options.resume || previous.is_some() {
+            match check_resume(game_dir, source_path, candidates, &extract_dir, previous.as_ref().or(pending.as_ref()))? {
+                Some(ResumeAction::AlreadyPatched(entry)) => {
+                    resumed_entries.push(entry);
+                    continue;
+                }
+                Some(ResumeAction::Restore(planned)) => {
+                    planned_patches.push(planned);
+                    continue;
+                }
+                None => {}
+            }
+        }
 
-    // Extraction du ZIP 
-   let extract_dir = download_dir.join("./patch_files"); 
-    println!("Préparation de l'extraction dans : {:?}", extract_dir);
-    if extract_dir.exists() {
-        println!("Nettoyage du répertoire d'extraction...");
-        std::fs::remove_dir_all(&extract_dir)?; 
-    }
-    std::fs::create_dir_all(&extract_dir)?; 
-    unzip_file(&zip_output_path, &extract_dir)?;
-    println!("Archive décompressée avec succès dans {:?}", extract_dir);
+        if candidates.len() > 1 {
+            println!("{}", messages::patch_candidates(candidates.len(), source_path));
+            let source_file_path = game_dir.join(source_path);
+            if !source_file_path.exists() {
+                handle_missing_source(&source_file_path, options.on_missing_source)?;
+                continue;
+            }
+            let detail = select_patch_candidate(&source_file_path, candidates, &extract_dir)?;
+            let patch_file_path = extract_dir.join(&detail.patch_path);
+            planned_patches.push(PlannedPatch { detail, patch_file_path, source_file_path, resume_backup: None });
+            continue;
+        }
 
-    println!("\n--- Début de l'application des patchs ---");
-    for detail in &platform_info.patchs {
-        println!("\nTraitement du patch : '{}' pour le fichier source '{}'", detail.patch_path, detail.source_path);
+        let detail = candidates[0];
+        println!("{}", messages::checking_patch(&detail.patch_path, &detail.source_path));
 
         let patch_file_path = extract_dir.join(&detail.patch_path);
 
         let source_file_path = game_dir.join(&detail.source_path);
 
         if !patch_file_path.exists() {
-            eprintln!("ERREUR : Le fichier patch {:?} est introuvable dans l'archive extraite. Passage au suivant.", patch_file_path);
+            eprintln!("{}", messages::patch_missing(&patch_file_path));
             continue; // Gestion de l'erreur à réétudier, c'est peut-être mieux d'arrêter l'installation entièrement
         }
         if !source_file_path.exists() {
-            eprintln!("ERREUR : Le fichier source {:?} est introuvable dans le répertoire du jeu. Passage au suivant.", source_file_path);
-            continue; // Idem
+            handle_missing_source(&source_file_path, options.on_missing_source)?;
+            continue;
         }
 
         match can_apply_bps(&source_file_path, &patch_file_path) {
             Ok(true) => {
-                println!("Préparation de l'application du patch...");
+                planned_patches.push(PlannedPatch { detail, patch_file_path, source_file_path, resume_backup: None });
             }
             Ok(false) => {
-                return Err(format!("Le fichier source {:?} ne correspond pas au patch {:?}.", source_file_path, patch_file_path).into());
+                let label = File::open(&source_file_path)
+                    .and_then(calculate_crc32_stream)
+                    .ok()
+                    .and_then(|crc| known_source_label(&[detail], crc));
+                return Err(match label {
+                    Some(label) => messages::source_known_uncovered(&source_file_path, &label),
+                    None => messages::source_mismatch(&source_file_path, &patch_file_path),
+                }.into());
             }
             Err(e) => {
-                eprintln!("Erreur lors de la vérification du patch pour {:?}: {}. Arrêt du patcher.", source_file_path, e);
+                eprintln!("{}", messages::check_failed(&source_file_path, &e));
                 return Err(e);
             }
         }
+    }
+    if !selection.patches_only {
+        check_extra_count(&extract_dir, selection, options.max_extras)?;
+    }
+    // Les liens symboliques sont suivis, mais seulement à l'intérieur du jeu et de l'archive.
+    for planned in &planned_patches {
+        fsutil::resolve_link_within(game_dir, &planned.source_file_path)?;
+        fsutil::resolve_link_within(&extract_dir, &planned.patch_file_path)?;
+    }
+
+    if options.dry_run {
+        print_install_plan(game_dir, &extract_dir, &planned_patches, resumed_entries.len(), platform_info, selection, options);
+        println!("{}", messages::dry_run_done());
+        return Ok(());
+    }
+    if options.preview {
+        print_install_plan(game_dir, &extract_dir, &planned_patches, resumed_entries.len(), platform_info, selection, options);
+        if options.assume_yes {
+            println!("{}", messages::preview_done_assume_yes());
+            return Ok(());
+        }
+        if !confirm(&messages::preview_proceed_prompt())? {
+            println!("{}", messages::preview_done());
+            return Ok(());
+        }
+    }
+
+    // Dernière occasion de renoncer avant d'écrire dans le jeu : les fichiers à patcher sont listés,
+    // avec l'espace pris par leurs sauvegardes, qui peuvent doubler l'espace occupé.
+    // --preview a déjà tout affiché et demandé confirmation.
+    let backups_size = backups_size(game_dir, &extract_dir, &planned_patches, selection);
+    if !options.preview && !planned_patches.is_empty() {
+        println!("{}", messages::files_to_patch(planned_patches.len()));
+        for planned in &planned_patches {
+            println!("  {}", planned.detail.source_path);
+        }
+        if backups_size > 0 && options.compress_backups {
+            println!("{}", messages::backups_size_compressed(&format_size(backups_size)));
+        } else if backups_size > 0 {
+            println!("{}", messages::backups_size(&format_size(backups_size)));
+        }
+        if !options.assume_yes && !confirm(&messages::continue_prompt())? {
+            return Err(messages::install_cancelled().into());
+        }
+    }
 
-        let backup_file_path = source_file_path.with_extension(
-            format!("{}.bak", source_file_path.extension().unwrap_or_default().to_str().unwrap_or(""))
-        ); 
-        println!("Création de la sauvegarde : {:?}", backup_file_path);
-        match std::fs::copy(&source_file_path, &backup_file_path) {
-             Ok(_) => println!("Sauvegarde créée."),
-             Err(e) => {
-                eprintln!("ERREUR lors de la création de la sauvegarde {:?} : {}", backup_file_path, e);
-                // On décide de continuer quand même ? Ou de s'arrêter ? Pour l'instant on continue.
-                // return Err(format!("Impossible de créer la sauvegarde pour {:?}: {}", source_file_path, e).into());
-             }
+    // Phase de modification.
+    deadline.check()?;
+    // La garde arrête le jeu suspendu à la fin de l'installation, y compris en cas d'erreur.
+    let _suspended_game = if options.suspend_running_game {
+        let running = process::find_running_game(game_dir);
+        if running.is_empty() {
+            None
+        } else {
+            println!("{}", messages::game_will_be_suspended(&process::describe(&running)));
+            if !options.assume_yes && !confirm(&messages::suspend_prompt())? {
+                return Err(messages::cancelled_game_running().into());
+            }
+            Some(process::SuspendedGame::suspend(&running)?)
+        }
+    } else {
+        None
+    };
+    ensure_writable(game_dir)?;
+    let mut install_manifest = InstallManifest::new(game_dir, &platform_key);
+    install_manifest.patch_version = options.patch_version.clone();
+    install_manifest.files.extend(unchanged_entries);
+    install_manifest.files.extend(resumed_entries);
+    manifest::save_pending(state_dir, &install_manifest)?;
+
+    let target_sizes: Vec<u64> = planned_patches
+        .iter()
+        .map(|planned| {
+            bps::read_bps_target_size(&planned.patch_file_path)
+                .or_else(|_| fs::metadata(&planned.source_file_path).map(|m| m.len()))
+                .unwrap_or(0)
+        })
+        .collect();
+    let total_target_size: u64 = target_sizes.iter().sum();
+    progress.set_weight(Phase::Patch, total_target_size);
+    progress.set_weight(Phase::Extras, extra_files_size(&extract_dir, selection));
+    progress.start_phase(Phase::Patch, total_target_size);
+
+    println!("{}", messages::apply_header());
+    let mut total_patch_size = 0;
+    let mut total_patched_size = 0;
+    for (planned, target_size) in planned_patches.iter().zip(&target_sizes) {
+        let detail = planned.detail;
+        let patch_file_path = &planned.patch_file_path;
+        let source_file_path = &planned.source_file_path;
+        if deadline.expired() {
+            return abort_on_deadline(game_dir, state_dir, &install_manifest, &deadline);
+        }
+        println!("{}", messages::processing_patch(&detail.patch_path, &detail.source_path));
+
+        // Fichier réellement écrit : la cible si la source est un lien symbolique, que l'écriture
+        // atomique remplacerait sinon par un fichier ordinaire. La sauvegarde, elle, copie le
+        // contenu de la cible sous le nom du lien : c'est ce nom que le manifeste enregistre.
+        let write_path = fsutil::resolve_link_within(game_dir, source_file_path)?;
+        let mut backup_file_path = backup_path_for(source_file_path);
+        let mut backup = None;
+        let mut backup_crc = None;
+        if let Some((relative_backup, expected_crc)) = &planned.resume_backup {
+            // La sauvegarde de la tentative précédente contient le fichier d'origine : elle est
+            // restaurée puis conservée telle quelle.
+            backup_file_path = game_dir.join(relative_backup);
+            println!("{}", messages::restoring_from(source_file_path, &backup_file_path));
+            if backup::is_compressed(&backup_file_path) {
+                backup::decompress_backup(&backup_file_path, &write_path, *expected_crc)?;
+            } else {
+                fsutil::write_atomic(&write_path, &fs::read(&backup_file_path)?)?;
+            }
+            backup = Some(relative_backup.clone());
+            backup_crc = *expected_crc;
+        } else if options.compress_backups {
+            println!("{}", messages::creating_compressed_backup(source_file_path));
+            match backup::create_compressed_backup(source_file_path) {
+                Ok((path, crc)) => {
+                    println!("{}", messages::backup_path_created(&path));
+                    backup = Some(backup::compressed_backup_path_for(Path::new(&detail.source_path)));
+                    backup_file_path = path;
+                    backup_crc = Some(crc);
+                }
+                Err(e) => {
+                    return Err(messages::compressed_backup_failed(source_file_path, &e).into());
+                }
+            }
+        } else {
+            println!("{}", messages::creating_backup(&backup_file_path));
+            match backup::create_linked_backup(source_file_path) {
+                 Ok(_) => {
+                    println!("{}", messages::backup_created());
+                    backup = Some(backup_path_for(Path::new(&detail.source_path)));
+                 }
+                 Err(e) => {
+                    eprintln!("{}", messages::backup_failed(&backup_file_path, &e));
+                    // On décide de continuer quand même ? Ou de s'arrêter ? Pour l'instant on continue.
+                    // return Err(format!("Impossible de créer la sauvegarde pour {:?}: {}", source_file_path, e).into());
+                 }
+            }
         }
 
 
-        println!("Application du patch sur : {:?}", source_file_path);
-        match apply_bps(&source_file_path, &patch_file_path, &source_file_path) {
-            Ok(_) => println!("Patch appliqué avec succès pour : {:?}", source_file_path),
+        println!("{}", messages::applying_patch(source_file_path));
+        // Le CRC cible ne garantit qu'un contenu identique à celui qu'a produit l'équipe : la structure
+        // du conteneur est vérifiée en plus pour les fichiers GameMaker, si on le demande.
+        let validate_container = options.validate_gamemaker && gamemaker::is_data_file(source_file_path);
+        let mut invalid_container = false;
+        let started = std::time::Instant::now();
+        let result = apply_bps(source_file_path, patch_file_path, &write_path).and_then(|_| {
+            if !validate_container {
+                return Ok(());
+            }
+            match gamemaker::validate(&fs::read(&write_path)?) {
+                Ok(()) => {
+                    println!("{}", messages::gamemaker_valid(source_file_path));
+                    Ok(())
+                }
+                Err(e) => {
+                    invalid_container = true;
+                    Err(messages::gamemaker_invalid(source_file_path, &e).into())
+                }
+            }
+        });
+        match result {
+            Ok(_) => {
+                progress.advance(*target_size);
+                println!("{}", messages::patch_applied(source_file_path));
+                tracing::info!(
+                    file = %detail.source_path,
+                    patch = %detail.patch_path,
+                    target_crc = detail.target_crc,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    "Patch appliqué"
+                );
+                if let Some((patch_size, target_size)) = log_patch_stats(patch_file_path) {
+                    total_patch_size += patch_size;
+                    total_patched_size += target_size;
+                }
+                install_manifest.files.push(ManifestEntry {
+                    path: PathBuf::from(&detail.source_path),
+                    backup,
+                    backup_crc,
+                    crc: detail.target_crc.or_else(|| File::open(&write_path).ok().and_then(|file| calculate_crc32_stream(file).ok())),
+                    ..Default::default()
+                });
+                manifest::save_pending(state_dir, &install_manifest)?;
+            }
             Err(e) => {
-                eprintln!("ERREUR lors de l'application du patch sur {:?} : {}", source_file_path, e);
+                tracing::error!(file = %detail.source_path, patch = %detail.patch_path, error = %e, "Échec du patch");
+                eprintln!("{}", messages::patch_failed(source_file_path, &e));
+                if e.is::<SourceChanged>() {
+                    // Le fichier du jeu n'a pas été écrit : le restaurer écraserait sa nouvelle version.
+                    // La sauvegarde qui vient d'être faite ne correspond plus à rien, elle est supprimée.
+                    if planned.resume_backup.is_none() && backup.is_some() {
+                        let _ = fs::remove_file(&backup_file_path);
+                    }
+                    let steam = process::find_running_steam();
+                    if !steam.is_empty() {
+                        eprintln!("{}", messages::steam_running(&process::describe(&steam)));
+                    }
+                    return Err(e);
+                }
                 // Essaie de restaurer depuis la sauvegarde. Pas sûr que ça soit hyper utile au final.
-                eprintln!("Tentative de restauration depuis {:?}", backup_file_path);
+                eprintln!("{}", messages::trying_restore(&backup_file_path));
                 if backup_file_path.exists() {
-                     match std::fs::copy(&backup_file_path, &source_file_path) {
-                         Ok(_) => eprintln!("Restauration depuis la sauvegarde réussie."),
-                         Err(restore_err) => eprintln!("ERREUR CRITIQUE : Impossible de restaurer {:?} depuis la sauvegarde ! Erreur: {}", source_file_path, restore_err),
+                     let restore_result = if backup::is_compressed(&backup_file_path) {
+                         backup::decompress_backup(&backup_file_path, &write_path, backup_crc).map(|_| 0)
+                     } else {
+                         // Copier en place écraserait aussi une sauvegarde par lien physique (même inode).
+                         fsutil::copy_file(&backup_file_path, &write_path).map(|_| 0).map_err(|e| e.into())
+                     };
+                     match restore_result {
+                         Ok(_) => eprintln!("{}", messages::restore_succeeded()),
+                         Err(restore_err) => eprintln!("{}", messages::restore_failed(source_file_path, &restore_err)),
                      }
                 } else {
-                     eprintln!("ERREUR CRITIQUE : Sauvegarde {:?} non trouvée, impossible de restaurer.", backup_file_path);
+                     eprintln!("{}", messages::backup_not_found(&backup_file_path));
+                }
+                if invalid_container {
+                    eprintln!("{}", messages::rolling_back_install());
+                    rollback_partial_install(game_dir, state_dir, &install_manifest)?;
                 }
                 return Err(e); 
             }
         }
     }
     
-    copy_extra_files(&extract_dir, game_dir)?; 
+    progress.finish_phase();
+    if total_patched_size > 0 {
+        tracing::info!(
+            "Total : {} de patchs pour {} de fichiers produits (ratio {:.1} %).",
+            format_size(total_patch_size), format_size(total_patched_size),
+            total_patch_size as f64 * 100.0 / total_patched_size as f64
+        );
+    }
 
-    println!("\n--- Application des patchs terminée ---");
+    if deadline.expired() {
+        return abort_on_deadline(game_dir, state_dir, &install_manifest, &deadline);
+    }
+    if selection.patches_only {
+        println!("{}", messages::extras_skipped());
+    } else {
+        let extra_entries = copy_extra_files(&extract_dir, game_dir, selection, &mut progress)?;
+        install_manifest.files.extend(extra_entries);
+    }
+    progress.finish();
+
+    if deadline.expired() {
+        return abort_on_deadline(game_dir, state_dir, &install_manifest, &deadline);
+    }
+    if !platform_info.post_install.is_empty() {
+        if options.allow_hooks {
+            hooks::run_post_install(&platform_info.post_install, game_dir, &mut install_manifest.files)?;
+        } else {
+            println!("{}", messages::hooks_ignored(platform_info.post_install.len()));
+        }
+    }
+
+    println!("{}", messages::apply_done());
+    if !selection.patches_only {
+        patchignored.extend(
+            WalkDir::new(&extract_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && e.path().extension().is_none_or(|ext| ext != "bps"))
+                .filter_map(|e| e.path().strip_prefix(&extract_dir).ok().map(Path::to_path_buf))
+                .filter(|relative_path| !is_patch_metadata(relative_path) && selection.patchignores_extra(relative_path))
+                .map(|relative_path| index_style_path(&relative_path)),
+        );
+    }
+    if !patchignored.is_empty() {
+        println!("{}", messages::patchignore_summary(patchignored.len()));
+        for path in &patchignored {
+            println!("  {}", path);
+        }
+    }
+
+    let manifest_path = manifest::save_manifest(state_dir, &install_manifest)?;
+    manifest::clear_pending(state_dir, game_dir);
+    println!("{}", messages::manifest_saved(&manifest_path));
+    tracing::info!(game_dir = ?game_dir, platform = %platform_key, files = install_manifest.files.len(), "Installation terminée");
 
     Ok(())
 }
 
-fn run_uninstall_process(game_dir: &Path) -> Result<(), Box<dyn Error>> {
-    println!("\n--- Début de la désinstallation du patch ---");
-    println!("Répertoire du jeu cible : {:?}", game_dir);
+/// Choisit la sauvegarde à restaurer pour chaque fichier : (sauvegarde, original) à restaurer,
+/// puis les sauvegardes redondantes, conservées sans être restaurées.
+fn choose_backups(backups_by_original: BTreeMap<PathBuf, Vec<PathBuf>>) -> (Vec<(PathBuf, PathBuf)>, Vec<PathBuf>) {
+    let mut redundant = Vec::new();
+    let mut to_restore = Vec::new();
+    for (original_path, mut backups) in backups_by_original {
+        // Le choix ne dépend pas de l'ordre du parcours : la plus ancienne sauvegarde est celle de
+        // la version d'origine, les suivantes ont sauvegardé un fichier déjà patché.
+        backups.sort_by_key(|path| (fs::metadata(path).and_then(|m| m.modified()).ok(), path.clone()));
+        let mut backups = backups.into_iter();
+        let Some(oldest) = backups.next() else { continue };
+        let others: Vec<PathBuf> = backups.collect();
+        if !others.is_empty() {
+            eprintln!("{}", messages::redundant_backups_for(others.len() + 1, &original_path, &oldest));
+            redundant.extend(others);
+        }
+        to_restore.push((oldest, original_path));
+    }
+    (to_restore, redundant)
+}
+
+/// Fichiers modifiés d'après les manifestes mais dont la sauvegarde a disparu : ils restent
+/// patchés, seule une vérification des fichiers du jeu (Steam) peut les rétablir. Ceux que
+/// `restored_paths` (relatifs au jeu) indique déjà restaurés n'en font pas partie.
+fn unrecoverable_files(
+    game_dir: &Path,
+    manifest_entries: &[ManifestEntry],
+    restored_paths: &[PathBuf],
+    to_restore: &[(PathBuf, PathBuf)],
+) -> Vec<PathBuf> {
+    let mut unrecoverable: Vec<PathBuf> = manifest_entries
+        .iter()
+        .filter(|entry| entry.backup.is_some() && !restored_paths.contains(&entry.path))
+        .map(|entry| game_dir.join(&entry.path))
+        .filter(|path| !to_restore.iter().any(|(_, original)| original == path))
+        .collect();
+    unrecoverable.sort();
+    unrecoverable.dedup();
+    unrecoverable
+}
+
+/// Avec `dry_run`, s'arrête après avoir listé les fichiers à restaurer : rien n'est modifié.
+/// Sinon, la liste est suivie d'une demande de confirmation, sauf avec `assume_yes`.
+fn run_uninstall_process(game_dir: &Path, state_dir: &Path, dry_run: bool, assume_yes: bool) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::uninstall_header());
+    check_game_dir(game_dir)?;
+    let game_dir = &absolute_game_dir(game_dir)?;
+    println!("{}", messages::target_game_dir(game_dir));
 
     let mut restored_count = 0;
     let mut error_count = 0;
 
-     if !game_dir.is_dir() {
-        return Err(format!("Le répertoire de jeu spécifié {:?} n'existe pas ou n'est pas un répertoire.", game_dir).into());
+    if !dry_run {
+        ensure_writable(game_dir)?;
     }
 
+    // Les manifestes ne font que compléter le parcours des .bak : illisibles ou incohérents, ils
+    // sont ignorés et la restauration se fait quand même à partir des sauvegardes présentes.
+    let manifest_entries: Vec<ManifestEntry> = match manifest::list_manifests(state_dir, game_dir) {
+        Ok(manifests) => manifests
+            .into_iter()
+            .flat_map(|(path, m)| m.files.into_iter().map(move |entry| (path.clone(), entry)))
+            .filter_map(|(path, entry)| {
+                let is_enclosed = |p: &Path| p.to_str().and_then(archive::enclosed_path).is_some();
+                if is_enclosed(&entry.path) && entry.backup.as_deref().is_none_or(is_enclosed) {
+                    Some(entry)
+                } else {
+                    eprintln!("{}", messages::manifest_entry_invalid(&path, &entry.path));
+                    None
+                }
+            })
+            .collect(),
+        Err(e) => {
+            eprintln!("{}", messages::history_unreadable(&e));
+            Vec::new()
+        }
+    };
+    // CRC des sauvegardes compressées, connus grâce aux manifestes d'installation.
+    let backup_crcs: HashMap<PathBuf, u32> = manifest_entries
+        .iter()
+        .filter_map(|entry| Some((game_dir.join(entry.backup.as_ref()?), entry.backup_crc?)))
+        .collect();
+
+    // Plusieurs sauvegardes peuvent correspondre au même fichier (data.win.bak et data.win.bak.zst
+    // après deux installations, ancien nommage DELTARUNE..bak) : elles sont regroupées par original.
+    let mut backups_by_original: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
     for entry_result in WalkDir::new(game_dir).into_iter().filter_map(|e| e.ok()) {
         let bak_path = entry_result.path();
-
-        if !(bak_path.is_file() && bak_path.extension().map_or(false, |ext| ext == "bak")) {
+        if !(bak_path.is_file() && (backup::is_compressed(bak_path) || bak_path.extension().is_some_and(|ext| ext == "bak"))) {
             continue;
         }
+        match backup::original_path_for(bak_path) {
+            Some(original_path) => backups_by_original.entry(original_path).or_default().push(bak_path.to_path_buf()),
+            None => {
+                eprintln!("{}", messages::backup_original_unknown(bak_path));
+                error_count += 1;
+            }
+        }
+    }
 
-        let original_path = bak_path.with_extension("");
+    let (to_restore, redundant) = choose_backups(backups_by_original);
 
-        if original_path == bak_path {
-             eprintln!("ATTENTION : Impossible de déterminer le nom original pour {:?}. Fichier ignoré.", bak_path);
-             error_count += 1;
-             continue;
+    // Désinstallation interrompue puis relancée : les fichiers déjà restaurés n'ont plus de
+    // sauvegarde, ce qui est normal. Les autres reprennent là où elle s'était arrêtée.
+    let mut restored_paths = manifest::load_uninstall_journal(state_dir, game_dir);
+    if !restored_paths.is_empty() {
+        println!("{}", messages::uninstall_resuming(restored_paths.len(), to_restore.len()));
+    }
+
+    let unrecoverable = unrecoverable_files(game_dir, &manifest_entries, &restored_paths, &to_restore);
+
+    if dry_run {
+        println!("{}", messages::uninstall_dry_run_header());
+        println!("{}", messages::files_would_be_restored(to_restore.len()));
+        for (bak_path, original_path) in &to_restore {
+            println!("  {:?} <- {:?}", original_path, bak_path);
+        }
+        for path in &redundant {
+            println!("{}", messages::redundant_backup_kept(path));
         }
+        for path in &unrecoverable {
+            println!("{}", messages::unrecoverable_file(path));
+        }
+        if error_count > 0 {
+            return Err(messages::backups_not_restorable(error_count).into());
+        }
+        return Ok(());
+    }
+    if !to_restore.is_empty() && !assume_yes {
+        println!("{}", messages::files_to_restore(to_restore.len()));
+        for (_, original_path) in &to_restore {
+            println!("  {:?}", original_path);
+        }
+        if !confirm(&messages::restore_prompt())? {
+            return Err(messages::uninstall_cancelled().into());
+        }
+    }
+
+    for (bak_path, original_path) in &to_restore {
+        let bak_path = bak_path.as_path();
+        let compressed = backup::is_compressed(bak_path);
+        let mut record_restored = || {
+            if let Ok(relative) = original_path.strip_prefix(game_dir) {
+                restored_paths.push(relative.to_path_buf());
+                if let Err(e) = manifest::save_uninstall_journal(state_dir, game_dir, &restored_paths) {
+                    eprintln!("{}", messages::uninstall_journal_failed(&e));
+                }
+            }
+        };
 
-        println!("\nSauvegarde trouvée : {:?}", bak_path);
+        println!("{}", messages::backup_found(bak_path));
+        // Un fichier patché à travers un lien symbolique est restauré de même : le lien est conservé.
+        let original_path = &match fsutil::resolve_link_within(game_dir, original_path) {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}", messages::backup_kept_after_error(&e, bak_path));
+                error_count += 1;
+                continue;
+            }
+        };
 
-        if original_path.exists() {
-            println!("Suppression du fichier patché actuel : {:?}", original_path);
-            match fs::remove_file(&original_path) {
-                Ok(_) => { /* Succès */ }
+        if compressed {
+            // Décompressée directement par-dessus le fichier patché : rien n'est supprimé avant que le CRC soit vérifié.
+            println!("{}", messages::decompressing_backup(bak_path, original_path));
+            match backup::decompress_backup(bak_path, original_path, backup_crcs.get(bak_path).copied()) {
+                Ok(()) => {
+                    if let Err(e) = fs::remove_file(bak_path) {
+                        eprintln!("{}", messages::backup_remove_failed(bak_path, &e));
+                    }
+                    println!("{}", messages::file_restored(original_path));
+                    record_restored();
+                    restored_count += 1;
+                }
                 Err(e) => {
-                    eprintln!("ERREUR : Impossible de supprimer {:?}: {}. Annulation pour ce fichier.", original_path, e);
+                    eprintln!("{}", messages::restore_from_failed(original_path, bak_path, &e));
                     error_count += 1;
-                    continue; 
                 }
             }
-        } else {
-            println!("Note : Le fichier {:?} n'existait pas (peut-être déjà supprimé?).", original_path);
+            continue;
+        }
+
+        if !original_path.exists() {
+            println!("{}", messages::original_missing(original_path));
         }
 
-        println!("Restauration de {:?} -> {:?}", bak_path, original_path);
-        match fs::rename(&bak_path, &original_path) {
+        // Le renommage remplace le fichier patché d'un seul coup : en cas d'échec, le fichier
+        // patché et la sauvegarde sont tous deux intacts.
+        println!("{}", messages::restoring(bak_path, original_path));
+        match fs::rename(bak_path, original_path) {
             Ok(_) => {
-                println!("Fichier {:?} restauré avec succès.", original_path);
+                println!("{}", messages::file_restored(original_path));
+                record_restored();
                 restored_count += 1;
             }
             Err(e) => {
-                eprintln!("ERREUR : Impossible de renommer {:?} en {:?}: {}. Le fichier .bak est conservé.", bak_path, original_path, e);
+                eprintln!("{}", messages::rename_backup_failed(bak_path, original_path, &e));
+                error_count += 1;
+            }
+        }
+    }
+
+    println!("{}", messages::uninstall_done());
+    println!("{}", messages::files_restored(restored_count));
+    if !redundant.is_empty() {
+        // Conservées par prudence, mais une prochaine désinstallation les restaurerait à leur tour.
+        eprintln!("{}", messages::redundant_backups());
+        for path in &redundant {
+            eprintln!("  {:?}", path);
+        }
+    }
+    if !unrecoverable.is_empty() {
+        eprintln!("{}", messages::unrecoverable_files());
+        for path in &unrecoverable {
+            eprintln!("  {:?}", path);
+        }
+    }
+    if error_count > 0 {
+        println!("{}", messages::errors_encountered(error_count));
+        return Err(messages::uninstall_failed(error_count).into());
+    }
+
+    // Les sauvegardes ont été consommées : l'historique de ce répertoire n'a plus de sens.
+    // Les fichiers du jeu sont déjà restaurés : un historique impossible à effacer n'est pas un échec.
+    manifest::clear_uninstall_journal(state_dir, game_dir);
+    match manifest::clear_history(state_dir, game_dir) {
+        Ok(0) => {}
+        Ok(cleared) => println!("{}", messages::history_cleared(cleared)),
+        Err(e) => eprintln!("{}", messages::history_clear_failed(&e)),
+    }
+
+    Ok(())
+}
+
+fn run_rollback_last(game_dir: &Path, state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::rollback_header());
+    println!("{}", messages::target_game_dir(game_dir));
+
+    check_game_dir(game_dir)?;
+    ensure_writable(game_dir)?;
+
+    let (manifest_path, last_install) = manifest::latest_manifest(state_dir, game_dir)?
+        .ok_or_else(|| messages::no_installation_recorded(game_dir, state_dir))?;
+    println!("{}", messages::rollback_manifest(&manifest_path, &last_install.platform, last_install.files.len()));
+
+    let mut restored_count = 0;
+    let mut error_count = 0;
+
+    // On défait dans l'ordre inverse de l'installation.
+    for entry in last_install.files.iter().rev() {
+        let file_path = game_dir.join(&entry.path);
+
+        match &entry.backup {
+            Some(backup) => {
+                let backup_path = game_dir.join(backup);
+                if !backup_path.exists() {
+                    eprintln!("{}", messages::rollback_backup_missing(&backup_path, &file_path));
+                    error_count += 1;
+                    continue;
+                }
+                println!("{}", messages::restoring(&backup_path, &file_path));
+                let restore_result = if backup::is_compressed(&backup_path) {
+                    backup::decompress_backup(&backup_path, &file_path, entry.backup_crc)
+                        .and_then(|_| fs::remove_file(&backup_path).map_err(|e| e.into()))
+                } else {
+                    fs::rename(&backup_path, &file_path).map_err(|e| e.into())
+                };
+                match restore_result {
+                    Ok(_) => restored_count += 1,
+                    Err(e) => {
+                        eprintln!("{}", messages::rollback_restore_failed(&file_path, &backup_path, &e));
+                        error_count += 1;
+                    }
+                }
+            }
+            None if entry.created => {
+                if file_path.exists() {
+                    println!("{}", messages::removing_created_file(&file_path));
+                    if let Err(e) = fs::remove_file(&file_path) {
+                        eprintln!("{}", messages::file_remove_failed(&file_path, &e));
+                        error_count += 1;
+                        continue;
+                    }
+                }
+                restored_count += 1;
+            }
+            None => {
+                eprintln!("{}", messages::no_backup_recorded(&file_path));
                 error_count += 1;
-                // Le fichier original est supprimé mais le .bak n'a pas pu être renommé... Situation délicate. Pour l'instant je pense laisser comme ça.
             }
         }
     }
 
-    println!("\n--- Désinstallation terminée ---");
-    println!("Fichiers restaurés : {}", restored_count);
+    println!("{}", messages::rollback_done());
+    println!("{}", messages::files_restored(restored_count));
     if error_count > 0 {
-        println!("Erreurs rencontrées : {}", error_count);
-        return Err(format!("{} erreurs se sont produites pendant la désinstallation.", error_count).into());
+        println!("{}", messages::errors_encountered(error_count));
+        return Err(messages::rollback_failed(error_count, &manifest_path).into());
     }
 
+    fs::remove_file(&manifest_path)?;
+    println!("{}", messages::manifest_removed(&manifest_path));
+
     Ok(())
 }
 
 fn main() {
     let args = Args::parse(); 
+    output::init(args.color);
+    let command = args.command.unwrap_or(Command::Wizard);
+    let wizard = matches!(command, Command::Wizard);
+    let machine_output = matches!(
+        command,
+        Command::Install { machine_progress: true, .. } | Command::Install { progress: ProgressMode::Machine, .. }
+    );
+    if args.quiet {
+        output::set_stdout_mode(output::StdoutMode::Quiet);
+    } else if machine_output {
+        output::set_stdout_mode(output::StdoutMode::ToStderr);
+    }
+    i18n::init(args.lang_ui);
+    output::init_tracing(args.verbose, args.log_format);
+    let state_dir = args.state_dir.unwrap_or_else(manifest::default_state_dir);
+    // Configuration ou URL d'index invalide : erreur affichée comme celle d'une commande,
+    // avec la pause de l'assistant, pour que le message reste lisible après un double-clic.
+    let config = match config::load(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => return finish(Err(e), wizard),
+    };
+    if let Some(max_idle) = config.network.max_idle_connections {
+        net::set_max_idle_per_host(max_idle);
+    }
+    if let Err(e) = init_index_url(args.index_url, &config) {
+        return finish(Err(e), wizard);
+    }
 
-    let result = match args.command {
-        Command::Install { game_dir } => {
-            println!("Lancement du processus d'installation pour : {:?}", game_dir);
-            run_install_process(&game_dir) 
+    let result = match command {
+        Command::Wizard => wizard::run_wizard(&state_dir, &config),
+        Command::Install { game_dir, profile, platform, all_platforms, allow_hooks, compress_backups, strict, progress, machine_progress, suspend_running_game, resume, temp_dir, skip_exe_check, skip_game_check, mirror, deadline, patch_version, from_dir, from_file, local_index, download_chunk_size, chapter, patches_only, preview, dry_run, torrent, fsync, output_dir, copy_game, use_cache, offline, max_extras, incremental, validate_gamemaker, on_missing_source, retry_install, atomic_swap, variant } => {
+            let options = InstallOptions {
+                assume_yes: args.yes,
+                allow_hooks,
+                compress_backups,
+                strict,
+                progress: if machine_progress { ProgressMode::Machine } else { progress },
+                suspend_running_game,
+                resume,
+                temp_dir,
+                skip_exe_check,
+                skip_game_check,
+                mirror,
+                deadline,
+                patch_version,
+                from_dir,
+                from_file,
+                local_index,
+                download_chunk_size: download_chunk_size as usize * 1024,
+                selection: Selection::new(chapter, patches_only, variant),
+                preview,
+                dry_run,
+                torrent,
+                fsync,
+                use_cache,
+                offline,
+                max_extras,
+                incremental,
+                validate_gamemaker,
+                on_missing_source,
+                retry_install,
+                platform: None,
+            };
+            resolve_platform_targets(game_dir, profile, platform, all_platforms, &state_dir, &config, &options).and_then(|mut targets| {
+                if targets.len() > 1 {
+                    if output_dir.is_some() {
+                        return Err(messages::output_dir_single_platform().into());
+                    }
+                    return run_multi_platform_install(&targets, &state_dir, &options, atomic_swap);
+                }
+                let mut target = targets.remove(0);
+                if let Some(output_dir) = output_dir {
+                    prepare_output_dir(&target.game_dir, &output_dir, copy_game)?;
+                    target.game_dir = output_dir;
+                }
+                install_target(&target, &state_dir, &options, atomic_swap)
+            })
+        }
+        Command::Update { game_dir, profile } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                update::run_update(&target, &state_dir, &InstallOptions::defaults(args.yes))
+            })
+        }
+        Command::Uninstall { game_dir, profile, dry_run } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                println!("{}", messages::uninstall_launch(&target.game_dir));
+                let _lock = lock::lock_game_dir(&state_dir, &target.game_dir)?;
+                if !dry_run {
+                    cleanup::clean_stale(&[&target.game_dir]);
+                }
+                run_uninstall_process(&target.game_dir, &target.state_dir, dry_run, args.yes)
+            })
         }
-        Command::Uninstall { game_dir } => {
-            println!("Lancement du processus de désinstallation pour : {:?}", game_dir);
-            run_uninstall_process(&game_dir)
+        Command::RollbackLast { game_dir, profile } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                println!("{}", messages::rollback_launch(&target.game_dir));
+                let _lock = lock::lock_game_dir(&state_dir, &target.game_dir)?;
+                cleanup::clean_stale(&[&target.game_dir]);
+                run_rollback_last(&target.game_dir, &target.state_dir)
+            })
         }
+        Command::VerifyBackups { game_dir, profile, repair, platform } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                let _lock = if repair { Some(lock::lock_game_dir(&state_dir, &target.game_dir)?) } else { None };
+                verify_backups::run_verify_backups(&target.game_dir, platform.or(target.platform).as_deref(), repair)
+            })
+        }
+        Command::Status { game_dir, profile } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                status::run_status(&target.game_dir, &target.state_dir)
+            })
+        }
+        Command::Verify { game_dir, profile, platform } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                verify::run_verify(&target.game_dir, &target.state_dir, platform.or(target.platform).as_deref(), index_url())
+            })
+        }
+        Command::Audit { game_dir, profile } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                audit::run_audit(&target.game_dir, &target.state_dir)
+            })
+        }
+        Command::CheckCrc { game_dir, profile, platform } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                check_crc::run_check_crc(&target.game_dir, platform.or(target.platform).as_deref(), index_url())
+            })
+        }
+        Command::ValidateIndex { patches_dir, index, platform } => {
+            validate_index::run_validate_index(&patches_dir, index.as_deref(), platform.as_deref())
+        }
+        Command::ExpectedLayout { platform } => layout::run_expected_layout(&platform, index_url()),
+        Command::ShowChanges { source, patch } => show_changes::run_show_changes(&source, &patch),
+        #[cfg(feature = "testing")]
+        Command::CorruptTest { file, at, byte } => corrupt::run_corrupt_test(&file, at, byte),
+        #[cfg(feature = "benchmark")]
+        Command::Benchmark { source, patch, iterations } => benchmark::run_benchmark(&source, &patch, iterations),
+        Command::Profile { action } => match action {
+            ProfileAction::List => profiles::run_profile_list(&state_dir),
+            ProfileAction::Add { name, game_dir, platform } => config
+                .resolve_game_dir(game_dir)
+                .and_then(|game_dir| profiles::run_profile_add(&state_dir, &name, &game_dir, platform)),
+            ProfileAction::Remove { name } => profiles::run_profile_remove(&state_dir, &name),
+        },
+        Command::ExportIndex { patches_dir, source_dir, output, platform, file_url } => {
+            export::run_export_index(&patches_dir, &source_dir, &output, &platform, file_url.as_deref())
+        }
+        Command::Versions => versions::run_versions(&versions_url()),
+        Command::Ping => ping::run_ping(index_url()),
+        // Pas de check_game_dir ici : un répertoire inaccessible fait partie du diagnostic.
+        Command::Doctor { game_dir, profile, temp_dir } => resolve_target(game_dir, profile, &state_dir, &config)
+            .and_then(|target| doctor::run_doctor(&target.game_dir, &target.state_dir, temp_dir.as_deref(), index_url())),
+        Command::Serve { port } => {
+            // Les sous-commandes lancées par le serveur partagent son état, sa configuration et sa langue.
+            let mut base_args: Vec<std::ffi::OsString> = vec!["--state-dir".into(), state_dir.clone().into()];
+            if let Some(config_path) = &args.config {
+                base_args.extend(["--config".into(), config_path.clone().into()]);
+            }
+            let lang = match i18n::lang() {
+                i18n::Lang::Fr => "fr",
+                i18n::Lang::En => "en",
+            };
+            base_args.extend(["--lang-ui".into(), lang.into(), "--index-url".into(), index_url().into()]);
+            serve::run_serve(port, base_args)
+        }
+        Command::ExportState { game_dir, profile, output } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                state_archive::run_export_state(&target.game_dir, &target.state_dir, &output)
+            })
+        }
+        Command::ImportState { game_dir, profile, input } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                let _lock = lock::lock_game_dir(&state_dir, &target.game_dir)?;
+                cleanup::clean_stale(&[&target.game_dir]);
+                state_archive::run_import_state(&target.game_dir, &target.state_dir, &input)
+            })
+        }
+        Command::CleanTemp { game_dir, profile, temp_dir, list } => {
+            resolve_target(game_dir, profile, &state_dir, &config).and_then(|target| {
+                check_game_dir(&target.game_dir)?;
+                let _lock = if list { None } else { Some(lock::lock_game_dir(&state_dir, &target.game_dir)?) };
+                let download_dir = download_dir_for(temp_dir.as_deref());
+                cleanup::run_clean_temp(&[&target.game_dir, &target.state_dir, &download_dir], list, args.yes)
+            })
+        }
+        Command::Convert { from, to, source } => patchfmt::run_convert(&from, &to, &source),
     };
+    finish(result, wizard);
+}
 
+/// Affiche le résultat de la commande et termine le programme en cas d'erreur.
+/// Lancé en assistant (`wizard`), attend que l'utilisateur ferme la fenêtre dans les deux cas.
+fn finish(result: Result<(), Box<dyn Error>>, wizard: bool) {
     if let Err(e) = result {
-        eprintln!("\n--- ERREUR ---");
+        tracing::error!(error = %e, "Échec de la commande");
+        eprintln!("{}", messages::error_banner());
         eprintln!("{}", e);
         let mut source = e.source();
         while let Some(s) = source {
-            eprintln!("  causé par: {}", s);
+            eprintln!("{}", messages::caused_by(s));
             source = s.source();
         }
         eprintln!("---------------");
+        if wizard {
+            wizard::wait_before_exit();
+        }
+        if e.downcast_ref::<deadline::DeadlineExceeded>().is_some() {
+            std::process::exit(deadline::EXIT_CODE);
+        }
         std::process::exit(1);
     } else {
-        println!("\nOpération terminée avec succès ! \nBon jeu !");
+        println!("{}", messages::success());
+        if wizard {
+            wizard::wait_before_exit();
+        }
     }
 }
\ No newline at end of file
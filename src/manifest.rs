@@ -0,0 +1,250 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::messages;
+
+/// Fichier touché par une installation.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ManifestEntry {
+    /// Chemin du fichier, relatif au répertoire du jeu.
+    pub path: PathBuf,
+
+    /// Sauvegarde du fichier d'origine, relative au répertoire du jeu.
+    #[serde(default)]
+    pub backup: Option<PathBuf>,
+
+    /// Vrai si le fichier n'existait pas avant l'installation.
+    #[serde(default)]
+    pub created: bool,
+
+    /// CRC32 du contenu sauvegardé, vérifié à la restauration d'une sauvegarde compressée.
+    #[serde(rename = "backupCrc", default, skip_serializing_if = "Option::is_none")]
+    pub backup_crc: Option<u32>,
+
+    /// CRC32 du fichier tel que l'installation l'a laissé, vérifié par audit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crc: Option<u32>,
+}
+
+/// Trace d'une installation, écrite dans l'historique du répertoire d'état.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InstallManifest {
+    #[serde(rename = "installedAt")]
+    pub installed_at: u64,
+
+    #[serde(rename = "gameDir")]
+    pub game_dir: PathBuf,
+
+    pub platform: String,
+
+    /// Version du patch demandée avec --patch-version ; absente pour la dernière version publiée.
+    #[serde(rename = "patchVersion", default, skip_serializing_if = "Option::is_none")]
+    pub patch_version: Option<String>,
+
+    pub files: Vec<ManifestEntry>,
+}
+
+impl InstallManifest {
+    pub fn new(game_dir: &Path, platform: &str) -> Self {
+        let installed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        InstallManifest {
+            installed_at,
+            game_dir: normalize_game_dir(game_dir),
+            platform: platform.to_string(),
+            patch_version: None,
+            files: Vec::new(),
+        }
+    }
+}
+
+/// Répertoire d'état par défaut (~/.local/share/patcher_drfr sous Linux).
+pub fn default_state_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("patcher_drfr")
+}
+
+fn history_dir(state_dir: &Path) -> PathBuf {
+    state_dir.join("history")
+}
+
+// Manifeste de l'installation en cours, réécrit après chaque fichier patché.
+// Un par répertoire de jeu, hors de l'historique : il n'y entre qu'une fois l'installation terminée.
+fn pending_manifest_path(state_dir: &Path, game_dir: &Path) -> PathBuf {
+    state_dir.join("pending").join(format!("{}.json", game_dir_key(game_dir)))
+}
+
+/// Clé courte propre à un répertoire de jeu, pour nommer les fichiers d'état qui lui sont associés.
+pub fn game_dir_key(game_dir: &Path) -> String {
+    format!("{:08x}", crate::calculate_crc32(normalize_game_dir(game_dir).to_string_lossy().as_bytes()))
+}
+
+// Les manifestes sont indexés par le chemin absolu du jeu pour qu'un même
+// répertoire passé sous deux formes (relative/absolue) partage son historique.
+fn normalize_game_dir(game_dir: &Path) -> PathBuf {
+    fs::canonicalize(game_dir).unwrap_or_else(|_| game_dir.to_path_buf())
+}
+
+/// Enregistre le manifeste dans l'historique et renvoie son chemin.
+pub fn save_manifest(state_dir: &Path, manifest: &InstallManifest) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = history_dir(state_dir);
+    fs::create_dir_all(&dir)?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let path = dir.join(format!("{}.json", millis));
+
+    crate::fsutil::write_atomic(&path, &serde_json::to_vec_pretty(manifest)?)?;
+    Ok(path)
+}
+
+/// Ajoute à l'historique de `game_dir` un manifeste exporté depuis un autre répertoire (import-state).
+/// Il garde sa date d'installation d'origine, qui détermine sa place dans l'historique.
+pub fn import_manifest(state_dir: &Path, mut manifest: InstallManifest, game_dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = history_dir(state_dir);
+    fs::create_dir_all(&dir)?;
+    manifest.game_dir = normalize_game_dir(game_dir);
+
+    let mut suffix = 0;
+    let path = loop {
+        let candidate = dir.join(format!("{}-import{}.json", manifest.installed_at, suffix));
+        if !candidate.exists() {
+            break candidate;
+        }
+        suffix += 1;
+    };
+    crate::fsutil::write_atomic(&path, &serde_json::to_vec_pretty(&manifest)?)?;
+    Ok(path)
+}
+
+/// Rattache à `to` les manifestes enregistrés pour `from`, qui peut ne plus exister (il doit alors
+/// être donné sous sa forme absolue). Sert après --atomic-swap : la copie patchée a pris la place du jeu.
+pub fn relocate_history(state_dir: &Path, from: &Path, to: &Path) -> Result<usize, Box<dyn Error>> {
+    let manifests = list_manifests(state_dir, from)?;
+    let count = manifests.len();
+    let to = normalize_game_dir(to);
+    for (path, mut manifest) in manifests {
+        manifest.game_dir = to.clone();
+        crate::fsutil::write_atomic(&path, &serde_json::to_vec_pretty(&manifest)?)?;
+    }
+    Ok(count)
+}
+
+/// Liste les manifestes enregistrés pour ce répertoire de jeu, du plus ancien au plus récent.
+pub fn list_manifests(state_dir: &Path, game_dir: &Path) -> Result<Vec<(PathBuf, InstallManifest)>, Box<dyn Error>> {
+    let dir = history_dir(state_dir);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let game_dir = normalize_game_dir(game_dir);
+    let mut manifests = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        let manifest: InstallManifest = match fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_slice(&data).map_err(|e| e.to_string()))
+        {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("{}", messages::manifest_unreadable(&path, &e));
+                continue;
+            }
+        };
+
+        if manifest.game_dir == game_dir {
+            manifests.push((path, manifest));
+        }
+    }
+
+    manifests.sort_by(|(path_a, a), (path_b, b)| {
+        a.installed_at.cmp(&b.installed_at).then_with(|| path_a.cmp(path_b))
+    });
+    Ok(manifests)
+}
+
+/// Renvoie le manifeste de la dernière installation de ce répertoire de jeu.
+pub fn latest_manifest(state_dir: &Path, game_dir: &Path) -> Result<Option<(PathBuf, InstallManifest)>, Box<dyn Error>> {
+    Ok(list_manifests(state_dir, game_dir)?.pop())
+}
+
+/// Supprime tout l'historique de ce répertoire de jeu (après une désinstallation complète).
+pub fn clear_history(state_dir: &Path, game_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    let manifests = list_manifests(state_dir, game_dir)?;
+    for (path, _) in &manifests {
+        fs::remove_file(path)?;
+    }
+    Ok(manifests.len())
+}
+
+/// Enregistre l'état de l'installation en cours, pour pouvoir la reprendre après une interruption.
+pub fn save_pending(state_dir: &Path, manifest: &InstallManifest) -> Result<(), Box<dyn Error>> {
+    let path = pending_manifest_path(state_dir, &manifest.game_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    crate::fsutil::write_atomic(&path, &serde_json::to_vec_pretty(manifest)?)
+}
+
+/// Manifeste d'une installation interrompue de ce répertoire de jeu, s'il en existe une.
+pub fn load_pending(state_dir: &Path, game_dir: &Path) -> Option<InstallManifest> {
+    let path = pending_manifest_path(state_dir, game_dir);
+    let data = fs::read(&path).ok()?;
+    match serde_json::from_slice(&data) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            eprintln!("{}", messages::pending_manifest_unreadable(&path, &e));
+            None
+        }
+    }
+}
+
+// Fichiers déjà restaurés par une désinstallation en cours, relatifs au jeu. Relancée après une
+// interruption, la désinstallation sait ainsi que leur sauvegarde a été consommée, pas perdue.
+fn uninstall_journal_path(state_dir: &Path, game_dir: &Path) -> PathBuf {
+    state_dir.join("uninstalling").join(format!("{}.json", game_dir_key(game_dir)))
+}
+
+/// Fichiers restaurés par une désinstallation interrompue de ce répertoire de jeu.
+pub fn load_uninstall_journal(state_dir: &Path, game_dir: &Path) -> Vec<PathBuf> {
+    let path = uninstall_journal_path(state_dir, game_dir);
+    let Ok(data) = fs::read(&path) else { return Vec::new() };
+    serde_json::from_slice(&data).unwrap_or_else(|e| {
+        eprintln!("{}", messages::uninstall_journal_unreadable(&path, &e));
+        Vec::new()
+    })
+}
+
+/// Enregistre les fichiers restaurés jusqu'ici, après chaque restauration.
+pub fn save_uninstall_journal(state_dir: &Path, game_dir: &Path, restored: &[PathBuf]) -> Result<(), Box<dyn Error>> {
+    let path = uninstall_journal_path(state_dir, game_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    crate::fsutil::write_atomic(&path, &serde_json::to_vec_pretty(restored)?)
+}
+
+/// Oublie la désinstallation en cours, une fois terminée.
+pub fn clear_uninstall_journal(state_dir: &Path, game_dir: &Path) {
+    let _ = fs::remove_file(uninstall_journal_path(state_dir, game_dir));
+}
+
+/// Oublie l'installation en cours (terminée ou annulée).
+pub fn clear_pending(state_dir: &Path, game_dir: &Path) {
+    let _ = fs::remove_file(pending_manifest_path(state_dir, game_dir));
+}
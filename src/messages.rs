@@ -0,0 +1,1701 @@
+//! Catalogue des messages traduits de l'interface (voir i18n). Chaque message est une fonction
+//! dont les arguments sont vérifiés à la compilation, avec une chaîne par langue.
+//! Restent en français : les journaux tracing et les erreurs de lecture du footer BPS (bps.rs est
+//! aussi compilé seul par la cible de fuzzing).
+
+use std::path::Path;
+
+use crate::i18n::{lang, Lang};
+
+macro_rules! messages {
+    ($( $(#[$doc:meta])* $name:ident ( $($arg:ident : $ty:ty),* ) { fr: $fr:literal, en: $en:literal } )*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name($($arg: $ty),*) -> String {
+                match lang() {
+                    Lang::Fr => format!($fr),
+                    Lang::En => format!($en),
+                }
+            }
+        )*
+    };
+}
+
+messages! {
+    // --- Général ---
+    confirm_suffix() { fr: "[o/N]", en: "[y/N]" }
+    error_banner() { fr: "\n--- ERREUR ---", en: "\n--- ERROR ---" }
+    caused_by(cause: &dyn std::fmt::Display) { fr: "  causé par: {cause}", en: "  caused by: {cause}" }
+    success() { fr: "\nOpération terminée avec succès ! \nBon jeu !", en: "\nOperation completed successfully! \nEnjoy the game!" }
+    no_answer_possible() {
+        fr: "Aucune réponse possible (entrée standard fermée) : relancez avec --yes pour une utilisation non interactive.",
+        en: "No answer possible (standard input closed): run again with --yes for non-interactive use."
+    }
+    continue_prompt() { fr: "Continuer ?", en: "Continue?" }
+    error_line(e: &dyn std::fmt::Display) { fr: "ERREUR : {e}", en: "ERROR: {e}" }
+    size_gib(size: f64) { fr: "{size:.1} Go", en: "{size:.1} GB" }
+    size_mib(size: f64) { fr: "{size:.0} Mo", en: "{size:.0} MB" }
+    size_kib(size: f64) { fr: "{size:.0} Ko", en: "{size:.0} KB" }
+    size_bytes(size: u64) { fr: "{size} octets", en: "{size} bytes" }
+
+    // --- install ---
+    install_attempt(attempt: u32, max: u32) { fr: "\n=== Tentative {attempt}/{max} ===", en: "\n=== Attempt {attempt}/{max} ===" }
+    install_attempt_failed(attempt: u32, e: &dyn std::fmt::Display, delay: u64) {
+        fr: "ATTENTION : La tentative {attempt} a échoué (erreur passagère) : {e}\nNouvelle tentative dans {delay} s...",
+        en: "WARNING: Attempt {attempt} failed (transient error): {e}\nRetrying in {delay} s..."
+    }
+    install_attempt_succeeded(attempt: u32) { fr: "OK : Installation réussie à la tentative {attempt}.", en: "OK: Installation succeeded on attempt {attempt}." }
+    install_error_permanent() {
+        fr: "Erreur définitive : l'installation n'est pas retentée.",
+        en: "Permanent error: the installation is not retried."
+    }
+    install_start(game_dir: &Path) { fr: "Lancement du processus d'installation pour : {game_dir:?}", en: "Starting installation for: {game_dir:?}" }
+    multi_platform_plan(count: usize) { fr: "Installation de {count} plateformes :", en: "Installing {count} platforms:" }
+    multi_platform_start(platform: &str, game_dir: &Path) {
+        fr: "\n=== Plateforme '{platform}' : {game_dir:?} ===",
+        en: "\n=== Platform '{platform}': {game_dir:?} ==="
+    }
+    multi_platform_install_failed(platform: &str, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Échec de l'installation pour la plateforme '{platform}' : {e}. Passage à la suivante.",
+        en: "ERROR: Installation failed for platform '{platform}': {e}. Moving on to the next one."
+    }
+    multi_platform_summary() { fr: "\n--- Récapitulatif par plateforme ---", en: "\n--- Summary by platform ---" }
+    multi_platform_ok(platform: &str, game_dir: &Path) { fr: "OK : '{platform}' ({game_dir:?}) : patch installé.", en: "OK: '{platform}' ({game_dir:?}): patch installed." }
+    multi_platform_failed(platform: &str, game_dir: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : '{platform}' ({game_dir:?}) : {e}",
+        en: "ERROR: '{platform}' ({game_dir:?}): {e}"
+    }
+    multi_platform_failures(failed: usize, total: usize) {
+        fr: "L'installation a échoué pour {failed} plateforme(s) sur {total}.",
+        en: "Installation failed for {failed} platform(s) out of {total}."
+    }
+    game_dir_chosen(game_dir: &Path) { fr: "Répertoire du jeu choisi : {game_dir:?}", en: "Selected game directory: {game_dir:?}" }
+    game_dir_unresolvable(game_dir: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible de résoudre le chemin absolu du répertoire du jeu {game_dir:?} : {e}",
+        en: "Cannot resolve the absolute path of the game directory {game_dir:?}: {e}"
+    }
+    game_running(running: &str) {
+        fr: "Le jeu semble lancé : {running}. Fermez-le avant d'installer le patch (option avancée : --suspend-running-game).",
+        en: "The game seems to be running: {running}. Close it before installing the patch (advanced option: --suspend-running-game)."
+    }
+    pending_install_found() {
+        fr: "Une installation interrompue a été détectée pour ce répertoire. Utilisez --resume pour la reprendre là où elle s'était arrêtée.",
+        en: "An interrupted installation was detected for this directory. Use --resume to continue where it stopped."
+    }
+    installing_version(version: &str) { fr: "Installation de la version {version} du patch.", en: "Installing version {version} of the patch." }
+    platform_forced(platform: &str) { fr: "Plateforme imposée : '{platform}' (pas de détection automatique).", en: "Platform set explicitly: '{platform}' (no automatic detection)." }
+    invalid_game_dir() {
+        fr: "Le dossier sélectionné semble invalide. Vérifiez que vous avez choisi le bon dossier. Si vous utilisez la version démo de DELTARUNE, vérifiez que vous avez bien activé la beta chapter1.2.lts.test sur Steam.",
+        en: "The selected folder looks invalid. Check that you chose the right folder. If you use the DELTARUNE demo, make sure the chapter1.2.lts.test beta is enabled on Steam."
+    }
+    game_check_skipped() { fr: "Vérification du répertoire du jeu désactivée (--skip-game-check).", en: "Game directory check disabled (--skip-game-check)." }
+    not_deltarune(reason: &str) {
+        fr: "Ce répertoire ne semble pas contenir Deltarune : {reason}. Vérifiez le chemin passé à --game-dir (option avancée : --skip-game-check).",
+        en: "This folder does not seem to contain Deltarune: {reason}. Check the path given to --game-dir (advanced option: --skip-game-check)."
+    }
+    not_deltarune_warning(reason: &str) {
+        fr: "ATTENTION : Ce répertoire ne ressemble pas à une installation habituelle de Deltarune : {reason}. L'installation continue ; si les patchs échouent, vérifiez le chemin passé à --game-dir.",
+        en: "WARNING: This folder does not look like a usual Deltarune installation: {reason}. Installation continues; if patches fail, check the path given to --game-dir."
+    }
+    not_deltarune_app_id(app_id: &str) {
+        fr: "steam_appid.txt désigne l'application Steam {app_id}",
+        en: "steam_appid.txt names Steam application {app_id}"
+    }
+    not_deltarune_no_executable() { fr: "aucun exécutable DELTARUNE à la racine", en: "no DELTARUNE executable at the root" }
+    not_deltarune_data(chapter_data: &str, size: &str) {
+        fr: "{chapter_data} n'est pas un fichier de données GameMaker plausible ({size})",
+        en: "{chapter_data} is not a plausible GameMaker data file ({size})"
+    }
+    exe_check_skipped() { fr: "Vérification de l'exécutable du jeu désactivée (--skip-exe-check).", en: "Game executable check disabled (--skip-exe-check)." }
+    no_patch_for_chapters(chapters: &[u32]) {
+        fr: "Aucun patch de l'index ne concerne le(s) chapitre(s) demandé(s) ({chapters:?}).",
+        en: "No patch in the index applies to the requested chapter(s) ({chapters:?})."
+    }
+    chapters_selected(chapters: &[u32], selected: usize, total: usize) {
+        fr: "Chapitre(s) sélectionné(s) : {chapters:?} ({selected} patch(s) sur {total}).",
+        en: "Selected chapter(s): {chapters:?} ({selected} of {total} patch(es))."
+    }
+    variant_selected(name: &str, description: &str) {
+        fr: "Variante des fichiers supplémentaires : {name} {description}",
+        en: "Extra files variant: {name} {description}"
+    }
+    no_variants(requested: &str) {
+        fr: "Variante '{requested}' demandée, mais l'index ne propose pas de variantes pour cette plateforme.",
+        en: "Variant '{requested}' requested, but the index offers no variants for this platform."
+    }
+    unknown_variant(requested: &str, available: &str) {
+        fr: "Variante '{requested}' inconnue. Variantes disponibles : {available}.",
+        en: "Unknown variant '{requested}'. Available variants: {available}."
+    }
+    patchignore_loaded(path: &Path, count: usize) {
+        fr: "{path:?} : {count} motif(s), les fichiers correspondants ne seront ni patchés ni remplacés.",
+        en: "{path:?}: {count} pattern(s), matching files will be neither patched nor replaced."
+    }
+    patchignore_invalid(path: &Path, error: &dyn std::fmt::Display) {
+        fr: "{path:?} contient un motif invalide : {error}",
+        en: "{path:?} contains an invalid pattern: {error}"
+    }
+    patchignore_skipped(path: &str) { fr: "Ignoré (.patchignore) : {path}", en: "Skipped (.patchignore): {path}" }
+    patchignore_summary(count: usize) {
+        fr: "\nFichiers laissés intacts à cause du .patchignore ({count}) :",
+        en: "\nFiles left untouched because of .patchignore ({count}):"
+    }
+    index_footer_mismatches(count: usize) {
+        fr: "{count} divergence(s) entre les CRC32 de l'index et le footer des patchs : l'index est probablement mal généré.",
+        en: "{count} mismatch(es) between the index CRC32s and the patch footers: the index was probably built incorrectly."
+    }
+    continuing_without_strict(message: &str) {
+        fr: "ATTENTION : {message} Poursuite de l'installation (utilisez --strict pour l'interrompre).",
+        en: "WARNING: {message} Continuing the installation (use --strict to stop it)."
+    }
+    patch_condition_unmet(patch: &str, reason: &str) {
+        fr: "Patch '{patch}' non appliqué : {reason}.",
+        en: "Patch '{patch}' not applied: {reason}."
+    }
+    condition_edition(platform: &str, editions: &str) {
+        fr: "réservé aux éditions {editions}, le jeu est l'édition '{platform}'",
+        en: "limited to the {editions} editions, the game is the '{platform}' edition"
+    }
+    condition_missing_file(file: &str) {
+        fr: "le fichier requis '{file}' est absent du jeu",
+        en: "the required file '{file}' is missing from the game"
+    }
+    no_file_crcs() {
+        fr: "Aucun CRC de fichier fourni par l'index ou l'archive : vérification post-extraction ignorée.",
+        en: "No file CRC provided by the index or the archive: post-extraction check skipped."
+    }
+    verification_header() { fr: "\n--- Vérification des patchs ---", en: "\n--- Checking patches ---" }
+    patch_candidates(count: usize, source_path: &str) { fr: "\n{count} patchs candidats pour le fichier source '{source_path}'", en: "\n{count} candidate patches for source file '{source_path}'" }
+    source_missing(path: &Path) {
+        fr: "ATTENTION : Le fichier source {path:?} est introuvable dans le répertoire du jeu. Passage au suivant.",
+        en: "WARNING: Source file {path:?} was not found in the game directory. Skipping."
+    }
+    source_missing_fatal(path: &Path) {
+        fr: "Le fichier source {path:?} est introuvable dans le répertoire du jeu (--on-missing-source fail). Rien n'a été modifié.",
+        en: "Source file {path:?} was not found in the game directory (--on-missing-source fail). Nothing was changed."
+    }
+    patch_missing(path: &Path) {
+        fr: "ERREUR : Le fichier patch {path:?} est introuvable dans l'archive extraite. Passage au suivant.",
+        en: "ERROR: Patch file {path:?} was not found in the extracted archive. Skipping."
+    }
+    checking_patch(patch_path: &str, source_path: &str) {
+        fr: "\nVérification du patch : '{patch_path}' pour le fichier source '{source_path}'",
+        en: "\nChecking patch '{patch_path}' for source file '{source_path}'"
+    }
+    source_mismatch(source: &Path, patch: &Path) {
+        fr: "Le fichier source {source:?} ne correspond pas au patch {patch:?}.",
+        en: "Source file {source:?} does not match patch {patch:?}."
+    }
+    source_known_uncovered(source: &Path, label: &str) {
+        fr: "Votre fichier {source:?} est la version « {label} », connue mais non couverte par ce patch. Mettez le jeu à jour ou installez la version du patch correspondante.",
+        en: "Your file {source:?} is version \"{label}\", which is known but not covered by this patch. Update the game or install the matching patch version."
+    }
+    check_failed(source: &Path, error: &dyn std::fmt::Display) {
+        fr: "Erreur lors de la vérification du patch pour {source:?}: {error}. Arrêt du patcher.",
+        en: "Error while checking the patch for {source:?}: {error}. Stopping."
+    }
+    preview_done_assume_yes() {
+        fr: "\nAperçu terminé : rien n'a été modifié. Relancez sans --preview pour installer.",
+        en: "\nPreview finished: nothing was changed. Run again without --preview to install."
+    }
+    dry_run_start() {
+        fr: "Simulation (--dry-run) : rien ne sera écrit dans le répertoire du jeu.",
+        en: "Dry run (--dry-run): nothing will be written to the game directory."
+    }
+    dry_run_done() {
+        fr: "\nSimulation terminée : rien n'a été modifié. Relancez sans --dry-run pour installer.",
+        en: "\nDry run finished: nothing was changed. Run again without --dry-run to install."
+    }
+    preview_proceed_prompt() { fr: "\nProcéder à l'installation ?", en: "\nProceed with the installation?" }
+    preview_done() { fr: "Aperçu terminé : rien n'a été modifié dans le répertoire du jeu.", en: "Preview finished: nothing was changed in the game directory." }
+    backups_size_compressed(size: &str) {
+        fr: "\nLes sauvegardes occuperont au plus {size} (moins une fois compressées).",
+        en: "\nBackups will take at most {size} (less once compressed)."
+    }
+    backups_size(size: &str) {
+        fr: "\nLes sauvegardes occuperont environ {size}. Pour économiser de l'espace, utilisez --compress-backups.",
+        en: "\nBackups will take about {size}. To save space, use --compress-backups."
+    }
+    files_to_patch(count: usize) {
+        fr: "\nFichiers du jeu qui vont être patchés ({count}) :",
+        en: "\nGame files about to be patched ({count}):"
+    }
+    install_cancelled() { fr: "Installation annulée.", en: "Installation cancelled." }
+    game_will_be_suspended(running: &str) {
+        fr: "\nLe jeu est lancé : {running}. Il va être suspendu pendant le patch, puis arrêté (il ne peut pas reprendre sur des fichiers modifiés). Toute progression non sauvegardée sera perdue.",
+        en: "\nThe game is running: {running}. It will be suspended during patching, then stopped (it cannot resume on modified files). Any unsaved progress will be lost."
+    }
+    suspend_prompt() { fr: "Suspendre puis arrêter le jeu ?", en: "Suspend, then stop the game?" }
+    cancelled_game_running() { fr: "Installation annulée : le jeu est toujours lancé.", en: "Installation cancelled: the game is still running." }
+    apply_header() { fr: "\n--- Début de l'application des patchs ---", en: "\n--- Applying patches ---" }
+    processing_patch(patch_path: &str, source_path: &str) {
+        fr: "\nTraitement du patch : '{patch_path}' pour le fichier source '{source_path}'",
+        en: "\nProcessing patch '{patch_path}' for source file '{source_path}'"
+    }
+    restoring_from(target: &Path, backup: &Path) { fr: "Restauration de {target:?} depuis {backup:?}", en: "Restoring {target:?} from {backup:?}" }
+    creating_compressed_backup(source: &Path) { fr: "Création de la sauvegarde compressée de : {source:?}", en: "Creating compressed backup of: {source:?}" }
+    backup_path_created(path: &Path) { fr: "Sauvegarde {path:?} créée.", en: "Backup {path:?} created." }
+    compressed_backup_failed(source: &Path, error: &dyn std::fmt::Display) {
+        fr: "Impossible de créer la sauvegarde compressée de {source:?}: {error}",
+        en: "Could not create the compressed backup of {source:?}: {error}"
+    }
+    creating_backup(path: &Path) { fr: "Création de la sauvegarde : {path:?}", en: "Creating backup: {path:?}" }
+    backup_created() { fr: "Sauvegarde créée.", en: "Backup created." }
+    backup_failed(path: &Path, error: &dyn std::fmt::Display) {
+        fr: "ERREUR lors de la création de la sauvegarde {path:?} : {error}",
+        en: "ERROR while creating backup {path:?}: {error}"
+    }
+    applying_patch(path: &Path) { fr: "Application du patch sur : {path:?}", en: "Applying patch to: {path:?}" }
+    patch_applied(path: &Path) { fr: "Patch appliqué avec succès pour : {path:?}", en: "Patch applied successfully to: {path:?}" }
+    patch_failed(path: &Path, error: &dyn std::fmt::Display) {
+        fr: "ERREUR lors de l'application du patch sur {path:?} : {error}",
+        en: "ERROR while applying the patch to {path:?}: {error}"
+    }
+    source_changed(path: &Path, expected: u32, actual: u32) {
+        fr: "Le fichier {path:?} a été modifié pendant l'opération (CRC32 {actual:#010X} au lieu de {expected:#010X}) : il n'a pas été patché. Réessayez après la mise à jour de Steam.",
+        en: "The file {path:?} was modified during the operation (CRC32 {actual:#010X} instead of {expected:#010X}): it was not patched. Try again once the Steam update is finished."
+    }
+    steam_running(processes: &str) {
+        fr: "ATTENTION : Steam est en cours d'exécution ({processes}) : il est probablement en train de mettre le jeu à jour. Attendez la fin de la mise à jour avant de relancer l'installation.",
+        en: "WARNING: Steam is running ({processes}): it is probably updating the game. Wait for the update to finish before running the installation again."
+    }
+    gamemaker_valid(path: &Path) { fr: "OK : Conteneur GameMaker de {path:?} cohérent.", en: "OK: GameMaker container of {path:?} is consistent." }
+    gamemaker_invalid(path: &Path, error: &dyn std::fmt::Display) {
+        fr: "Le fichier patché {path:?} n'est pas un fichier GameMaker valide ({error}) : le patch est probablement défectueux.",
+        en: "The patched file {path:?} is not a valid GameMaker file ({error}): the patch is probably broken."
+    }
+    rolling_back_install() {
+        fr: "Annulation des fichiers déjà patchés par cette installation...",
+        en: "Reverting the files already patched by this installation..."
+    }
+    trying_restore(backup: &Path) { fr: "Tentative de restauration depuis {backup:?}", en: "Trying to restore from {backup:?}" }
+    restore_succeeded() { fr: "Restauration depuis la sauvegarde réussie.", en: "Restored from backup." }
+    restore_failed(path: &Path, error: &dyn std::fmt::Display) {
+        fr: "ERREUR CRITIQUE : Impossible de restaurer {path:?} depuis la sauvegarde ! Erreur: {error}",
+        en: "CRITICAL ERROR: Could not restore {path:?} from the backup! Error: {error}"
+    }
+    backup_not_found(backup: &Path) {
+        fr: "ERREUR CRITIQUE : Sauvegarde {backup:?} non trouvée, impossible de restaurer.",
+        en: "CRITICAL ERROR: Backup {backup:?} not found, cannot restore."
+    }
+    extras_skipped() { fr: "\nFichiers supplémentaires non copiés (--patches-only).", en: "\nExtra files not copied (--patches-only)." }
+    hooks_ignored(count: usize) {
+        fr: "\nL'index déclare {count} opération(s) post-installation, ignorée(s). Utilisez --allow-hooks pour les exécuter.",
+        en: "\nThe index declares {count} post-install operation(s), skipped. Use --allow-hooks to run them."
+    }
+    apply_done() { fr: "\n--- Application des patchs terminée ---", en: "\n--- Patches applied ---" }
+    manifest_saved(path: &Path) { fr: "Manifeste d'installation enregistré : {path:?}", en: "Installation manifest saved: {path:?}" }
+
+    // --- wizard ---
+    wizard_input_closed() { fr: "Entrée fermée : assistant interrompu.", en: "Input closed: wizard interrupted." }
+    wizard_game_dir_suggested(dir: &Path) { fr: "Répertoire du jeu (Entrée pour {dir:?}) :", en: "Game directory (Enter for {dir:?}):" }
+    wizard_game_dir_question() {
+        fr: "Répertoire du jeu (celui qui contient DELTARUNE.exe ; vous pouvez y glisser le dossier) :",
+        en: "Game directory (the one containing DELTARUNE.exe; you can drag the folder here):"
+    }
+    wizard_not_game(dir: &Path) {
+        fr: "ERREUR : {dir:?} ne contient pas Deltarune (dossier chapter2_windows ou chapter3_windows introuvable).",
+        en: "ERROR: {dir:?} does not contain Deltarune (no chapter2_windows or chapter3_windows folder)."
+    }
+    wizard_title() { fr: "\n=== Patcher Deltarune FR ===", en: "\n=== Deltarune FR patcher ===" }
+    wizard_intro() {
+        fr: "Cet assistant installe ou désinstalle la traduction française de Deltarune.\n",
+        en: "This wizard installs or uninstalls the French translation of Deltarune.\n"
+    }
+    wizard_installed(platform: &str) {
+        fr: "\nLe patch FR est déjà installé dans ce répertoire (plateforme '{platform}').",
+        en: "\nThe FR patch is already installed in this directory (platform '{platform}')."
+    }
+    wizard_not_installed() { fr: "\nLe patch FR n'est pas installé dans ce répertoire.", en: "\nThe FR patch is not installed in this directory." }
+    wizard_menu() { fr: "\nQue voulez-vous faire ?", en: "\nWhat do you want to do?" }
+    wizard_reinstall() { fr: "Réinstaller le patch (dernière version)", en: "Reinstall the patch (latest version)" }
+    wizard_install() { fr: "Installer le patch", en: "Install the patch" }
+    wizard_uninstall() { fr: "Désinstaller le patch (restaurer les fichiers d'origine)", en: "Uninstall the patch (restore the original files)" }
+    wizard_quit() { fr: "Quitter", en: "Quit" }
+    wizard_choice() { fr: "Votre choix [1-3] :", en: "Your choice [1-3]:" }
+    wizard_install_notice() {
+        fr: "\nLe patch va être téléchargé, puis le plan d'installation affiché avant toute modification.",
+        en: "\nThe patch will be downloaded, then the installation plan shown before anything is changed."
+    }
+    wizard_nothing_done() { fr: "Aucune opération effectuée.", en: "Nothing was done." }
+    wizard_invalid_choice() { fr: "Répondez 1, 2 ou 3.", en: "Answer 1, 2 or 3." }
+    wizard_press_enter() { fr: "\nAppuyez sur Entrée pour fermer.", en: "\nPress Enter to close." }
+
+    // --- doctor ---
+    doctor_ok(message: &str) { fr: "OK : {message}", en: "OK: {message}" }
+    doctor_warning(message: &str, advice: &str) { fr: "ATTENTION : {message}\n  -> {advice}", en: "WARNING: {message}\n  -> {advice}" }
+    doctor_error(message: &str, advice: &str) { fr: "ERREUR : {message}\n  -> {advice}", en: "ERROR: {message}\n  -> {advice}" }
+    doctor_header() { fr: "\n--- Diagnostic ---", en: "\n--- Diagnostics ---" }
+    doctor_version(version: &str, os: &str, arch: &str) { fr: "Patcher {version} ({os} {arch})", en: "Patcher {version} ({os} {arch})" }
+    doctor_network_ok(url: &str) { fr: "Serveur des patchs joignable ({url}).", en: "Patch server reachable ({url})." }
+    doctor_network_error(e: &dyn std::fmt::Display) { fr: "Serveur des patchs injoignable : {e}", en: "Patch server unreachable: {e}" }
+    doctor_network_advice() {
+        fr: "Vérifiez la connexion (proxy, pare-feu, antivirus). Hors ligne, une archive déjà téléchargée s'installe avec install --from-file.",
+        en: "Check the connection (proxy, firewall, antivirus). Offline, an already downloaded archive can be installed with install --from-file."
+    }
+    doctor_game_dir_advice() { fr: "Passez avec -d le répertoire qui contient DELTARUNE.exe.", en: "Pass the directory containing DELTARUNE.exe with -d." }
+    doctor_game_files_ok(platform: &str) {
+        fr: "Fichiers de Deltarune présents (plateforme détectée : '{platform}').",
+        en: "Deltarune files found (detected platform: '{platform}')."
+    }
+    doctor_game_files_advice() {
+        fr: "Si le jeu est bien là, vérifiez ses fichiers (Steam : Propriétés > Fichiers installés > Vérifier).",
+        en: "If the game is there, verify its files (Steam: Properties > Installed Files > Verify)."
+    }
+    doctor_label_game_dir() { fr: "Répertoire du jeu", en: "Game directory" }
+    doctor_label_temp_dir() { fr: "Répertoire temporaire", en: "Temporary directory" }
+    doctor_label_state_dir() { fr: "Répertoire d'état", en: "State directory" }
+    doctor_cannot_create(dir: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de créer {dir:?} : {e}", en: "Cannot create {dir:?}: {e}" }
+    doctor_writable(label: &str, dir: &Path) { fr: "{label} accessible en écriture ({dir:?}).", en: "{label} is writable ({dir:?})." }
+    doctor_writable_advice() {
+        fr: "Corrigez les droits du répertoire, ou lancez le patcher avec un compte qui peut y écrire.",
+        en: "Fix the directory permissions, or run the patcher with an account that can write to it."
+    }
+    doctor_space_unknown(label: &str, dir: &Path) { fr: "{label} : espace libre inconnu ({dir:?}).", en: "{label}: free space unknown ({dir:?})." }
+    doctor_space_low(label: &str, available: &str, mount: &Path, needed: &str) {
+        fr: "{label} : {available} libres sur {mount:?}, environ {needed} nécessaires.",
+        en: "{label}: {available} free on {mount:?}, about {needed} needed."
+    }
+    doctor_space_ok(label: &str, available: &str, mount: &Path) { fr: "{label} : {available} libres sur {mount:?}.", en: "{label}: {available} free on {mount:?}." }
+    doctor_temp_space_advice() { fr: "Libérez de l'espace ou téléchargez ailleurs avec --temp-dir.", en: "Free up space or download elsewhere with --temp-dir." }
+    doctor_game_space_advice() {
+        fr: "Libérez de l'espace, ou installez avec --compress-backups pour des sauvegardes plus petites.",
+        en: "Free up space, or install with --compress-backups for smaller backups."
+    }
+    doctor_game_not_running() { fr: "Le jeu n'est pas lancé.", en: "The game is not running." }
+    doctor_game_running(running: &str) { fr: "Le jeu est en cours d'exécution : {running}.", en: "The game is running: {running}." }
+    doctor_game_running_advice() {
+        fr: "Fermez le jeu avant d'installer ou de désinstaller le patch.",
+        en: "Close the game before installing or uninstalling the patch."
+    }
+    doctor_pending_install() { fr: "Une installation précédente a été interrompue.", en: "A previous installation was interrupted." }
+    doctor_pending_install_advice() {
+        fr: "Relancez install --resume pour la terminer, ou uninstall pour revenir à la version d'origine.",
+        en: "Run install --resume to finish it, or uninstall to go back to the original version."
+    }
+    doctor_original_game() { fr: "Aucune sauvegarde ni installation du patch : jeu d'origine.", en: "No backup and no patch installation: original game." }
+    doctor_backups_missing() {
+        fr: "Le patch est enregistré comme installé, mais aucune sauvegarde (.bak) n'est présente.",
+        en: "The patch is recorded as installed, but no backup (.bak) is present."
+    }
+    doctor_backups_missing_advice() {
+        fr: "Lancez verify-backups ; sans sauvegarde, seule une vérification des fichiers par Steam rendra le jeu d'origine.",
+        en: "Run verify-backups; without backups, only a Steam file verification will bring back the original game."
+    }
+    doctor_backups_ok(count: usize, platform: &str) {
+        fr: "{count} sauvegarde(s) présente(s), patch installé (plateforme '{platform}').",
+        en: "{count} backup(s) present, patch installed (platform '{platform}')."
+    }
+    doctor_backups_unknown(count: usize) {
+        fr: "{count} sauvegarde(s) présente(s) sans installation enregistrée :",
+        en: "{count} backup(s) present with no recorded installation:"
+    }
+    doctor_backups_unknown_advice() {
+        fr: "Elles viennent sans doute d'une autre copie du patcher ou d'un autre --state-dir : utilisez le même pour uninstall.",
+        en: "They probably come from another copy of the patcher or another --state-dir: use the same one for uninstall."
+    }
+    doctor_summary(errors: usize, warnings: usize) { fr: "\n{errors} erreur(s), {warnings} avertissement(s).", en: "\n{errors} error(s), {warnings} warning(s)." }
+    doctor_failed() {
+        fr: "Le diagnostic a relevé des problèmes qui empêcheront l'installation.",
+        en: "The diagnostics found problems that will prevent the installation."
+    }
+
+    // --- cleanup ---
+    orphan_age_days(days: u64) { fr: "{days} jours", en: "{days} days" }
+    orphan_age_hours(hours: u64) { fr: "{hours} h", en: "{hours} h" }
+    orphan_temp() { fr: "écriture interrompue", en: "interrupted write" }
+    orphan_partial_resumable() {
+        fr: "téléchargement interrompu, repris par la prochaine installation",
+        en: "interrupted download, resumed by the next installation"
+    }
+    orphan_partial_stale() { fr: "téléchargement interrompu, trop ancien pour être repris", en: "interrupted download, too old to be resumed" }
+    orphan_description(path: &Path, size: &str, age: &str, note: &str) {
+        fr: "{path:?} ({size}, il y a {age}) : {note}",
+        en: "{path:?} ({size}, {age} ago): {note}"
+    }
+    orphan_removed(description: &str) { fr: "Fichier temporaire orphelin supprimé : {description}", en: "Orphaned temporary file removed: {description}" }
+    orphan_remove_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de supprimer le fichier temporaire {path:?} : {e}",
+        en: "WARNING: Cannot remove temporary file {path:?}: {e}"
+    }
+    orphan_resumable_found(description: &str) { fr: "Téléchargement interrompu trouvé : {description}", en: "Interrupted download found: {description}" }
+    no_orphans() { fr: "OK : Aucun fichier temporaire orphelin.", en: "OK: No orphaned temporary files." }
+    orphans_found() { fr: "Fichiers temporaires trouvés :", en: "Temporary files found:" }
+    orphans_remove_prompt(count: usize) { fr: "Supprimer les {count} fichiers temporaires orphelins ?", en: "Remove the {count} orphaned temporary files?" }
+    orphans_remove_resumable_prompt(count: usize) {
+        fr: "{count} téléchargement(s) interrompu(s) peuvent être repris en relançant install. Les supprimer quand même ?",
+        en: "{count} interrupted download(s) can be resumed by running install again. Remove them anyway?"
+    }
+    remove_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "ERREUR : Impossible de supprimer {path:?} : {e}", en: "ERROR: Cannot remove {path:?}: {e}" }
+    orphans_remove_errors(count: usize) {
+        fr: "{count} fichiers temporaires n'ont pas pu être supprimés.",
+        en: "{count} temporary files could not be removed."
+    }
+    orphans_removed(count: usize, freed: &str) { fr: "OK : {count} fichier(s) supprimé(s), {freed} libéré(s).", en: "OK: {count} file(s) removed, {freed} freed." }
+
+    // --- hooks ---
+    hook_create_file(path: &str) { fr: "création du fichier '{path}'", en: "creating file '{path}'" }
+    hook_path_refused(path: &str) { fr: "Chemin refusé pour une opération post-installation : '{path}'.", en: "Path refused for a post-install operation: '{path}'." }
+    hooks_header() { fr: "\n--- Opérations post-installation ---\n", en: "\n--- Post-install operations ---\n" }
+    hook_operation(description: &str) { fr: "Opération : {description}", en: "Operation: {description}" }
+    hook_backup_failed(path: &Path, backup: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible de sauvegarder {path:?} en {backup:?}: {e}",
+        en: "Cannot back up {path:?} to {backup:?}: {e}"
+    }
+    hooks_done() { fr: "\n--- Opérations post-installation terminées ---", en: "\n--- Post-install operations finished ---" }
+
+    // --- net ---
+    net_timeout(url: &str) {
+        fr: "Le serveur {url} n'a pas répondu à temps. Vérifiez votre connexion internet.",
+        en: "The server {url} did not answer in time. Check your internet connection."
+    }
+    net_connect(url: &str) {
+        fr: "Impossible de se connecter à {url}. Vérifiez votre connexion internet.",
+        en: "Cannot connect to {url}. Check your internet connection."
+    }
+    net_redirect(url: &str) {
+        fr: "Redirection refusée pour {url} : le serveur renvoie vers une adresse non autorisée.",
+        en: "Redirect refused for {url}: the server points to an address that is not allowed."
+    }
+    net_status(status: &dyn std::fmt::Display, url: &str) { fr: "Le serveur a répondu {status} pour {url}.", en: "The server answered {status} for {url}." }
+    net_body(url: &str) {
+        fr: "La connexion a été interrompue pendant la réception de {url}.",
+        en: "The connection was interrupted while receiving {url}."
+    }
+    net_json(url: &str) {
+        fr: "Le contenu reçu depuis {url} n'est pas un JSON valide. Le serveur a peut-être un problème.",
+        en: "The content received from {url} is not valid JSON. The server may have a problem."
+    }
+    net_too_many_redirects(max: usize) { fr: "plus de {max} redirections", en: "more than {max} redirects" }
+    net_https_downgrade(url: &dyn std::fmt::Display) { fr: "redirection de HTTPS vers {url} refusée", en: "redirect from HTTPS to {url} refused" }
+    net_redirect_host(url: &dyn std::fmt::Display, allowed: &str) {
+        fr: "redirection vers {url} refusée (hôtes autorisés : {allowed})",
+        en: "redirect to {url} refused (allowed hosts: {allowed})"
+    }
+    net_retry(e: &dyn std::fmt::Display, attempt: u32, max: u32, delay: u64) {
+        fr: "ATTENTION : {e} Nouvelle tentative ({attempt}/{max}) dans {delay} s...",
+        en: "WARNING: {e} Retrying ({attempt}/{max}) in {delay} s..."
+    }
+
+    // --- archive ---
+    archive_format_mismatch(declared: &dyn std::fmt::Display, detected: &dyn std::fmt::Display) {
+        fr: "ATTENTION : L'index annonce une archive {declared} mais le fichier reçu est au format {detected}.",
+        en: "WARNING: The index announces a {declared} archive but the received file is in {detected} format."
+    }
+    archive_format_unknown(path: &Path) {
+        fr: "Format d'archive non reconnu pour {path:?} (formats pris en charge : ZIP, tar.gz, 7z). Le téléchargement est peut-être une page d'erreur.",
+        en: "Unrecognized archive format for {path:?} (supported formats: ZIP, tar.gz, 7z). The download may be an error page."
+    }
+    windows_forbidden_char(name: &str, c: char) {
+        fr: "« {name} » contient le caractère {c:?}, interdit sous Windows",
+        en: "\"{name}\" contains the character {c:?}, forbidden on Windows"
+    }
+    windows_reserved_name(name: &str) {
+        fr: "« {name} » est un nom de périphérique réservé par Windows",
+        en: "\"{name}\" is a device name reserved by Windows"
+    }
+    windows_trailing_dot(name: &str) {
+        fr: "« {name} » se termine par un point ou une espace, retirés par Windows",
+        en: "\"{name}\" ends with a dot or a space, which Windows strips"
+    }
+    windows_name_refused(path: &Path, problem: &str, suggestion: &Path) {
+        fr: "L'archive contient {path:?}, impossible à créer sous Windows : {problem}. Signalez-le aux auteurs du patch (nom possible : {suggestion:?}).",
+        en: "The archive contains {path:?}, which cannot be created on Windows: {problem}. Report it to the patch authors (possible name: {suggestion:?})."
+    }
+    windows_name_warning(path: &Path, problem: &str, suggestion: &Path) {
+        fr: "ATTENTION : {path:?} ne pourrait pas être extrait sous Windows : {problem} (nom portable possible : {suggestion:?}).",
+        en: "WARNING: {path:?} could not be extracted on Windows: {problem} (possible portable name: {suggestion:?})."
+    }
+    archive_case_collisions(collisions: &str) {
+        fr: "L'archive contient des fichiers dont le nom ne diffère que par la casse, ce qui rend l'extraction imprévisible :\n  {collisions}",
+        en: "The archive contains files whose names differ only by case, which makes extraction unpredictable:\n  {collisions}"
+    }
+    archive_entry_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Erreur lors de l'extraction de {path:?} : {e}",
+        en: "Error while extracting {path:?}: {e}"
+    }
+    archive_dangerous_path(name: &str) {
+        fr: "L'archive contient un chemin dangereux : {name:?}. Extraction annulée.",
+        en: "The archive contains a dangerous path: {name:?}. Extraction cancelled."
+    }
+    archive_not_regular_file(name: &str) {
+        fr: "ATTENTION : {name:?} n'est pas un fichier ordinaire (lien ?) : ignoré.",
+        en: "WARNING: {name:?} is not a regular file (link?): skipped."
+    }
+    extracting(path: &Path, format: &dyn std::fmt::Display, target: &Path) {
+        fr: "Décompression de {path:?} ({format}) vers {target:?}...",
+        en: "Extracting {path:?} ({format}) to {target:?}..."
+    }
+    extract_skipped(count: usize) {
+        fr: "{count} fichier(s) de l'archive non extrait(s) (chapitres ou extras exclus).",
+        en: "{count} file(s) of the archive not extracted (excluded chapters or extras)."
+    }
+    extract_done() { fr: "Décompression terminée.", en: "Extraction finished." }
+
+    // --- uninstall ---
+    uninstall_launch(game_dir: &Path) { fr: "Lancement du processus de désinstallation pour : {game_dir:?}", en: "Starting the uninstall process for: {game_dir:?}" }
+    uninstall_header() { fr: "\n--- Début de la désinstallation du patch ---", en: "\n--- Starting patch uninstall ---" }
+    target_game_dir(game_dir: &Path) { fr: "Répertoire du jeu cible : {game_dir:?}", en: "Target game directory: {game_dir:?}" }
+    redundant_backups_for(count: usize, path: &Path, oldest: &Path) {
+        fr: "ATTENTION : {count} sauvegardes pour {path:?} : restauration depuis la plus ancienne, {oldest:?}.",
+        en: "WARNING: {count} backups for {path:?}: restoring from the oldest one, {oldest:?}."
+    }
+    manifest_entry_invalid(manifest: &Path, path: &Path) {
+        fr: "ATTENTION : Entrée invalide ignorée dans le manifeste {manifest:?} : {path:?}.",
+        en: "WARNING: Invalid entry ignored in manifest {manifest:?}: {path:?}."
+    }
+    history_unreadable(e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Historique des installations illisible ({e}) : restauration à partir des seules sauvegardes présentes.",
+        en: "WARNING: Installation history unreadable ({e}): restoring from the existing backups only."
+    }
+    backup_original_unknown(path: &Path) {
+        fr: "ATTENTION : Impossible de déterminer le nom original pour {path:?}. Fichier ignoré.",
+        en: "WARNING: Cannot determine the original name for {path:?}. File skipped."
+    }
+    uninstall_resuming(restored: usize, remaining: usize) {
+        fr: "Reprise d'une désinstallation interrompue : {restored} fichier(s) déjà restauré(s), {remaining} restant(s).",
+        en: "Resuming an interrupted uninstall: {restored} file(s) already restored, {remaining} remaining."
+    }
+    uninstall_dry_run_header() { fr: "\n--- Désinstallation (simulation) : rien n'est modifié ---", en: "\n--- Uninstall (dry run): nothing is modified ---" }
+    files_would_be_restored(count: usize) { fr: "Fichiers qui seraient restaurés ({count}) :", en: "Files that would be restored ({count}):" }
+    redundant_backup_kept(path: &Path) { fr: "Sauvegarde redondante, conservée : {path:?}", en: "Redundant backup, kept: {path:?}" }
+    unrecoverable_file(path: &Path) { fr: "Restauration impossible, sauvegarde introuvable : {path:?}", en: "Cannot restore, backup not found: {path:?}" }
+    backups_not_restorable(count: usize) { fr: "{count} sauvegarde(s) ne pourraient pas être restaurées.", en: "{count} backup(s) could not be restored." }
+    files_to_restore(count: usize) { fr: "\nFichiers qui vont être restaurés ({count}) :", en: "\nFiles that will be restored ({count}):" }
+    restore_prompt() { fr: "Restaurer ces fichiers ?", en: "Restore these files?" }
+    uninstall_cancelled() { fr: "Désinstallation annulée.", en: "Uninstall cancelled." }
+    uninstall_journal_failed(e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible d'enregistrer l'avancement de la désinstallation : {e}",
+        en: "WARNING: Cannot record the uninstall progress: {e}"
+    }
+    backup_found(path: &Path) { fr: "\nSauvegarde trouvée : {path:?}", en: "\nBackup found: {path:?}" }
+    backup_kept_after_error(e: &dyn std::fmt::Display, backup: &Path) { fr: "ERREUR : {e} La sauvegarde {backup:?} est conservée.", en: "ERROR: {e} Backup {backup:?} is kept." }
+    decompressing_backup(backup: &Path, target: &Path) { fr: "Décompression de {backup:?} -> {target:?}", en: "Decompressing {backup:?} -> {target:?}" }
+    backup_remove_failed(backup: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de supprimer la sauvegarde {backup:?}: {e}.",
+        en: "WARNING: Cannot remove backup {backup:?}: {e}."
+    }
+    file_restored(path: &Path) { fr: "Fichier {path:?} restauré avec succès.", en: "File {path:?} restored successfully." }
+    restore_from_failed(path: &Path, backup: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Impossible de restaurer {path:?} depuis {backup:?}: {e}. La sauvegarde est conservée.",
+        en: "ERROR: Cannot restore {path:?} from {backup:?}: {e}. The backup is kept."
+    }
+    original_missing(path: &Path) {
+        fr: "Note : Le fichier {path:?} est absent (supprimé ?) : la sauvegarde est restaurée à sa place.",
+        en: "Note: File {path:?} is missing (deleted?): the backup is restored in its place."
+    }
+    restoring(backup: &Path, target: &Path) { fr: "Restauration de {backup:?} -> {target:?}", en: "Restoring {backup:?} -> {target:?}" }
+    rename_backup_failed(backup: &Path, target: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Impossible de renommer {backup:?} en {target:?}: {e}. Le fichier .bak est conservé.",
+        en: "ERROR: Cannot rename {backup:?} to {target:?}: {e}. The .bak file is kept."
+    }
+    uninstall_done() { fr: "\n--- Désinstallation terminée ---", en: "\n--- Uninstall finished ---" }
+    files_restored(count: usize) { fr: "Fichiers restaurés : {count}", en: "Files restored: {count}" }
+    redundant_backups() {
+        fr: "ATTENTION : Sauvegardes redondantes, non restaurées, à supprimer après vérification :",
+        en: "WARNING: Redundant backups, not restored, to delete after checking:"
+    }
+    unrecoverable_files() {
+        fr: "ATTENTION : Restauration impossible, sauvegarde introuvable (vérifiez l'intégrité des fichiers du jeu sur Steam) :",
+        en: "WARNING: Cannot restore, backup not found (verify the integrity of the game files on Steam):"
+    }
+    errors_encountered(count: usize) { fr: "Erreurs rencontrées : {count}", en: "Errors encountered: {count}" }
+    uninstall_failed(count: usize) { fr: "{count} erreurs se sont produites pendant la désinstallation.", en: "{count} errors occurred during the uninstall." }
+    history_cleared(count: usize) { fr: "Historique des installations effacé ({count} manifeste(s)).", en: "Installation history cleared ({count} manifest(s))." }
+    history_clear_failed(e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible d'effacer l'historique des installations : {e}",
+        en: "WARNING: Cannot clear the installation history: {e}"
+    }
+
+    // --- rollback-last ---
+    rollback_launch(game_dir: &Path) {
+        fr: "Lancement de l'annulation de la dernière installation pour : {game_dir:?}",
+        en: "Starting the rollback of the last installation for: {game_dir:?}"
+    }
+    rollback_header() { fr: "\n--- Annulation de la dernière installation ---", en: "\n--- Rolling back the last installation ---" }
+    no_installation_recorded(game_dir: &Path, state_dir: &Path) {
+        fr: "Aucune installation enregistrée pour {game_dir:?} dans {state_dir:?}.",
+        en: "No installation recorded for {game_dir:?} in {state_dir:?}."
+    }
+    rollback_manifest(path: &Path, platform: &str, count: usize) {
+        fr: "Manifeste utilisé : {path:?} (plateforme '{platform}', {count} fichier(s))",
+        en: "Manifest used: {path:?} (platform '{platform}', {count} file(s))"
+    }
+    rollback_backup_missing(backup: &Path, path: &Path) {
+        fr: "ERREUR : Sauvegarde {backup:?} introuvable, impossible de restaurer {path:?}.",
+        en: "ERROR: Backup {backup:?} not found, cannot restore {path:?}."
+    }
+    rollback_restore_failed(path: &Path, backup: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Impossible de restaurer {path:?} depuis {backup:?}: {e}.",
+        en: "ERROR: Cannot restore {path:?} from {backup:?}: {e}."
+    }
+    removing_created_file(path: &Path) {
+        fr: "Suppression du fichier ajouté par l'installation : {path:?}",
+        en: "Removing the file added by the installation: {path:?}"
+    }
+    file_remove_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "ERREUR : Impossible de supprimer {path:?}: {e}.", en: "ERROR: Cannot remove {path:?}: {e}." }
+    no_backup_recorded(path: &Path) {
+        fr: "ATTENTION : Aucune sauvegarde enregistrée pour {path:?}, fichier laissé en l'état.",
+        en: "WARNING: No backup recorded for {path:?}, file left as is."
+    }
+    rollback_done() { fr: "\n--- Annulation terminée ---", en: "\n--- Rollback finished ---" }
+    rollback_failed(count: usize, manifest: &Path) {
+        fr: "{count} erreurs se sont produites pendant l'annulation. Le manifeste {manifest:?} est conservé.",
+        en: "{count} errors occurred during the rollback. Manifest {manifest:?} is kept."
+    }
+    manifest_removed(path: &Path) { fr: "Manifeste {path:?} retiré de l'historique.", en: "Manifest {path:?} removed from the history." }
+
+    // --- install : téléchargement, extraction, fichiers supplémentaires ---
+    checking_patch_compatibility(patch: &Path, source: &Path) {
+        fr: "Vérification de la compatibilité du patch {patch:?} avec le fichier source {source:?}...",
+        en: "Checking that patch {patch:?} matches source file {source:?}..."
+    }
+    source_read_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Erreur lecture source {path:?}: {e}", en: "Error reading source {path:?}: {e}" }
+    source_crc_matches(crc: u32) {
+        fr: "OK : Le CRC32 du fichier source ({crc:#010X}) correspond au CRC32 attendu par le patch.",
+        en: "OK: The CRC32 of the source file ({crc:#010X}) matches the CRC32 expected by the patch."
+    }
+    source_crc_differs(actual: u32, expected: u32) {
+        fr: "ERREUR : Le CRC32 du fichier source ({actual:#010X}) ne correspond PAS au CRC32 attendu par le patch ({expected:#010X}).",
+        en: "ERROR: The CRC32 of the source file ({actual:#010X}) does NOT match the CRC32 expected by the patch ({expected:#010X})."
+    }
+    extras_header() { fr: "\n--- Copie des fichiers supplémentaires (non-BPS) ---\n", en: "\n--- Copying additional (non-BPS) files ---\n" }
+    extra_relative_path_unknown(path: &Path) {
+        fr: "ATTENTION : Impossible de déterminer le chemin relatif pour {path:?}. Fichier ignoré.",
+        en: "WARNING: Cannot determine the relative path for {path:?}. File skipped."
+    }
+    extra_copying(source: &Path, target: &Path) { fr: "Copie : {source:?} -> {target:?}", en: "Copying: {source:?} -> {target:?}" }
+    creating_parent_dir(path: &Path) { fr: "Création du répertoire parent de destination : {path:?}", en: "Creating destination parent directory: {path:?}" }
+    extra_parent_unknown(path: &Path) {
+        fr: "ATTENTION : Impossible de déterminer le répertoire parent pour {path:?}. Fichier ignoré.",
+        en: "WARNING: Cannot determine the parent directory for {path:?}. File skipped."
+    }
+    backup_already_present(path: &Path) { fr: "Sauvegarde {path:?} déjà présente, conservée.", en: "Backup {path:?} already present, kept." }
+    existing_file_backup(path: &Path, backup: &Path) {
+        fr: "Fichier existant trouvé à {path:?}. Sauvegarde en {backup:?}",
+        en: "Existing file found at {path:?}. Backing up to {backup:?}"
+    }
+    extra_backup_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Impossible de sauvegarder {path:?} : {e}. Copie annulée pour ce fichier.",
+        en: "ERROR: Cannot back up {path:?}: {e}. Copy cancelled for this file."
+    }
+    extra_copied(path: &Path) { fr: "Fichier {path:?} copié avec succès.", en: "File {path:?} copied successfully." }
+    extra_copy_failed(source: &Path, target: &Path, e: &dyn std::fmt::Display) {
+        fr: "ERREUR : Impossible de copier {source:?} vers {target:?}: {e}.",
+        en: "ERROR: Cannot copy {source:?} to {target:?}: {e}."
+    }
+    extras_done() { fr: "\n--- Copie des fichiers supplémentaires terminée ---", en: "\n--- Additional files copied ---" }
+    space_low_shared(mount: &Path, needed: &str, available: &str) {
+        fr: "ATTENTION : Espace insuffisant sur {mount:?} (répertoire temporaire et répertoire du jeu) : {needed} nécessaires, {available} disponibles.",
+        en: "WARNING: Not enough space on {mount:?} (temporary directory and game directory): {needed} needed, {available} available."
+    }
+    space_low_temp(dir: &Path, mount: &Path, needed: &str, available: &str) {
+        fr: "ATTENTION : Espace insuffisant dans le répertoire temporaire {dir:?} (volume {mount:?}) pour le ZIP et son extraction : {needed} nécessaires, {available} disponibles.",
+        en: "WARNING: Not enough space in temporary directory {dir:?} (volume {mount:?}) for the ZIP and its extraction: {needed} needed, {available} available."
+    }
+    temp_dir_hint(game_dir: &Path) {
+        fr: "Utilisez --temp-dir pour télécharger ailleurs, par exemple sur le disque du jeu : --temp-dir {game_dir:?}",
+        en: "Use --temp-dir to download elsewhere, for example on the game's drive: --temp-dir {game_dir:?}"
+    }
+    space_low_game(dir: &Path, mount: &Path, needed: &str, available: &str) {
+        fr: "ATTENTION : Espace insuffisant dans le répertoire du jeu {dir:?} (volume {mount:?}) pour les sauvegardes et les fichiers patchés : {needed} nécessaires, {available} disponibles.",
+        en: "WARNING: Not enough space in game directory {dir:?} (volume {mount:?}) for the backups and patched files: {needed} needed, {available} available."
+    }
+    downloading(url: &str, path: &Path) { fr: "Téléchargement de {url} vers {path:?}...", en: "Downloading {url} to {path:?}..." }
+    download_resuming(size: &str) { fr: "Reprise du téléchargement interrompu après {size}.", en: "Resuming the interrupted download after {size}." }
+    download_restarting() {
+        fr: "Le serveur ne permet pas de reprendre le téléchargement : il recommence depuis le début.",
+        en: "The server does not allow resuming the download: it starts again from the beginning."
+    }
+    sync_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de synchroniser {path:?} sur le disque : {e}", en: "Cannot sync {path:?} to disk: {e}" }
+    download_done(url: &str) { fr: "Téléchargement de {url} terminé.", en: "Download of {url} finished." }
+    cache_missing(dir: &Path) { fr: "aucune archive extraite dans {dir:?}", en: "no extracted archive in {dir:?}" }
+    cache_interrupted(dir: &Path) { fr: "l'extraction dans {dir:?} a été interrompue", en: "the extraction in {dir:?} was interrupted" }
+    cache_patch_missing(patch: &str, dir: &Path) { fr: "le patch '{patch}' manque dans {dir:?}", en: "patch '{patch}' is missing from {dir:?}" }
+    cache_used(dir: &Path) { fr: "Utilisation de l'archive déjà extraite dans {dir:?} (--use-cache).", en: "Using the archive already extracted in {dir:?} (--use-cache)." }
+    cache_unusable_offline(problem: &str) { fr: "Cache inutilisable hors ligne : {problem}.", en: "Cache unusable offline: {problem}." }
+    cache_unusable(problem: &str) {
+        fr: "ATTENTION : Cache inutilisable : {problem}. Téléchargement de l'archive.",
+        en: "WARNING: Cache unusable: {problem}. Downloading the archive."
+    }
+    cache_index_save_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible d'enregistrer l'index dans le cache {path:?} : {e}",
+        en: "WARNING: Cannot save the index in cache {path:?}: {e}"
+    }
+    continue_anyway_prompt() { fr: "Continuer quand même ?", en: "Continue anyway?" }
+    extraction_space_cancelled() {
+        fr: "Installation annulée : espace disque insuffisant pour l'extraction.",
+        en: "Installation cancelled: not enough disk space for the extraction."
+    }
+    uncompressed_size_unknown(format: &dyn std::fmt::Display) {
+        fr: "Taille décompressée inconnue pour une archive {format} : l'estimation faite avant le téléchargement reste valable.",
+        en: "Uncompressed size unknown for a {format} archive: the estimate made before the download still applies."
+    }
+    preparing_extraction(dir: &Path) { fr: "Préparation de l'extraction dans : {dir:?}", en: "Preparing extraction in: {dir:?}" }
+    cleaning_extraction_dir() { fr: "Nettoyage du répertoire d'extraction...", en: "Cleaning the extraction directory..." }
+    incomplete_extraction_remove_failed(dir: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de supprimer l'extraction incomplète {dir:?} : {e}. Elle ne sera pas réutilisée.",
+        en: "WARNING: Cannot remove the incomplete extraction {dir:?}: {e}. It will not be reused."
+    }
+    archive_extracted(dir: &Path) { fr: "Archive décompressée avec succès dans {dir:?}", en: "Archive extracted successfully to {dir:?}" }
+    index_url_invalid(origin: &str, url: &str) {
+        fr: "URL d'index invalide ({origin}) : {url} (http:// ou https:// attendu).",
+        en: "Invalid index URL ({origin}): {url} (http:// or https:// expected)."
+    }
+    index_url_unofficial(origin: &str, url: &str) {
+        fr: "ATTENTION : Index des patchs non officiel ({origin}) : {url}",
+        en: "WARNING: Unofficial patch index ({origin}): {url}"
+    }
+    profile_target(name: &str, game_dir: &Path) { fr: "Profil '{name}' : {game_dir:?}", en: "Profile '{name}': {game_dir:?}" }
+    target_missing() {
+        fr: "Indiquez le répertoire du jeu (--game-dir) ou un profil (--profile).",
+        en: "Specify the game directory (--game-dir) or a profile (--profile)."
+    }
+    extracted_files_check_header() {
+        fr: "\n--- Vérification de l'intégrité des fichiers extraits ---",
+        en: "\n--- Checking the integrity of the extracted files ---"
+    }
+    extracted_crc_mismatch(path: &str, actual: u32, expected: u32) {
+        fr: "ERREUR : {path} : CRC32 {actual:#010X}, attendu {expected:#010X}.",
+        en: "ERROR: {path}: CRC32 {actual:#010X}, expected {expected:#010X}."
+    }
+    extracted_missing(path: &str) { fr: "ERREUR : {path} est absent de l'extraction.", en: "ERROR: {path} is missing from the extraction." }
+    extracted_unexpected(path: &str) { fr: "Note : {path} n'est pas listé dans les CRC attendus.", en: "Note: {path} is not listed in the expected CRCs." }
+    extracted_all_ok(count: usize) {
+        fr: "OK : Les {count} fichiers extraits correspondent aux CRC32 attendus.",
+        en: "OK: The {count} extracted files match the expected CRC32s."
+    }
+    extracted_problems(corrupted: usize, missing: usize) {
+        fr: "{corrupted} fichier(s) corrompu(s) et {missing} fichier(s) manquant(s) après extraction. L'archive est peut-être abîmée ou le disque plein.",
+        en: "{corrupted} corrupted file(s) and {missing} missing file(s) after extraction. The archive may be damaged or the disk full."
+    }
+    source_crc(path: &Path, crc: u32) { fr: "CRC32 du fichier source {path:?} : {crc:#010X}", en: "CRC32 of source file {path:?}: {crc:#010X}" }
+    candidate_missing(path: &Path) {
+        fr: "ATTENTION : Le fichier patch {path:?} est introuvable dans l'archive extraite. Candidat ignoré.",
+        en: "WARNING: Patch file {path:?} was not found in the extracted archive. Candidate skipped."
+    }
+    candidate_selected(patch: &str, source_crc: u32, target_crc: u32) {
+        fr: "OK : Patch sélectionné : '{patch}' (il s'applique au CRC32 source {source_crc:#010X} et produit {target_crc:#010X}).",
+        en: "OK: Patch selected: '{patch}' (it applies to source CRC32 {source_crc:#010X} and produces {target_crc:#010X})."
+    }
+    candidate_rejected(patch: &str, source_crc: u32) {
+        fr: "Patch '{patch}' écarté : il attend le CRC32 source {source_crc:#010X}.",
+        en: "Patch '{patch}' rejected: it expects source CRC32 {source_crc:#010X}."
+    }
+    no_candidate_matches(count: usize, path: &Path, crc: u32) {
+        fr: "Aucun des {count} patchs candidats ne correspond au fichier source {path:?} (CRC32 {crc:#010X}). Votre version du jeu n'est peut-être pas prise en charge.",
+        en: "None of the {count} candidate patches matches source file {path:?} (CRC32 {crc:#010X}). Your version of the game may not be supported."
+    }
+    resume_already_patched(path: &Path, crc: u32) {
+        fr: "Reprise : {path:?} est déjà patché (CRC32 {crc:#010X}).",
+        en: "Resume: {path:?} is already patched (CRC32 {crc:#010X})."
+    }
+    resume_inconsistent(path: &Path, crc: u32, backup: &Path) {
+        fr: "Reprise : {path:?} est dans un état incohérent (CRC32 {crc:#010X}) : il sera restauré depuis {backup:?} puis patché.",
+        en: "Resume: {path:?} is in an inconsistent state (CRC32 {crc:#010X}): it will be restored from {backup:?} and then patched."
+    }
+    source_size_mismatch(path: &Path, size: u64, patch: &Path, expected: u64) {
+        fr: "{path:?} fait {size} octets alors que le patch {patch:?} attend un fichier de {expected} octets.",
+        en: "{path:?} is {size} bytes but patch {patch:?} expects a file of {expected} bytes."
+    }
+    file_too_large(path: &Path, max: usize) {
+        fr: "{path:?} ou le fichier produit dépasse la taille adressable sur cette plateforme ({max} octets) : utilisez une version 64 bits du patcher.",
+        en: "{path:?} or the produced file exceeds the addressable size on this platform ({max} bytes): use a 64-bit version of the patcher."
+    }
+    patch_changes_nothing(patch: &Path, crc: u32) {
+        fr: "ATTENTION : Le patch {patch:?} annonce le même CRC32 source et cible ({crc:#010X}) : il ne modifie pas le fichier.",
+        en: "WARNING: Patch {patch:?} announces the same source and target CRC32 ({crc:#010X}): it does not change the file."
+    }
+    bps_apply_failed(e: &dyn std::fmt::Display) { fr: "Erreur lors de l'application du patch BPS: {e}", en: "Error while applying the BPS patch: {e}" }
+    patch_output_unchanged(patch: &Path, path: &Path, crc: u32) {
+        fr: "Le patch {patch:?} n'a pas modifié {path:?} (CRC32 {crc:#010X} inchangé) alors qu'il annonce un changement. Le patch est peut-être corrompu.",
+        en: "Patch {patch:?} did not change {path:?} (CRC32 {crc:#010X} unchanged) although it announces a change. The patch may be corrupted."
+    }
+    patch_output_crc_mismatch(patch: &Path, actual: u32, expected: u32) {
+        fr: "Le fichier produit par {patch:?} a le CRC32 {actual:#010X} au lieu de {expected:#010X} annoncé par le patch.",
+        en: "The file produced by {patch:?} has CRC32 {actual:#010X} instead of {expected:#010X} announced by the patch."
+    }
+    platform_full_detected() { fr: "Jeu complet détecté. Téléchargement du patch.", en: "Full game detected. Downloading the patch." }
+    platform_demo_detected() { fr: "Demo détectée ! Téléchargement du patch demo.", en: "Demo detected! Downloading the demo patch." }
+    platform_none_detected() { fr: "Le dossier du chapitre 2 n'a pas été détecté !", en: "The chapter 2 folder was not detected!" }
+    too_many_extras(count: usize, max: usize) {
+        fr: "Nombre anormal de fichiers supplémentaires ({count}, limite {max}) : archive suspecte, installation annulée. Utilisez --max-extras pour relever la limite si l'archive est fiable.",
+        en: "Abnormal number of additional files ({count}, limit {max}): suspicious archive, installation cancelled. Use --max-extras to raise the limit if the archive is trustworthy."
+    }
+    executable_known(path: &str, crc: u32) {
+        fr: "OK : {path} correspond à une version officielle connue ({crc:#010X}).",
+        en: "OK: {path} matches a known official version ({crc:#010X})."
+    }
+    executable_unknown(path: &str, crc: u32) {
+        fr: "ATTENTION : Version du jeu non reconnue ({path} : CRC32 {crc:#010X}), le patch pourrait ne pas fonctionner. \
+        Si votre jeu vient d'être mis à jour, signalez-le ; sinon réinstallez une version officielle avant de rapporter un bug.",
+        en: "WARNING: Unrecognized game version ({path}: CRC32 {crc:#010X}), the patch might not work. \
+        If your game has just been updated, report it; otherwise reinstall an official version before reporting a bug."
+    }
+    game_dir_unreadable(path: &Path, e: &dyn std::fmt::Display, hint: &str) {
+        fr: "Le répertoire {path:?} existe mais son contenu est illisible : {e}.{hint}",
+        en: "Directory {path:?} exists but its contents cannot be read: {e}.{hint}"
+    }
+    game_dir_is_file(path: &Path) {
+        fr: "Le chemin fourni {path:?} n'est pas un répertoire valide (c'est un fichier).",
+        en: "The given path {path:?} is not a valid directory (it is a file)."
+    }
+    game_dir_permission_denied(path: &Path, hint: &str) {
+        fr: "Le répertoire {path:?} existe peut-être mais est inaccessible (permission refusée).{hint}",
+        en: "Directory {path:?} may exist but is inaccessible (permission denied).{hint}"
+    }
+    game_dir_not_found_sandboxed(path: &Path, hint: &str) {
+        fr: "Le répertoire {path:?} est introuvable. S'il existe bien, le bac à sable l'empêche sans doute d'y accéder.\n{hint}",
+        en: "Directory {path:?} cannot be found. If it does exist, the sandbox probably prevents access to it.\n{hint}"
+    }
+    game_dir_not_found(path: &Path) {
+        fr: "Le répertoire {path:?} n'existe pas. Vérifiez le chemin du jeu.",
+        en: "Directory {path:?} does not exist. Check the game path."
+    }
+    game_dir_inaccessible(path: &Path, e: &dyn std::fmt::Display, hint: &str) {
+        fr: "Impossible d'accéder à {path:?} : {e}.{hint}",
+        en: "Cannot access {path:?}: {e}.{hint}"
+    }
+    sandbox_flatpak_hint(path: &dyn std::fmt::Display, app_id: &str) {
+        fr: "Le patcher tourne dans un Flatpak, qui ne voit que les dossiers autorisés. \
+        Donnez-lui accès au jeu avec :\n  flatpak override --user --filesystem={path} {app_id}\npuis relancez-le.",
+        en: "The patcher runs in a Flatpak, which only sees allowed folders. \
+        Give it access to the game with:\n  flatpak override --user --filesystem={path} {app_id}\nthen run it again."
+    }
+    sandbox_flatpak_app_id() { fr: "<id de l'application>", en: "<application id>" }
+    sandbox_snap_hint() {
+        fr: "Le patcher tourne dans un Snap, qui n'a accès qu'à votre dossier personnel (hors dossiers cachés) \
+        et aux supports amovibles connectés. Si le jeu est ailleurs (ex. ~/.steam ou un autre disque), \
+        utilisez une version hors Snap du patcher, ou connectez l'interface adaptée avec `snap connect`.",
+        en: "The patcher runs in a Snap, which only has access to your home folder (except hidden folders) \
+        and to connected removable media. If the game is elsewhere (e.g. ~/.steam or another disk), \
+        use a non-Snap version of the patcher, or connect the appropriate interface with `snap connect`."
+    }
+    output_dir_not_empty(path: &Path) {
+        fr: "Le répertoire de sortie {path:?} n'est pas vide : choisissez un répertoire absent ou vide.",
+        en: "Output directory {path:?} is not empty: choose a missing or empty directory."
+    }
+    output_dir_create_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible de créer le répertoire de sortie {path:?} : {e}",
+        en: "Cannot create output directory {path:?}: {e}"
+    }
+    output_dir_inside_game(path: &Path) {
+        fr: "Le répertoire de sortie {path:?} ne peut pas se trouver dans le répertoire du jeu.",
+        en: "Output directory {path:?} cannot be inside the game directory."
+    }
+    copying_game(game_dir: &Path, output_dir: &Path) { fr: "Copie du jeu {game_dir:?} vers {output_dir:?}...", en: "Copying the game {game_dir:?} to {output_dir:?}..." }
+    game_copied(count: u64) {
+        fr: "{count} fichier(s) copié(s). Le patch sera appliqué à la copie.",
+        en: "{count} file(s) copied. The patch will be applied to the copy."
+    }
+    game_dir_no_parent(path: &Path) { fr: "Le répertoire du jeu {path:?} n'a pas de répertoire parent.", en: "Game directory {path:?} has no parent directory." }
+    swap_leftover(previous: &Path, game_dir: &Path) {
+        fr: "{previous:?} existe déjà : il reste d'un échange interrompu. Vérifiez que {game_dir:?} est complet, puis supprimez {previous:?}.",
+        en: "{previous:?} already exists: it is left over from an interrupted swap. Check that {game_dir:?} is complete, then delete {previous:?}."
+    }
+    swap_removing_staging(path: &Path) {
+        fr: "Suppression de la copie de travail d'une tentative précédente : {path:?}",
+        en: "Removing the working copy of a previous attempt: {path:?}"
+    }
+    swap_staging_remove_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de supprimer la copie de travail {path:?} : {e}",
+        en: "WARNING: Cannot remove the working copy {path:?}: {e}"
+    }
+    swap_install_failed(path: &Path) {
+        fr: "ERREUR : Installation de la copie échouée, le jeu n'a pas été modifié. Suppression de {path:?}.",
+        en: "ERROR: Installation of the copy failed, the game was not modified. Removing {path:?}."
+    }
+    swap_game_running(running: &str) {
+        fr: "ERREUR : Le jeu est lancé ({running}), échange impossible. Suppression de la copie patchée.",
+        en: "ERROR: The game is running ({running}), swap impossible. Removing the patched copy."
+    }
+    swapping(game_dir: &Path, staging: &Path) {
+        fr: "\nÉchange de {game_dir:?} et de la copie patchée {staging:?}...",
+        en: "\nSwapping {game_dir:?} and the patched copy {staging:?}..."
+    }
+    swap_mount_point(path: &Path) {
+        fr: "{path:?} est un point de montage : il ne peut pas être renommé, --atomic-swap est impossible ici. Le jeu n'a pas été modifié.",
+        en: "{path:?} is a mount point: it cannot be renamed, --atomic-swap is not possible here. The game was not modified."
+    }
+    swap_rename_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible de renommer {path:?} : {e}. Le jeu n'a pas été modifié.",
+        en: "Cannot rename {path:?}: {e}. The game was not modified."
+    }
+    swap_restore_failed(e: &dyn std::fmt::Display, restore_error: &dyn std::fmt::Display, previous: &Path, game_dir: &Path) {
+        fr: "ERREUR CRITIQUE : Échange interrompu ({e}), et le jeu d'origine n'a pas pu être remis en place ({restore_error}). Renommez vous-même {previous:?} en {game_dir:?}.",
+        en: "CRITICAL ERROR: Swap interrupted ({e}), and the original game could not be put back ({restore_error}). Rename {previous:?} to {game_dir:?} yourself."
+    }
+    swap_move_failed(e: &dyn std::fmt::Display) {
+        fr: "Impossible de mettre la copie patchée à la place du jeu : {e}. Le jeu n'a pas été modifié.",
+        en: "Cannot put the patched copy in place of the game: {e}. The game was not modified."
+    }
+    swap_done(previous: &Path) {
+        fr: "OK : Copie patchée en place. Suppression de l'ancienne version {previous:?}...",
+        en: "OK: Patched copy in place. Removing the old version {previous:?}..."
+    }
+    swap_old_remove_failed(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de supprimer {path:?} : {e}. Vous pouvez le supprimer vous-même.",
+        en: "WARNING: Cannot remove {path:?}: {e}. You can delete it yourself."
+    }
+    not_writable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible d'écrire dans {path:?} : {e}. Vérifiez les droits du répertoire (un répertoire en lecture seule ne peut pas être patché).",
+        en: "Cannot write to {path:?}: {e}. Check the directory permissions (a read-only directory cannot be patched)."
+    }
+    index_empty() {
+        fr: "L'index téléchargé ne contient aucune plateforme, le serveur a peut-être un problème. Réessayez plus tard.",
+        en: "The downloaded index contains no platform, the server may have a problem. Try again later."
+    }
+    platform_not_in_index(platform: &str, available: &str) {
+        fr: "Plateforme '{platform}' non trouvée dans l'index JSON. Plateformes disponibles : {available}.",
+        en: "Platform '{platform}' not found in the JSON index. Available platforms: {available}."
+    }
+    patches_dir_invalid(path: &Path) { fr: "Le répertoire de patchs {path:?} n'est pas un répertoire valide.", en: "Patch directory {path:?} is not a valid directory." }
+    installing_from_dir(path: &Path) { fr: "Installation depuis le répertoire de patchs local {path:?}.", en: "Installing from the local patch directory {path:?}." }
+    patches_git_revision(revision: &str) { fr: "Révision git des patchs : {revision}", en: "Git revision of the patches: {revision}" }
+    index_read_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de lire l'index {path:?} : {e}", en: "Cannot read index {path:?}: {e}" }
+    index_invalid(path: &Path, e: &dyn std::fmt::Display) { fr: "Index {path:?} invalide : {e}", en: "Invalid index {path:?}: {e}" }
+    index_from_archive() { fr: "Index lu dans l'archive.", en: "Index read from the archive." }
+    archive_without_index(name: &str) {
+        fr: "ATTENTION : L'archive ne contient pas de {name} : utilisation de l'index de la dernière installation en ligne. Indiquez l'index avec --local-index s'il ne correspond pas à l'archive.",
+        en: "WARNING: The archive does not contain {name}: using the index of the last online installation. Specify the index with --local-index if it does not match the archive."
+    }
+    fetching_index(url: &str) { fr: "Téléchargement de l'index des patchs depuis {url}...", en: "Downloading the patch index from {url}..." }
+    index_fetched() { fr: "Index téléchargé et analysé avec succès.", en: "Index downloaded and parsed successfully." }
+    volume_header(number: usize, count: usize) { fr: "\nVolume {number}/{count} de l'archive.", en: "\nArchive volume {number}/{count}." }
+    volume_corrupted(actual: u32, expected: u32) {
+        fr: "volume corrompu : CRC32 {actual:#010X}, attendu {expected:#010X}",
+        en: "corrupted volume: CRC32 {actual:#010X}, expected {expected:#010X}"
+    }
+    volume_failed(number: usize, url: &str, e: &dyn std::fmt::Display) { fr: "ERREUR : Volume {number} ({url}) : {e}", en: "ERROR: Volume {number} ({url}): {e}" }
+    volume_missing(number: usize, url: &str) { fr: "  volume {number} : {url}", en: "  volume {number}: {url}" }
+    volumes_incomplete(missing: usize, count: usize, list: &str) {
+        fr: "Téléchargement incomplet : {missing} volume(s) sur {count} manquant(s) ou corrompu(s) :\n{list}",
+        en: "Incomplete download: {missing} volume(s) out of {count} missing or corrupted:\n{list}"
+    }
+    volumes_joined(count: usize, path: &Path) { fr: "{count} volumes réunis dans {path:?}.", en: "{count} volumes joined into {path:?}." }
+    deadline_rolling_back(e: &dyn std::fmt::Display) {
+        fr: "\nERREUR : {e} Annulation des modifications déjà faites...",
+        en: "\nERROR: {e} Undoing the changes already made..."
+    }
+    offline_cached_index(path: &Path) { fr: "Hors ligne : lecture de l'index en cache {path:?}.", en: "Offline: reading the cached index {path:?}." }
+    offline_index_unreadable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Cache inutilisable hors ligne : impossible de lire l'index {path:?} : {e}",
+        en: "Cache unusable offline: cannot read index {path:?}: {e}"
+    }
+    cached_index_invalid(path: &Path, e: &dyn std::fmt::Display) { fr: "Index en cache {path:?} invalide : {e}", en: "Invalid cached index {path:?}: {e}" }
+    downloading_from_mirror(url: &str) { fr: "Téléchargement depuis le miroir fourni : {url}", en: "Downloading from the given mirror: {url}" }
+    patch_url_found(platform: &str, url: &str) { fr: "URL du patch trouvée pour la plateforme '{platform}': {url}", en: "Patch URL found for platform '{platform}': {url}" }
+    patch_size(size: &str) { fr: "Le patch fait environ {size}.", en: "The patch is about {size}." }
+    download_cancelled() { fr: "Téléchargement annulé.", en: "Download cancelled." }
+    patch_size_unknown() { fr: "Taille du patch inconnue (le serveur ne l'indique pas).", en: "Patch size unknown (the server does not provide it)." }
+    space_cancelled() { fr: "Installation annulée : espace disque insuffisant.", en: "Installation cancelled: not enough disk space." }
+    torrent_failed(e: &dyn std::fmt::Display) { fr: "ATTENTION : {e} Téléchargement direct en HTTP.", en: "WARNING: {e} Downloading directly over HTTP." }
+    torrent_unavailable() {
+        fr: "L'index ne fournit pas de torrent pour cette plateforme : téléchargement direct en HTTP.",
+        en: "The index provides no torrent for this platform: downloading directly over HTTP."
+    }
+    archive_downloaded(path: &Path) { fr: "L'archive a été téléchargée ici : {path:?}", en: "The archive was downloaded here: {path:?}" }
+    archive_corrupted(actual: u32, expected: u32) {
+        fr: "L'archive téléchargée est corrompue : CRC32 {actual:#010X}, attendu {expected:#010X}. Relancez l'installation.",
+        en: "The downloaded archive is corrupted: CRC32 {actual:#010X}, expected {expected:#010X}. Run the installation again."
+    }
+    archive_crc_ok(crc: u32) { fr: "OK : CRC32 de l'archive conforme à l'index ({crc:#010X}).", en: "OK: Archive CRC32 matches the index ({crc:#010X})." }
+    archive_crc_unchecked(crc: u32) {
+        fr: "CRC32 de l'archive : {crc:#010X} (l'index n'en fournit pas pour comparaison).",
+        en: "Archive CRC32: {crc:#010X} (the index provides none to compare with)."
+    }
+    local_archive_crc_mismatch(path: &Path, actual: u32, expected: u32) {
+        fr: "L'archive {path:?} a le CRC32 {actual:#010X} alors que l'index annonce {expected:#010X} : elle ne correspond peut-être pas à cet index.",
+        en: "Archive {path:?} has CRC32 {actual:#010X} while the index announces {expected:#010X}: it may not match this index."
+    }
+    archive_not_found(path: &Path) { fr: "L'archive {path:?} n'existe pas.", en: "Archive {path:?} does not exist." }
+    installing_from_archive(path: &Path) {
+        fr: "Installation depuis l'archive locale {path:?}, sans accès réseau.",
+        en: "Installing from the local archive {path:?}, without network access."
+    }
+    local_index(path: &Path) { fr: "Index local : {path:?}", en: "Local index: {path:?}" }
+    compatibility_header() { fr: "\n--- Compatibilité des fichiers du jeu ---", en: "\n--- Game file compatibility ---" }
+    compat_missing(path: &str) { fr: "{path} : introuvable.", en: "{path}: not found." }
+    compat_ok(path: &str, crc: u32) { fr: "OK : {path} : compatible ({crc:#010X}).", en: "OK: {path}: compatible ({crc:#010X})." }
+    compat_patched(path: &str, crc: u32) { fr: "{path} : déjà patché ({crc:#010X}).", en: "{path}: already patched ({crc:#010X})." }
+    compat_version_mismatch(path: &str, mismatch: &str) { fr: "{path} : {mismatch}", en: "{path}: {mismatch}" }
+    compat_no_crc(path: &str) {
+        fr: "{path} : CRC32 attendu non fourni par l'index, vérifié après le téléchargement.",
+        en: "{path}: expected CRC32 not provided by the index, checked after the download."
+    }
+    compat_known_uncovered(path: &str, label: &str, crc: u32) {
+        fr: "{path} : version « {label} » ({crc:#010X}), connue mais non couverte par ce patch.",
+        en: "{path}: version \"{label}\" ({crc:#010X}), known but not covered by this patch."
+    }
+    compat_unknown(path: &str, crc: u32) { fr: "{path} : version inconnue ({crc:#010X}).", en: "{path}: unknown version ({crc:#010X})." }
+    already_installed() {
+        fr: "Le patch semble déjà installé : tous les fichiers sont déjà dans leur version patchée. Rien n'a été téléchargé.",
+        en: "The patch seems to be installed already: all files are already in their patched version. Nothing was downloaded."
+    }
+    game_version_incompatible(mismatches: &str) {
+        fr: "Version du jeu incompatible avec ce patch :\n{mismatches}\nMettez le jeu à jour, ou installez la version du patch qui lui correspond (voir la commande versions). Rien n'a été téléchargé.",
+        en: "Game version incompatible with this patch:\n{mismatches}\nUpdate the game, or install the patch version that matches it (see the versions command). Nothing was downloaded."
+    }
+    no_compatible_file(incompatible: usize) {
+        fr: "Aucun fichier du jeu n'est compatible avec ce patch ({incompatible} dans une version inconnue ou introuvable). Vérifiez la version du jeu ; rien n'a été téléchargé.",
+        en: "No game file is compatible with this patch ({incompatible} in an unknown version or missing). Check the game version; nothing was downloaded."
+    }
+    no_compatible_file_some_patched(incompatible: usize, patched: usize) {
+        fr: "Aucun fichier du jeu n'est compatible avec ce patch ({incompatible} dans une version inconnue ou introuvable, {patched} déjà patché(s)). Vérifiez la version du jeu ; rien n'a été téléchargé.",
+        en: "No game file is compatible with this patch ({incompatible} in an unknown version or missing, {patched} already patched). Check the game version; nothing was downloaded."
+    }
+    gen8_version_mismatch(actual: &str, expected: &str) {
+        fr: "le jeu est en version {actual}, le patch cible la version {expected}",
+        en: "the game is version {actual}, the patch targets version {expected}"
+    }
+    gen8_or() { fr: " ou ", en: " or " }
+    plan_header() { fr: "\n--- Plan d'installation (aperçu) ---", en: "\n--- Installation plan (preview) ---" }
+    plan_patched_files(count: usize) { fr: "\nFichiers patchés ({count}) :", en: "\nPatched files ({count}):" }
+    plan_restored_from(backup: &Path) { fr: "restauré depuis la sauvegarde existante {backup:?}", en: "restored from the existing backup {backup:?}" }
+    plan_compressed_backup(backup: &Path) { fr: "sauvegarde compressée {backup:?}", en: "compressed backup {backup:?}" }
+    plan_backup(backup: &Path) { fr: "sauvegarde {backup:?}", en: "backup {backup:?}" }
+    plan_patched_file(path: &str, source_size: &str, target_size: &str, backup: &str) {
+        fr: "  {path} ({source_size} -> {target_size}) ; {backup}",
+        en: "  {path} ({source_size} -> {target_size}); {backup}"
+    }
+    plan_resumed(count: usize) {
+        fr: "  + {count} fichier(s) déjà patché(s) lors de la tentative interrompue.",
+        en: "  + {count} file(s) already patched during the interrupted attempt."
+    }
+    plan_no_extras() { fr: "\nFichiers supplémentaires : aucun (--patches-only).", en: "\nAdditional files: none (--patches-only)." }
+    plan_extras(count: usize) { fr: "\nFichiers supplémentaires ({count}) :", en: "\nAdditional files ({count}):" }
+    plan_extra_replaced(path: &str, size: &str, backup: &Path) {
+        fr: "  {path} ({size}) : remplacé, l'ancien est conservé en {backup:?}",
+        en: "  {path} ({size}): replaced, the old one is kept as {backup:?}"
+    }
+    plan_extra_created(path: &str, size: &str) { fr: "  {path} ({size}) : créé", en: "  {path} ({size}): created" }
+    plan_hooks_run(count: usize) { fr: "\nOpérations post-installation : {count} exécutée(s).", en: "\nPost-install operations: {count} run." }
+    plan_hooks_ignored(count: usize) {
+        fr: "\nOpérations post-installation : {count} ignorée(s) sans --allow-hooks.",
+        en: "\nPost-install operations: {count} ignored without --allow-hooks."
+    }
+    plan_space(size: &str) { fr: "\nEspace nécessaire dans le répertoire du jeu : environ {size}.", en: "\nSpace needed in the game directory: about {size}." }
+    plan_space_compressed(size: &str) {
+        fr: "\nEspace nécessaire dans le répertoire du jeu : environ {size} (moins, les sauvegardes étant compressées).",
+        en: "\nSpace needed in the game directory: about {size} (less, as the backups are compressed)."
+    }
+    game_dirs_without_platforms() {
+        fr: "Plusieurs --game-dir : indiquez la plateforme de chacun avec --platform, dans le même ordre.",
+        en: "Several --game-dir: specify the platform of each one with --platform, in the same order."
+    }
+    profile_with_platforms() {
+        fr: "--profile désigne une seule installation : utilisez --game-dir pour chaque plateforme.",
+        en: "--profile designates a single installation: use --game-dir for each platform."
+    }
+    index_without_platforms() { fr: "L'index ne déclare aucune plateforme.", en: "The index declares no platform." }
+    platforms_game_dirs_mismatch(count: usize, platforms: &str, game_dirs: usize) {
+        fr: "{count} plateforme(s) ({platforms}) mais {game_dirs} --game-dir : indiquez un répertoire par plateforme, dans le même ordre, ou aucun pour utiliser les profils.",
+        en: "{count} platform(s) ({platforms}) but {game_dirs} --game-dir: specify one directory per platform, in the same order, or none to use the profiles."
+    }
+    platform_profiles_ambiguous(count: usize, platform: &str) {
+        fr: "{count} profil(s) pour la plateforme '{platform}' : indiquez son répertoire avec --game-dir (un par plateforme, dans le même ordre).",
+        en: "{count} profile(s) for platform '{platform}': specify its directory with --game-dir (one per platform, in the same order)."
+    }
+    game_dir_for_several_platforms(path: &Path) {
+        fr: "Le répertoire {path:?} est associé à plusieurs plateformes.",
+        en: "Directory {path:?} is associated with several platforms."
+    }
+    incremental_without_previous() {
+        fr: "Aucune installation précédente enregistrée pour ce répertoire : --incremental est impossible, lancez une installation complète.",
+        en: "No previous installation recorded for this directory: --incremental is not possible, run a full installation."
+    }
+    incremental_update(changed: usize, unchanged: usize) {
+        fr: "Mise à jour incrémentale : {changed} fichier(s) à mettre à jour, {unchanged} déjà dans la version de l'index.",
+        en: "Incremental update: {changed} file(s) to update, {unchanged} already at the index version."
+    }
+    incremental_up_to_date() { fr: "OK : Tous les fichiers patchés sont déjà à jour, rien à faire.", en: "OK: All patched files are already up to date, nothing to do." }
+    archive_manifest_invalid(name: &str, e: &dyn std::fmt::Display) { fr: "{name} de l'archive invalide : {e}", en: "Invalid {name} in the archive: {e}" }
+    output_dir_single_platform() { fr: "--output-dir ne s'utilise qu'avec une seule plateforme.", en: "--output-dir can only be used with a single platform." }
+    backup_compressed_size(size: usize, compressed: u64) {
+        fr: "Sauvegarde compressée : {size} -> {compressed} octets.",
+        en: "Compressed backup: {size} -> {compressed} bytes."
+    }
+    backup_unreadable(path: &Path, e: &dyn std::fmt::Display) { fr: "Sauvegarde {path:?} illisible ou corrompue : {e}", en: "Backup {path:?} unreadable or corrupted: {e}" }
+    backup_crc_mismatch(path: &Path, actual: u32, expected: u32) {
+        fr: "Le CRC32 de la sauvegarde décompressée {path:?} ({actual:#010X}) ne correspond pas au CRC32 d'origine ({expected:#010X}).",
+        en: "The CRC32 of the decompressed backup {path:?} ({actual:#010X}) does not match the original CRC32 ({expected:#010X})."
+    }
+    backup_crc_ok(crc: u32) { fr: "OK : CRC32 de la sauvegarde décompressée vérifié ({crc:#010X}).", en: "OK: CRC32 of the decompressed backup checked ({crc:#010X})." }
+    backup_crc_unknown(path: &Path) {
+        fr: "CRC32 d'origine inconnu pour {path:?}, seule la somme de contrôle zstd a été vérifiée.",
+        en: "Original CRC32 unknown for {path:?}, only the zstd checksum was checked."
+    }
+    config_unreadable(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de lire la configuration {path:?} : {e}", en: "Cannot read configuration {path:?}: {e}" }
+    config_invalid(path: &Path, e: &dyn std::fmt::Display) { fr: "Configuration {path:?} invalide : {e}", en: "Invalid configuration {path:?}: {e}" }
+    alias_target(name: &str, path: &Path) { fr: "Alias '@{name}' : {path:?}", en: "Alias '@{name}': {path:?}" }
+    alias_unknown_none_defined(name: &str, path: &Path) {
+        fr: "Alias '@{name}' inconnu : aucun alias n'est défini dans {path:?} (table [aliases]).",
+        en: "Unknown alias '@{name}': no alias is defined in {path:?} ([aliases] table)."
+    }
+    alias_unknown(name: &str, path: &Path, aliases: &str) {
+        fr: "Alias '@{name}' inconnu. Alias définis dans {path:?} : {aliases}",
+        en: "Unknown alias '@{name}'. Aliases defined in {path:?}: {aliases}"
+    }
+    cross_device_move(from: &Path, to: &Path) {
+        fr: "ATTENTION : {from:?} et {to:?} sont sur des systèmes de fichiers différents, copie puis suppression.",
+        en: "WARNING: {from:?} and {to:?} are on different file systems, copying then deleting."
+    }
+    link_unresolvable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "Impossible de résoudre le chemin réel de {path:?} : {e} (lien symbolique cassé ?)",
+        en: "Cannot resolve the real path of {path:?}: {e} (broken symbolic link?)"
+    }
+    link_outside(path: &Path, real: &Path, root: &Path) {
+        fr: "{path:?} mène à {real:?}, hors de {root:?} (lien symbolique) : refusé par sécurité.",
+        en: "{path:?} leads to {real:?}, outside of {root:?} (symbolic link): refused for safety."
+    }
+    link_recreate_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de recréer le lien {path:?} : {e}", en: "Cannot recreate link {path:?}: {e}" }
+    copy_failed(from: &Path, to: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de copier {from:?} vers {to:?} : {e}", en: "Cannot copy {from:?} to {to:?}: {e}" }
+    write_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible d'écrire {path:?} : {e}", en: "Cannot write {path:?}: {e}" }
+    lock_held(owner: &str) {
+        fr: "Une autre instance du patcher opère déjà sur ce répertoire{owner}. Attendez qu'elle se termine avant de recommencer.",
+        en: "Another instance of the patcher is already working on this directory{owner}. Wait for it to finish before trying again."
+    }
+    lock_unavailable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible de verrouiller {path:?} ({e}). Ne lancez pas deux instances du patcher en même temps.",
+        en: "WARNING: Cannot lock {path:?} ({e}). Do not run two instances of the patcher at the same time."
+    }
+    manifest_unreadable(path: &Path, e: &dyn std::fmt::Display) { fr: "ATTENTION : Manifeste illisible {path:?} ignoré : {e}", en: "WARNING: Unreadable manifest {path:?} ignored: {e}" }
+    pending_manifest_unreadable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Manifeste d'installation interrompue illisible {path:?} ignoré : {e}",
+        en: "WARNING: Unreadable manifest of an interrupted installation {path:?} ignored: {e}"
+    }
+    uninstall_journal_unreadable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Journal de désinstallation illisible {path:?} ignoré : {e}",
+        en: "WARNING: Unreadable uninstall journal {path:?} ignored: {e}"
+    }
+    process_suspended(name: &str, pid: &dyn std::fmt::Display) { fr: "Processus {name} (PID {pid}) suspendu.", en: "Process {name} (PID {pid}) suspended." }
+    process_suspend_failed(name: &str, pid: &dyn std::fmt::Display) {
+        fr: "Impossible de suspendre {name} (PID {pid}). Fermez le jeu manuellement.",
+        en: "Cannot suspend {name} (PID {pid}). Close the game manually."
+    }
+    process_suspend_unsupported() {
+        fr: "La suspension de processus n'est pas prise en charge sur ce système. Fermez le jeu manuellement.",
+        en: "Suspending processes is not supported on this system. Close the game manually."
+    }
+    process_killed(pid: &dyn std::fmt::Display) {
+        fr: "Processus du jeu (PID {pid}) arrêté. Relancez le jeu pour profiter du patch.",
+        en: "Game process (PID {pid}) stopped. Start the game again to enjoy the patch."
+    }
+    process_kill_failed(pid: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Impossible d'arrêter le processus suspendu (PID {pid}). Tuez-le manuellement (kill -9 {pid}).",
+        en: "WARNING: Cannot stop the suspended process (PID {pid}). Kill it manually (kill -9 {pid})."
+    }
+    torrent_start(client: &str, torrent: &str) { fr: "Téléchargement en pair-à-pair via {client} : {torrent}", en: "Peer-to-peer download via {client}: {torrent}" }
+    torrent_client_missing(client: &str) {
+        fr: "{client} est introuvable : installez-le (paquet aria2) pour télécharger en pair-à-pair.",
+        en: "{client} was not found: install it (aria2 package) to download peer-to-peer."
+    }
+    torrent_client_failed_to_start(client: &str, e: &dyn std::fmt::Display) { fr: "Impossible de lancer {client} : {e}", en: "Cannot start {client}: {e}" }
+    torrent_client_failed(client: &str, status: &dyn std::fmt::Display) { fr: "{client} a échoué ({status}).", en: "{client} failed ({status})." }
+    torrent_no_output(client: &str, path: &Path) { fr: "{client} s'est terminé sans produire {path:?}.", en: "{client} finished without producing {path:?}." }
+    torrent_done() { fr: "Téléchargement pair-à-pair terminé.", en: "Peer-to-peer download finished." }
+
+    // --- update ---
+    update_header() { fr: "\n--- Mise à jour du patch ---", en: "\n--- Updating the patch ---" }
+    update_not_installed() {
+        fr: "Aucune installation du patch enregistrée pour ce répertoire : utilisez install.",
+        en: "No patch installation recorded for this directory: use install."
+    }
+    no_version_published() { fr: "Aucune version du patch n'est publiée.", en: "No version of the patch is published." }
+    version_unspecified() { fr: "non précisée", en: "unspecified" }
+    update_up_to_date(version: &str) { fr: "OK : Le patch est à jour (version {version}).", en: "OK: The patch is up to date (version {version})." }
+    update_available(latest: &str, installed: &str) {
+        fr: "Nouvelle version disponible : {latest} (installée : {installed}).",
+        en: "New version available: {latest} (installed: {installed})."
+    }
+    update_restoring() {
+        fr: "\nRestauration des fichiers d'origine avant la mise à jour...",
+        en: "\nRestoring the original files before the update..."
+    }
+    update_install_failed(e: &dyn std::fmt::Display) {
+        fr: "La nouvelle version n'a pas pu être installée ({e}). Le jeu est revenu à sa version d'origine : relancez update ou install.",
+        en: "The new version could not be installed ({e}). The game is back to its original version: run update or install again."
+    }
+
+    // --- versions ---
+    fetching_versions(url: &str) { fr: "Téléchargement de l'historique des versions depuis {url}...", en: "Downloading the version history from {url}..." }
+    version_not_found(version: &str, available: &str) {
+        fr: "La version '{version}' du patch n'existe pas. Versions disponibles : {available}.",
+        en: "Version '{version}' of the patch does not exist. Available versions: {available}."
+    }
+    versions_none() { fr: "aucune", en: "none" }
+    no_versions_published() { fr: "Aucune version publiée.", en: "No version published." }
+    versions_header() { fr: "\n--- Versions publiées du patch ---", en: "\n--- Published versions of the patch ---" }
+    version_latest_marker() { fr: " (dernière)", en: " (latest)" }
+    versions_install_hint() {
+        fr: "\nInstallez une version précise avec : install --patch-version <VERSION>",
+        en: "\nInstall a specific version with: install --patch-version <VERSION>"
+    }
+    // --- convert ---
+    patch_format_unknown(path: &Path) {
+        fr: "Format de patch inconnu pour {path:?} (extensions reconnues : .bps, .ips, .ups).",
+        en: "Unknown patch format for {path:?} (recognised extensions: .bps, .ips, .ups)."
+    }
+    patch_apply_failed(format: &dyn std::fmt::Display, e: &dyn std::fmt::Display) {
+        fr: "Erreur lors de l'application du patch {format} : {e}",
+        en: "Error while applying the {format} patch: {e}"
+    }
+    patch_create_failed(format: &dyn std::fmt::Display, e: &dyn std::fmt::Display) {
+        fr: "Erreur lors de la création du patch {format} : {e}",
+        en: "Error while creating the {format} patch: {e}"
+    }
+    ups_creation_unsupported() {
+        fr: "flips sait appliquer un patch UPS mais pas en créer : choisissez .bps ou .ips pour --to.",
+        en: "flips can apply a UPS patch but cannot create one: choose .bps or .ips for --to."
+    }
+    convert_header(from: &Path, from_format: &dyn std::fmt::Display, to: &Path, to_format: &dyn std::fmt::Display) {
+        fr: "\n--- Conversion de {from:?} ({from_format}) vers {to:?} ({to_format}) ---",
+        en: "\n--- Converting {from:?} ({from_format}) to {to:?} ({to_format}) ---"
+    }
+    patch_read_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Erreur lecture patch {path:?}: {e}", en: "Error reading patch {path:?}: {e}" }
+    convert_original_applied(size: usize, crc: u32) {
+        fr: "Patch d'origine appliqué : {size} octets, CRC32 {crc:#010X}.",
+        en: "Original patch applied: {size} bytes, CRC32 {crc:#010X}."
+    }
+    convert_mismatch(actual: u32, expected: u32) {
+        fr: "Le patch converti ne produit pas le même fichier que le patch d'origine (CRC32 {actual:#010X} au lieu de {expected:#010X}). Rien n'a été écrit.",
+        en: "The converted patch does not produce the same file as the original patch (CRC32 {actual:#010X} instead of {expected:#010X}). Nothing was written."
+    }
+    convert_same_output() { fr: "OK : Les deux patchs produisent le même fichier.", en: "OK: Both patches produce the same file." }
+    convert_written(format: &dyn std::fmt::Display, path: &Path, size: usize, original_size: usize) {
+        fr: "Patch {format} écrit dans {path:?} ({size} octets, contre {original_size} pour l'original).",
+        en: "{format} patch written to {path:?} ({size} bytes, versus {original_size} for the original)."
+    }
+    hook_set_ini_value(path: &str, section: &str, key: &str, value: &str) {
+        fr: "'{path}' : [{section}] {key}={value}",
+        en: "'{path}': [{section}] {key}={value}"
+    }
+    // --- ping ---
+    ping_failed(url: &str, ms: u128) { fr: "HEAD {url} : échec après {ms} ms.", en: "HEAD {url}: failed after {ms} ms." }
+    ping_unavailable(e: &dyn std::fmt::Display) { fr: "Service de patch indisponible : {e}", en: "Patch service unavailable: {e}" }
+    ping_response(url: &str, status: &dyn std::fmt::Display, ms: u128, content_type: &str) {
+        fr: "HEAD {url} : {status} en {ms} ms (Content-Type : {content_type}).",
+        en: "HEAD {url}: {status} in {ms} ms (Content-Type: {content_type})."
+    }
+    ping_not_json(content_type: &str) {
+        fr: "Service de patch indisponible : l'index ne renvoie pas du JSON (Content-Type : {content_type}). Le serveur a peut-être un problème.",
+        en: "Patch service unavailable: the index does not return JSON (Content-Type: {content_type}). The server may have a problem."
+    }
+    ping_ok() { fr: "OK : Le service de patch est disponible.", en: "OK: The patch service is available." }
+
+    // --- expected-layout ---
+    layout_role_patched() { fr: "patché (doit exister)", en: "patched (must exist)" }
+    layout_role_added() { fr: "ajouté", en: "added" }
+    layout_role_hook() { fr: "post-installation", en: "post-install" }
+    layout_header(platform: &str) {
+        fr: "\n--- Fichiers du jeu concernés par le patch (plateforme '{platform}') ---",
+        en: "\n--- Game files affected by the patch (platform '{platform}') ---"
+    }
+    layout_root() { fr: "<répertoire du jeu>/", en: "<game directory>/" }
+    layout_summary(patched: usize, added: usize, hooks: usize) {
+        fr: "\n{patched} fichier(s) patché(s), {added} ajouté(s), {hooks} modifié(s) après installation.",
+        en: "\n{patched} patched file(s), {added} added, {hooks} modified after installation."
+    }
+    layout_extras_unknown() {
+        fr: "Note : l'index ne liste pas le contenu de l'archive (fileCrcs) ; les fichiers ajoutés ne sont pas connus avant le téléchargement.",
+        en: "Note: the index does not list the archive contents (fileCrcs); added files are not known before the download."
+    }
+
+    // --- validate-index ---
+    index_windows_name(path: &str, problem: &str, portable: &dyn std::fmt::Display) {
+        fr: "{path} : {problem} (nom portable possible : {portable})",
+        en: "{path}: {problem} (possible portable name: {portable})"
+    }
+    index_footer_unreadable(patch: &str, e: &dyn std::fmt::Display) { fr: "{patch} : footer illisible ({e})", en: "{patch}: unreadable footer ({e})" }
+    crc_label_source() { fr: "source", en: "source" }
+    crc_label_target() { fr: "cible", en: "target" }
+    index_footer_crc_mismatch(patch: &str, label: &str, declared: u32, actual: u32) {
+        fr: "{patch} : CRC32 {label} {declared:#010X} dans l'index, {actual:#010X} dans le footer du patch",
+        en: "{patch}: {label} CRC32 {declared:#010X} in the index, {actual:#010X} in the patch footer"
+    }
+    index_platform_ok(platform: &str, count: usize) {
+        fr: "OK : '{platform}' : CRC32 des {count} patch(s) cohérents avec leur footer, chemins valides sous Windows.",
+        en: "OK: '{platform}': CRC32 of the {count} patch(es) match their footer, paths valid on Windows."
+    }
+    index_platform_problems(platform: &str, count: usize) {
+        fr: "ERREUR : '{platform}' : {count} problème(s) dans l'index :",
+        en: "ERROR: '{platform}': {count} problem(s) in the index:"
+    }
+    index_problems(count: usize, path: &Path) {
+        fr: "{count} problème(s) : CRC32 différents de ceux des patchs (index généré à partir d'autres patchs ? voir export-index) ou chemins invalides sous Windows, dans l'index {path:?}.",
+        en: "{count} problem(s): CRC32 different from the patches' (index generated from other patches? see export-index) or paths invalid on Windows, in index {path:?}."
+    }
+    // --- verify ---
+    read_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de lire {path:?} : {e}", en: "Cannot read {path:?}: {e}" }
+    read_error(path: &Path, e: &dyn std::fmt::Display) { fr: "Erreur lecture {path:?} : {e}", en: "Error reading {path:?}: {e}" }
+    verify_nothing(platform: &str) {
+        fr: "Aucun fichier patché à vérifier pour la plateforme '{platform}'.",
+        en: "No patched file to check for platform '{platform}'."
+    }
+    verify_header(platform: &str) {
+        fr: "\n--- Vérification des fichiers patchés (plateforme '{platform}') ---",
+        en: "\n--- Checking patched files (platform '{platform}') ---"
+    }
+    verify_missing(path: &str) { fr: "MANQUANT : {path}", en: "MISSING: {path}" }
+    verify_no_target_crc(path: &str) {
+        fr: "?  {path} : CRC32 cible absent de l'index, vérification impossible.",
+        en: "?  {path}: target CRC32 missing from the index, cannot check."
+    }
+    verify_ok(path: &str) { fr: "OK : {path}", en: "OK: {path}" }
+    verify_unpatched(path: &str, crc: u32) {
+        fr: "NON PATCHÉ : {path} (version d'origine, CRC32 {crc:#010X})",
+        en: "NOT PATCHED: {path} (original version, CRC32 {crc:#010X})"
+    }
+    verify_corrupted(path: &str, crc: u32) { fr: "CORROMPU : {path} (CRC32 {crc:#010X})", en: "CORRUPTED: {path} (CRC32 {crc:#010X})" }
+    verify_summary(ok: usize, unpatched: usize, corrupted: usize, missing: usize, unknown: usize) {
+        fr: "\n{ok} fichier(s) OK, {unpatched} non patché(s), {corrupted} corrompu(s), {missing} manquant(s), {unknown} non vérifiable(s).",
+        en: "\n{ok} file(s) OK, {unpatched} not patched, {corrupted} corrupted, {missing} missing, {unknown} not checkable."
+    }
+    verify_unpatched_advice() {
+        fr: "Fichiers non patchés ou manquants (mise à jour du jeu ?) : relancez install --incremental.",
+        en: "Unpatched or missing files (game update?): run install --incremental again."
+    }
+    verify_corrupted_advice() {
+        fr: "Fichiers corrompus : vérifiez l'intégrité des fichiers du jeu (Steam : Propriétés > Fichiers installés > Vérifier), puis relancez install.",
+        en: "Corrupted files: verify the integrity of the game files (Steam: Properties > Installed Files > Verify), then run install again."
+    }
+    verify_failed(count: usize) {
+        fr: "{count} fichier(s) ne sont pas dans la version patchée.",
+        en: "{count} file(s) are not in the patched version."
+    }
+
+    // --- audit ---
+    audit_not_installed() {
+        fr: "Aucune installation du patch enregistrée pour ce répertoire : rien à vérifier.",
+        en: "No patch installation recorded for this directory: nothing to check."
+    }
+    audit_header() { fr: "\n--- Audit des fichiers installés ---", en: "\n--- Audit of installed files ---" }
+    audit_manifest(path: &Path, platform: &str) { fr: "Manifeste : {path:?} (plateforme '{platform}').", en: "Manifest: {path:?} (platform '{platform}')." }
+    audit_unknown(path: &Path) {
+        fr: "{path:?} : CRC32 non enregistré par cette installation, vérification impossible.",
+        en: "{path:?}: CRC32 not recorded by this installation, cannot check."
+    }
+    audit_missing(path: &Path) { fr: "ERREUR : {path:?} a disparu.", en: "ERROR: {path:?} has disappeared." }
+    audit_reverted(path: &Path) {
+        fr: "ATTENTION : {path:?} est redevenu la version d'origine (mise à jour du jeu ?).",
+        en: "WARNING: {path:?} is back to the original version (game update?)."
+    }
+    audit_corrupted(path: &Path, crc: u32, expected: u32) {
+        fr: "ERREUR : {path:?} est altéré (CRC32 {crc:#010X}, attendu {expected:#010X}) : ni la version patchée, ni la version d'origine.",
+        en: "ERROR: {path:?} is altered (CRC32 {crc:#010X}, expected {expected:#010X}): neither the patched nor the original version."
+    }
+    audit_summary(intact: usize, reverted: usize, corrupted: usize, missing: usize, unknown: usize) {
+        fr: "\n{intact} fichier(s) intact(s), {reverted} redevenu(s) d'origine, {corrupted} altéré(s), {missing} disparu(s), {unknown} non vérifiable(s).",
+        en: "\n{intact} intact file(s), {reverted} back to original, {corrupted} altered, {missing} gone, {unknown} not checkable."
+    }
+    audit_reverted_advice() {
+        fr: "Fichiers redevenus d'origine ou disparus : relancez install --incremental pour les patcher à nouveau.",
+        en: "Files back to original or gone: run install --incremental to patch them again."
+    }
+    audit_corrupted_advice() {
+        fr: "Fichiers altérés : vérifiez l'intégrité des fichiers du jeu (Steam : Propriétés > Fichiers installés), puis relancez install.",
+        en: "Altered files: verify the integrity of the game files (Steam: Properties > Installed Files), then run install again."
+    }
+    audit_failed(count: usize) {
+        fr: "{count} fichier(s) ne sont plus dans la version installée par le patch.",
+        en: "{count} file(s) are no longer in the version installed by the patch."
+    }
+    audit_all_intact() { fr: "OK : Tous les fichiers installés par le patch sont intacts.", en: "OK: All files installed by the patch are intact." }
+
+    // --- show-changes ---
+    strings_count(before: usize, after: usize) {
+        fr: "{before} chaîne(s) dans l'original, {after} après patch.",
+        en: "{before} string(s) in the original, {after} after patching."
+    }
+    strings_fallback(e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : {e} Comparaison des textes imprimables du fichier.",
+        en: "WARNING: {e} Comparing the printable text of the file."
+    }
+    strings_changed(count: usize, patch: &Path) { fr: "\n{count} chaîne(s) modifiée(s) par {patch:?}.", en: "\n{count} string(s) changed by {patch:?}." }
+    // --- export-state, import-state ---
+    export_nothing(game_dir: &Path) {
+        fr: "Aucune installation enregistrée pour {game_dir:?} : rien à exporter.",
+        en: "No installation recorded for {game_dir:?}: nothing to export."
+    }
+    export_backup_unreadable(path: &Path, e: &dyn std::fmt::Display) {
+        fr: "ATTENTION : Sauvegarde {path:?} illisible, non exportée : {e}",
+        en: "WARNING: Backup {path:?} unreadable, not exported: {e}"
+    }
+    create_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible de créer {path:?} : {e}", en: "Cannot create {path:?}: {e}" }
+    open_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible d'ouvrir {path:?} : {e}", en: "Cannot open {path:?}: {e}" }
+    export_adding_backup(backup: &str) { fr: "Ajout de la sauvegarde {backup}", en: "Adding backup {backup}" }
+    export_done(path: &Path, manifests: usize, backups: usize) {
+        fr: "OK : État exporté dans {path:?} ({manifests} manifeste(s), {backups} sauvegarde(s)).",
+        en: "OK: State exported to {path:?} ({manifests} manifest(s), {backups} backup(s))."
+    }
+    state_dangerous_path(name: &str) {
+        fr: "L'archive d'état contient un chemin dangereux : {name:?}.",
+        en: "The state archive contains a dangerous path: {name:?}."
+    }
+    state_info_invalid(name: &str, path: &Path, e: &dyn std::fmt::Display) {
+        fr: "{name} invalide dans {path:?} : {e}",
+        en: "Invalid {name} in {path:?}: {e}"
+    }
+    state_format_too_recent(patcher: &str, format: u32, current: &str, current_format: u32) {
+        fr: "État exporté par le patcher {patcher} (format {format}), plus récent que celui-ci ({current}, format {current_format}) : mettez le patcher à jour.",
+        en: "State exported by patcher {patcher} (format {format}), newer than this one ({current}, format {current_format}): update the patcher."
+    }
+    state_manifest_invalid(name: &str, e: &dyn std::fmt::Display) { fr: "Manifeste {name} invalide : {e}", en: "Invalid manifest {name}: {e}" }
+    state_info_not_first(name: &str, path: &Path) {
+        fr: "{name} absent en tête de l'archive {path:?}.",
+        en: "{name} missing at the start of archive {path:?}."
+    }
+    state_backup_undeclared(backup: &str, name: &str) {
+        fr: "Sauvegarde {backup} non déclarée dans {name}.",
+        en: "Backup {backup} not declared in {name}."
+    }
+    state_backup_corrupted(backup: &str, actual: u32, expected: u32) {
+        fr: "Sauvegarde {backup} corrompue dans l'archive : CRC32 {actual:#010X}, attendu {expected:#010X}.",
+        en: "Backup {backup} corrupted in the archive: CRC32 {actual:#010X}, expected {expected:#010X}."
+    }
+    state_backup_conflict(path: &Path) {
+        fr: "{path:?} existe déjà et diffère de la sauvegarde exportée : import annulé.",
+        en: "{path:?} already exists and differs from the exported backup: import cancelled."
+    }
+    state_not_archive(path: &Path, name: &str) {
+        fr: "{path:?} n'est pas une archive d'état du patcher ({name} absent).",
+        en: "{path:?} is not a patcher state archive ({name} missing)."
+    }
+    state_incomplete(found: usize, expected: usize) {
+        fr: "Archive incomplète : {found} sauvegarde(s) sur {expected} présentes.",
+        en: "Incomplete archive: {found} of {expected} backup(s) present."
+    }
+    state_backups_verified(count: usize, platform: &str) {
+        fr: "OK : {count} sauvegarde(s) vérifiée(s) (état de la plateforme '{platform}').",
+        en: "OK: {count} backup(s) checked (state of platform '{platform}')."
+    }
+    state_backup_restored(path: &Path) { fr: "Sauvegarde {path:?} restaurée.", en: "Backup {path:?} restored." }
+    state_imported(game_dir: &Path) {
+        fr: "OK : État importé pour {game_dir:?}. La désinstallation restaurera les fichiers d'origine.",
+        en: "OK: State imported for {game_dir:?}. Uninstalling will restore the original files."
+    }
+    // --- serve ---
+    serve_bind_failed(port: u16, e: &dyn std::fmt::Display) { fr: "Impossible d'écouter sur 127.0.0.1:{port} : {e}", en: "Cannot listen on 127.0.0.1:{port}: {e}" }
+    serve_listening(port: u16) {
+        fr: "Serveur local en écoute sur http://127.0.0.1:{port} (Ctrl+C pour arrêter).",
+        en: "Local server listening on http://127.0.0.1:{port} (Ctrl+C to stop)."
+    }
+    serve_connection_refused(e: &dyn std::fmt::Display) { fr: "ATTENTION : Connexion refusée : {e}", en: "WARNING: Connection refused: {e}" }
+    serve_origin_refused() { fr: "Origine non autorisée.", en: "Origin not allowed." }
+    serve_unknown_endpoint(path: &str) { fr: "Endpoint inconnu : {path}", en: "Unknown endpoint: {path}" }
+    serve_wrong_method(path: &str, method: &str) { fr: "{path} attend la méthode {method}.", en: "{path} expects method {method}." }
+    serve_unknown_param(path: &str, name: &str) { fr: "Paramètre inconnu pour {path} : {name}", en: "Unknown parameter for {path}: {name}" }
+    serve_spawn_failed(e: &dyn std::fmt::Display) { fr: "Impossible de lancer le patcher : {e}", en: "Cannot start the patcher: {e}" }
+    serve_bad_request_line() { fr: "Ligne de requête invalide.", en: "Invalid request line." }
+    serve_bad_encoding(value: &str) { fr: "Encodage invalide dans le paramètre : {value}", en: "Invalid encoding in parameter: {value}" }
+    serve_not_utf8(value: &str) { fr: "Paramètre non UTF-8 : {value}", en: "Non-UTF-8 parameter: {value}" }
+    // --- status ---
+    status_header() { fr: "\n--- État du patch FR ---", en: "\n--- FR patch status ---" }
+    status_game(game_dir: &Path) { fr: "Jeu : {game_dir:?}", en: "Game: {game_dir:?}" }
+    status_pending(date: &str, count: usize) {
+        fr: "ATTENTION : Une installation commencée le {date} a été interrompue ({count} fichier(s) déjà traité(s)) : relancez install --resume pour la terminer, ou uninstall.",
+        en: "WARNING: An installation started on {date} was interrupted ({count} file(s) already processed): run install --resume to finish it, or uninstall."
+    }
+    status_not_installed() { fr: "Patch installé : non.", en: "Patch installed: no." }
+    status_installed(platform: &str) { fr: "Patch installé : oui (plateforme '{platform}').", en: "Patch installed: yes (platform '{platform}')." }
+    status_version(version: &str) { fr: "Version : {version}", en: "Version: {version}" }
+    status_version_latest() {
+        fr: "Version : dernière version publiée au moment de l'installation.",
+        en: "Version: latest version published at installation time."
+    }
+    status_installed_at(date: &str) { fr: "Installé le : {date}", en: "Installed on: {date}" }
+    status_file_missing(path: &Path) { fr: "{path:?} : disparu", en: "{path:?}: gone" }
+    status_file_reverted(path: &Path) { fr: "{path:?} : redevenu la version d'origine", en: "{path:?}: back to the original version" }
+    status_file_modified(path: &Path) { fr: "{path:?} : modifié", en: "{path:?}: modified" }
+    status_file_count(count: usize) { fr: "Fichiers installés : {count}.", en: "Installed files: {count}." }
+    status_unknown(count: usize) {
+        fr: "{count} fichier(s) ne peuvent pas être vérifiés (installation antérieure à l'enregistrement des CRC32).",
+        en: "{count} file(s) cannot be checked (installation older than CRC32 recording)."
+    }
+    status_unmodified() { fr: "OK : Aucun fichier modifié depuis l'installation.", en: "OK: No file modified since installation." }
+    status_modified(count: usize) {
+        fr: "ATTENTION : {count} fichier(s) modifié(s) depuis l'installation :",
+        en: "WARNING: {count} file(s) modified since installation:"
+    }
+    status_modified_advice() {
+        fr: "Voir audit pour le détail, ou relancez install --incremental pour les patcher à nouveau.",
+        en: "See audit for details, or run install --incremental to patch them again."
+    }
+
+    // --- verify-backups ---
+    verify_backups_header() { fr: "\n--- Vérification des sauvegardes ---", en: "\n--- Checking backups ---" }
+    verify_backups_no_crc(path: &str) {
+        fr: "{path} : CRC32 d'origine non fourni par l'index, vérification impossible.",
+        en: "{path}: original CRC32 not provided by the index, cannot check."
+    }
+    verify_backups_none(path: &str) { fr: "{path} : aucune sauvegarde.", en: "{path}: no backup." }
+    verify_backups_ok(path: &Path, crc: u32) {
+        fr: "OK : {path:?} correspond à la version d'origine ({crc:#010X}).",
+        en: "OK: {path:?} matches the original version ({crc:#010X})."
+    }
+    verify_backups_patched(path: &Path, crc: u32) {
+        fr: "{path:?} contient la version PATCHÉE ({crc:#010X}), pas l'original : le patch a sans doute été installé deux fois.",
+        en: "{path:?} contains the PATCHED version ({crc:#010X}), not the original: the patch was probably installed twice."
+    }
+    verify_backups_corrupted(path: &Path, crc: u32) {
+        fr: "{path:?} est corrompue ou d'une version inconnue (CRC32 {crc:#010X}).",
+        en: "{path:?} is corrupted or of an unknown version (CRC32 {crc:#010X})."
+    }
+    verify_backups_unreadable(path: &Path, e: &dyn std::fmt::Display) { fr: "{path:?} est illisible : {e}", en: "{path:?} is unreadable: {e}" }
+    verify_backups_repaired(backup: &Path, original: &Path) {
+        fr: "OK : Sauvegarde {backup:?} recréée à partir de {original:?}, qui est d'origine.",
+        en: "OK: Backup {backup:?} recreated from {original:?}, which is original."
+    }
+    verify_backups_repair_hint(original: &Path) {
+        fr: "{original:?} est d'origine : relancez avec --repair pour recréer la sauvegarde.",
+        en: "{original:?} is original: run again with --repair to recreate the backup."
+    }
+    verify_backups_original_altered(original: &Path) {
+        fr: "Le fichier du jeu {original:?} n'est pas d'origine non plus : vérifiez l'intégrité des fichiers du jeu dans Steam avant toute désinstallation.",
+        en: "The game file {original:?} is not original either: verify the integrity of the game files in Steam before uninstalling."
+    }
+    verify_backups_summary(checked: usize, problems: usize) {
+        fr: "\n{checked} sauvegarde(s) vérifiée(s), {problems} problème(s).",
+        en: "\n{checked} backup(s) checked, {problems} problem(s)."
+    }
+    verify_backups_failed(count: usize) {
+        fr: "{count} sauvegarde(s) ne permettent pas de restaurer la version d'origine.",
+        en: "{count} backup(s) cannot restore the original version."
+    }
+    // --- profile ---
+    profiles_invalid(path: &Path, e: &dyn std::fmt::Display) { fr: "Fichier de profils {path:?} invalide : {e}", en: "Invalid profiles file {path:?}: {e}" }
+    profile_name_invalid(name: &str) {
+        fr: "Nom de profil invalide : '{name}' (lettres, chiffres, '-' et '_' uniquement).",
+        en: "Invalid profile name: '{name}' (letters, digits, '-' and '_' only)."
+    }
+    profile_unknown_hint(name: &str, known: &str) {
+        fr: "Profil '{name}' inconnu. Profils existants : {known}. Créez-le avec : profile add {name} -d <REPERTOIRE_JEU>",
+        en: "Unknown profile '{name}'. Existing profiles: {known}. Create it with: profile add {name} -d <GAME_DIR>"
+    }
+    profiles_none_known() { fr: "aucun", en: "none" }
+    profiles_empty() {
+        fr: "Aucun profil. Créez-en un avec : profile add <NOM> -d <REPERTOIRE_JEU>",
+        en: "No profile. Create one with: profile add <NAME> -d <GAME_DIR>"
+    }
+    profiles_header() { fr: "\n--- Profils ---", en: "\n--- Profiles ---" }
+    profile_state_installed(platform: &str, count: usize) {
+        fr: "patch installé (plateforme '{platform}', {count} fichier(s))",
+        en: "patch installed (platform '{platform}', {count} file(s))"
+    }
+    profile_state_none() { fr: "aucune installation enregistrée", en: "no installation recorded" }
+    profile_game(game_dir: &Path) { fr: "    Jeu : {game_dir:?}", en: "    Game: {game_dir:?}" }
+    profile_platform(platform: &str) { fr: "    Plateforme : {platform}", en: "    Platform: {platform}" }
+    profile_platform_auto() { fr: "détection automatique", en: "automatic detection" }
+    profile_state(state: &str) { fr: "    État : {state}", en: "    State: {state}" }
+    profile_exists(name: &str) {
+        fr: "Le profil '{name}' existe déjà. Supprimez-le d'abord avec : profile remove {name}",
+        en: "Profile '{name}' already exists. Remove it first with: profile remove {name}"
+    }
+    profile_game_dir_inaccessible(game_dir: &Path) {
+        fr: "ATTENTION : {game_dir:?} n'est pas (encore) un répertoire accessible. Profil créé quand même.",
+        en: "WARNING: {game_dir:?} is not (yet) an accessible directory. Profile created anyway."
+    }
+    profile_created(name: &str, game_dir: &Path) { fr: "Profil '{name}' créé pour {game_dir:?}.", en: "Profile '{name}' created for {game_dir:?}." }
+    profile_unknown(name: &str) { fr: "Profil '{name}' inconnu.", en: "Unknown profile '{name}'." }
+    profile_removed(name: &str, state_dir: &Path) {
+        fr: "Profil '{name}' supprimé. Son historique d'installation reste dans {state_dir:?}.",
+        en: "Profile '{name}' removed. Its installation history stays in {state_dir:?}."
+    }
+
+    // --- export-index ---
+    export_source_crc_mismatch(path: &Path, crc: u32) {
+        fr: "ATTENTION : {path:?} existe mais son CRC32 ne correspond pas au CRC32 source du patch ({crc:#010X}). Recherche d'un autre fichier...",
+        en: "WARNING: {path:?} exists but its CRC32 does not match the patch's source CRC32 ({crc:#010X}). Looking for another file..."
+    }
+    export_header() { fr: "\n--- Génération de l'index des patchs ---", en: "\n--- Generating the patch index ---" }
+    export_patches_dir_invalid(path: &Path) {
+        fr: "Le répertoire des patchs {path:?} n'est pas un répertoire valide.",
+        en: "The patches directory {path:?} is not a valid directory."
+    }
+    export_source_dir_invalid(path: &Path) {
+        fr: "Le répertoire source {path:?} n'est pas un répertoire valide.",
+        en: "The source directory {path:?} is not a valid directory."
+    }
+    export_no_patches(path: &Path) { fr: "Aucun fichier .bps trouvé dans {path:?}.", en: "No .bps file found in {path:?}." }
+    export_patch_matched(patch: &str, source: &str, source_crc: u32, target_crc: u32) {
+        fr: "{patch} -> {source} (source {source_crc:#010X}, cible {target_crc:#010X})",
+        en: "{patch} -> {source} (source {source_crc:#010X}, target {target_crc:#010X})"
+    }
+    export_patch_unmatched(dir: &Path, crc: u32, patch: &Path) {
+        fr: "ERREUR : Aucun fichier de {dir:?} ne correspond au CRC32 source {crc:#010X} du patch {patch:?}. Patch ignoré.",
+        en: "ERROR: No file in {dir:?} matches source CRC32 {crc:#010X} of patch {patch:?}. Patch skipped."
+    }
+    export_no_file_url() {
+        fr: "ATTENTION : Aucune URL d'archive fournie (--file-url) : le champ fileUrl est à compléter avant publication.",
+        en: "WARNING: No archive URL given (--file-url): the fileUrl field must be filled in before publishing."
+    }
+    export_write_failed(path: &Path, e: &dyn std::fmt::Display) { fr: "Impossible d'écrire l'index {path:?} : {e}", en: "Cannot write index {path:?}: {e}" }
+    export_index_written(path: &Path, count: usize, platform: &str) {
+        fr: "Index écrit dans {path:?} : {count} patch(s) pour la plateforme '{platform}'.",
+        en: "Index written to {path:?}: {count} patch(es) for platform '{platform}'."
+    }
+    export_unmatched(count: usize) {
+        fr: "{count} patch(s) sans fichier source correspondant n'ont pas été ajoutés à l'index.",
+        en: "{count} patch(es) without a matching source file were not added to the index."
+    }
+
+    // --- check-crc ---
+    check_crc_header(platform: &str) {
+        fr: "\n--- CRC32 des fichiers du jeu (plateforme '{platform}') ---",
+        en: "\n--- CRC32 of the game files (platform '{platform}') ---"
+    }
+    check_crc_col_file() { fr: "Fichier", en: "File" }
+    check_crc_col_actual() { fr: "Réel", en: "Actual" }
+    check_crc_col_source() { fr: "Source", en: "Source" }
+    check_crc_col_target() { fr: "Cible", en: "Target" }
+    check_crc_col_verdict() { fr: "Verdict", en: "Verdict" }
+    check_crc_not_found() { fr: "introuvable", en: "not found" }
+    check_crc_patchable() { fr: "patchable", en: "patchable" }
+    check_crc_patched() { fr: "déjà patché", en: "already patched" }
+    check_crc_unknown_no_crc() { fr: "inconnu (CRC absents de l'index)", en: "unknown (CRCs missing from the index)" }
+    check_crc_unknown() { fr: "inconnu", en: "unknown" }
+    check_crc_unknown_summary(count: usize) {
+        fr: "\n{count} fichier(s) dans une version inconnue : ni la version d'origine attendue par le patch, ni la version patchée. Vérifiez l'intégrité des fichiers du jeu (Steam : Propriétés > Fichiers installés > Vérifier).",
+        en: "\n{count} file(s) in an unknown version: neither the original version expected by the patch nor the patched version. Verify the integrity of the game files (Steam: Properties > Installed Files > Verify)."
+    }
+    // --- gamemaker ---
+    gm_not_data_file() {
+        fr: "Ce n'est pas un fichier de données GameMaker (en-tête FORM absent).",
+        en: "Not a GameMaker data file (FORM header missing)."
+    }
+    gm_truncated() { fr: "Fichier GameMaker tronqué.", en: "Truncated GameMaker file." }
+    gm_chunk_name_invalid(position: usize) { fr: "Nom de chunk invalide à la position {position}.", en: "Invalid chunk name at position {position}." }
+    gm_chunk_truncated(name: &str, size: usize) { fr: "Chunk {name} tronqué ({size} octets annoncés).", en: "Chunk {name} truncated ({size} bytes declared)." }
+    gm_gen8_too_short_file() { fr: "Fichier trop court pour contenir un chunk GEN8.", en: "File too short to contain a GEN8 chunk." }
+    gm_first_not_gen8() { fr: "Le premier chunk n'est pas GEN8.", en: "The first chunk is not GEN8." }
+    gm_gen8_too_short(size: usize) { fr: "Chunk GEN8 trop court ({size} octets).", en: "GEN8 chunk too short ({size} bytes)." }
+    gm_form_size_mismatch(declared: u64, actual: usize) {
+        fr: "taille annoncée par FORM ({declared} octets) différente de celle du fichier ({actual} octets)",
+        en: "size declared by FORM ({declared} bytes) differs from the file size ({actual} bytes)"
+    }
+    gm_chunks_coverage(covered: u64, form_size: u64) {
+        fr: "les chunks couvrent {covered} octets sur les {form_size} du conteneur FORM",
+        en: "the chunks cover {covered} bytes out of the {form_size} of the FORM container"
+    }
+    gm_first_chunk(name: &str) { fr: "premier chunk {name} au lieu de GEN8", en: "first chunk {name} instead of GEN8" }
+    gm_chunk_duplicate(name: &str) { fr: "chunk {name} présent plusieurs fois", en: "chunk {name} present more than once" }
+    gm_chunks_missing(missing: &str) { fr: "chunk(s) indispensable(s) absent(s) : {missing}", en: "required chunk(s) missing: {missing}" }
+    gm_strg_missing() { fr: "Chunk STRG (chaînes du jeu) introuvable.", en: "STRG chunk (game strings) not found." }
+    gm_strg_truncated() { fr: "Chunk STRG tronqué.", en: "Truncated STRG chunk." }
+    gm_strg_table_truncated() { fr: "Table du chunk STRG tronquée.", en: "Truncated STRG chunk table." }
+    gm_strg_out_of_file() { fr: "Chaîne hors du fichier dans le chunk STRG.", en: "String outside the file in the STRG chunk." }
+
+    // --- git revision ---
+    git_branch(branch: &str) { fr: "branche {branch}", en: "branch {branch}" }
+    git_detached_head() { fr: "HEAD détachée", en: "detached HEAD" }
+
+    // --- progression ---
+    phase_download() { fr: "Téléchargement", en: "Download" }
+    phase_extraction() { fr: "Extraction", en: "Extraction" }
+    phase_patch() { fr: "Application des patchs", en: "Applying patches" }
+    phase_extras() { fr: "Copie des fichiers", en: "Copying files" }
+    deadline_exceeded(seconds: u64) {
+        fr: "Durée maximale de {seconds} s dépassée (--deadline), opération interrompue.",
+        en: "Maximum duration of {seconds} s exceeded (--deadline), operation aborted."
+    }
+}
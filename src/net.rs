@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use reqwest::blocking::{Client, ClientBuilder, Response};
+use reqwest::redirect::{Attempt, Policy};
+use reqwest::StatusCode;
+
+use crate::messages;
+
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Hôtes vers lesquels une redirection est suivie, sous-domaines compris.
+const ALLOWED_REDIRECT_HOSTS: &[&str] = &["deltarune-fr.com"];
+
+const MAX_REDIRECTS: usize = 10;
+
+/// Connexions inactives gardées ouvertes par hôte, réglable dans la configuration ([network]).
+static MAX_IDLE_PER_HOST: AtomicUsize = AtomicUsize::new(4);
+
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Erreur réseau, classée selon l'étape de la requête qui a échoué.
+#[derive(Debug)]
+pub enum NetError {
+    /// Connexion impossible (DNS, TCP, TLS) ou délai dépassé avant la réponse.
+    Connect { url: String, source: reqwest::Error },
+    /// Redirection vers un hôte ou un schéma non autorisé.
+    Redirect { url: String, source: reqwest::Error },
+    /// Le serveur a répondu avec un code d'erreur.
+    Status { url: String, status: StatusCode },
+    /// La connexion a été coupée pendant la réception du contenu.
+    Body { url: String, source: Box<dyn Error + Send + Sync> },
+    /// Le contenu reçu n'est pas un JSON valide.
+    Json { url: String, source: serde_json::Error },
+}
+
+impl NetError {
+    /// Vrai pour les erreurs qui peuvent disparaître en réessayant.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            NetError::Connect { .. } | NetError::Body { .. } => true,
+            NetError::Status { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            NetError::Json { .. } | NetError::Redirect { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            NetError::Connect { url, source } if source.is_timeout() => messages::net_timeout(url),
+            NetError::Connect { url, .. } => messages::net_connect(url),
+            NetError::Redirect { url, .. } => messages::net_redirect(url),
+            NetError::Status { url, status } => messages::net_status(status, url),
+            NetError::Body { url, .. } => messages::net_body(url),
+            NetError::Json { url, .. } => messages::net_json(url),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl Error for NetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NetError::Connect { source, .. } | NetError::Redirect { source, .. } => Some(source),
+            NetError::Body { source, .. } => Some(source.as_ref()),
+            NetError::Json { source, .. } => Some(source),
+            NetError::Status { .. } => None,
+        }
+    }
+}
+
+fn is_allowed_redirect_host(host: &str) -> bool {
+    ALLOWED_REDIRECT_HOSTS
+        .iter()
+        .any(|allowed| host == *allowed || host.strip_suffix(allowed).is_some_and(|sub| sub.ends_with('.')))
+}
+
+// Une redirection (3xx) n'est suivie que vers un hôte de la liste blanche, et jamais de HTTPS vers HTTP.
+fn check_redirect(attempt: Attempt) -> reqwest::redirect::Action {
+    if attempt.previous().len() >= MAX_REDIRECTS {
+        return attempt.error(messages::net_too_many_redirects(MAX_REDIRECTS));
+    }
+    let downgrade = attempt.url().scheme() != "https"
+        && attempt.previous().iter().any(|previous| previous.scheme() == "https");
+    if downgrade {
+        let message = messages::net_https_downgrade(attempt.url());
+        return attempt.error(message);
+    }
+    match attempt.url().host_str() {
+        Some(host) if is_allowed_redirect_host(host) => attempt.follow(),
+        _ => {
+            let message = messages::net_redirect_host(attempt.url(), &ALLOWED_REDIRECT_HOSTS.join(", "));
+            attempt.error(message)
+        }
+    }
+}
+
+/// Nombre de connexions inactives gardées ouvertes par hôte ; à régler avant la première requête.
+pub fn set_max_idle_per_host(max_idle: usize) {
+    MAX_IDLE_PER_HOST.store(max_idle, Ordering::Relaxed);
+}
+
+// Un seul client par usage pour tout le processus : ses connexions sont réutilisées d'une requête
+// à l'autre (index, HEAD puis GET de l'archive, volumes...) au lieu de refaire à chaque fois la
+// poignée de main TLS. HTTP/2 est négocié par ALPN quand le serveur le propose.
+fn shared_client(cell: &'static OnceLock<Client>, builder: ClientBuilder) -> Result<Client, reqwest::Error> {
+    if let Some(client) = cell.get() {
+        return Ok(client.clone());
+    }
+    let client = builder
+        .pool_max_idle_per_host(MAX_IDLE_PER_HOST.load(Ordering::Relaxed))
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(Duration::from_secs(60))
+        .build()?;
+    Ok(cell.get_or_init(|| client).clone())
+}
+
+/// Client des URL de l'index et du site officiel : redirections limitées à la liste blanche.
+pub fn client() -> Result<Client, reqwest::Error> {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    shared_client(&CLIENT, Client::builder().redirect(Policy::custom(check_redirect)))
+}
+
+/// Client des URL fournies explicitement par l'utilisateur (--mirror) : redirections libres.
+pub fn user_client() -> Result<Client, reqwest::Error> {
+    static USER_CLIENT: OnceLock<Client> = OnceLock::new();
+    shared_client(&USER_CLIENT, Client::builder())
+}
+
+/// Envoie un GET et vérifie le code de statut, sans lire le corps.
+pub fn get(url: &str) -> Result<Response, NetError> {
+    send_get(client(), url, None, 0)
+}
+
+/// Comme `get`, avec une durée maximale pour toute la requête, lecture du corps comprise.
+/// `url_from_user` : URL fournie par l'utilisateur, dont les redirections ne sont pas filtrées.
+/// Avec `offset` non nul, seule la suite du contenu est demandée (en-tête Range) : le serveur
+/// répond 206 s'il l'accepte, ou 200 avec le contenu complet s'il l'ignore.
+pub fn get_with_timeout(url: &str, url_from_user: bool, timeout: Option<Duration>, offset: u64) -> Result<Response, NetError> {
+    let client = if url_from_user { user_client() } else { client() };
+    send_get(client, url, timeout, offset)
+}
+
+/// Envoie un HEAD et vérifie le code de statut : les en-têtes seuls, sans télécharger le contenu.
+pub fn head(url: &str) -> Result<Response, NetError> {
+    let connect_error = |source| connect_error(url, source);
+    let started = Instant::now();
+    let response = client().map_err(connect_error)?.head(url).send();
+    trace_result("HEAD", url, &response, started);
+    let response = response.map_err(connect_error)?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(NetError::Status { url: url.to_string(), status });
+    }
+    Ok(response)
+}
+
+fn connect_error(url: &str, source: reqwest::Error) -> NetError {
+    if source.is_redirect() {
+        NetError::Redirect { url: url.to_string(), source }
+    } else {
+        NetError::Connect { url: url.to_string(), source }
+    }
+}
+
+fn send_get(client: Result<Client, reqwest::Error>, url: &str, timeout: Option<Duration>, offset: u64) -> Result<Response, NetError> {
+    let connect_error = |source| connect_error(url, source);
+
+    let mut request = client.map_err(connect_error)?.get(url);
+    if let Some(timeout) = timeout {
+        request = request.timeout(timeout);
+    }
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let started = Instant::now();
+    let response = request.send();
+    trace_result("GET", url, &response, started);
+    let response = response.map_err(connect_error)?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(NetError::Status { url: url.to_string(), status });
+    }
+    Ok(response)
+}
+
+/// Valeur d'un en-tête de la réponse, ou « - » s'il est absent ou illisible.
+pub fn header_value(response: &Response, name: reqwest::header::HeaderName) -> &str {
+    response.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or("-")
+}
+
+/// Chaîne complète d'une erreur et de ses causes, sur une ligne.
+pub fn error_chain(error: &dyn Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        chain.push_str(" : ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    chain
+}
+
+/// Trace une requête HTTP (visible avec -vv) : URL finale après redirections, statut,
+/// en-têtes utiles et temps de réponse, ou la chaîne d'erreur complète de reqwest.
+pub fn trace_result(method: &str, url: &str, result: &Result<Response, reqwest::Error>, started: Instant) {
+    let elapsed_ms = started.elapsed().as_millis();
+    match result {
+        Ok(response) => tracing::debug!(
+            "{} {} -> {} ({}, {:?}) en {} ms ; Content-Length: {}, Content-Type: {}, ETag: {}",
+            method,
+            url,
+            response.url(),
+            response.status(),
+            response.version(),
+            elapsed_ms,
+            header_value(response, reqwest::header::CONTENT_LENGTH),
+            header_value(response, reqwest::header::CONTENT_TYPE),
+            header_value(response, reqwest::header::ETAG),
+        ),
+        Err(e) => tracing::debug!("{} {} a échoué après {} ms : {}", method, url, elapsed_ms, error_chain(e)),
+    }
+}
+
+/// Télécharge entièrement le corps d'une réponse en mémoire.
+pub fn get_bytes(url: &str) -> Result<Vec<u8>, NetError> {
+    let response = get(url)?;
+    let body = response
+        .bytes()
+        .map_err(|source| NetError::Body { url: url.to_string(), source: source.into() })?;
+    Ok(body.to_vec())
+}
+
+/// Télécharge un document JSON, en distinguant les erreurs réseau des erreurs d'analyse.
+pub fn get_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, NetError> {
+    let body = with_retry(|| get_bytes(url))?;
+    serde_json::from_slice(&body).map_err(|source| NetError::Json { url: url.to_string(), source })
+}
+
+/// Réessaie l'opération tant que l'erreur est transitoire, avec un délai croissant.
+pub fn with_retry<T>(mut operation: impl FnMut() -> Result<T, NetError>) -> Result<T, NetError> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(e) if e.is_transient() && attempt < MAX_ATTEMPTS => {
+                let delay = Duration::from_secs(2u64.pow(attempt - 1));
+                eprintln!("{}", messages::net_retry(&e, attempt + 1, MAX_ATTEMPTS, delay.as_secs()));
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, PoisonError};
+use clap::ValueEnum;
+use indicatif::MultiProgress;
+use owo_colors::OwoColorize;
+
+/// Couleurs de la sortie.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Couleurs seulement vers un terminal, et si NO_COLOR n'est pas défini
+    Auto,
+    /// Toujours des couleurs, même redirigé vers un fichier
+    Always,
+    /// Jamais de couleurs
+    Never,
+}
+
+/// Format des journaux (-v).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Lignes lisibles sur la sortie d'erreur
+    Text,
+    /// Un objet JSON par ligne (horodatage, niveau, message, champs), pour Loki, ELK...
+    Json,
+}
+
+/// Applique le choix de couleurs pour toute la suite du programme.
+/// En mode `auto`, anstream retire les couleurs si la sortie n'est pas un terminal
+/// ou si NO_COLOR est défini ; `always` passe outre, comme le veut la convention NO_COLOR.
+pub fn init(mode: ColorMode) {
+    let choice = match mode {
+        ColorMode::Auto => anstream::ColorChoice::Auto,
+        ColorMode::Always => anstream::ColorChoice::Always,
+        ColorMode::Never => anstream::ColorChoice::Never,
+    };
+    choice.write_global();
+}
+
+/// Destination des messages normalement écrits sur la sortie standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StdoutMode {
+    Normal,
+    /// Sortie standard réservée à un format machine (--machine-progress) : messages sur stderr.
+    ToStderr,
+    /// Messages supprimés (--quiet) ; avertissements et erreurs restent sur stderr.
+    Quiet,
+}
+
+static STDOUT_MODE: AtomicU8 = AtomicU8::new(StdoutMode::Normal as u8);
+
+pub fn set_stdout_mode(mode: StdoutMode) {
+    STDOUT_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+fn stdout_mode() -> StdoutMode {
+    match STDOUT_MODE.load(Ordering::Relaxed) {
+        1 => StdoutMode::ToStderr,
+        2 => StdoutMode::Quiet,
+        _ => StdoutMode::Normal,
+    }
+}
+
+// Barres de progression affichées, s'il y en a : les messages sont écrits au-dessus d'elles
+// (MultiProgress::suspend) plutôt qu'au milieu d'une barre en cours de dessin. Le verrou sérialise
+// aussi les messages émis depuis plusieurs threads : chaque ligne est écrite d'un seul tenant.
+static PROGRESS_BARS: Mutex<Option<MultiProgress>> = Mutex::new(None);
+
+/// Déclare les barres de progression affichées (None quand elles disparaissent).
+pub fn set_progress_bars(bars: Option<MultiProgress>) {
+    *PROGRESS_BARS.lock().unwrap_or_else(PoisonError::into_inner) = bars;
+}
+
+/// Écrit une ligne destinée à un programme sur la sortie standard, quel que soit le mode :
+/// sans couleur et vidée immédiatement, pour que le lecteur la reçoive sans attendre.
+pub fn machine_line(args: fmt::Arguments) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", args).and_then(|_| stdout.flush());
+}
+
+/// Écrit une question (ou la fin de sa ligne) sans retour à la ligne, juste avant de lire la réponse.
+/// Sur la sortie standard en temps normal ; sur stderr quand elle est réservée à un programme
+/// (--machine-progress), et même avec --quiet : une question doit rester visible.
+pub fn prompt(args: fmt::Arguments) -> std::io::Result<()> {
+    if stdout_mode() == StdoutMode::Normal {
+        let mut stdout = std::io::stdout().lock();
+        write!(stdout, "{}", args).and_then(|_| stdout.flush())
+    } else {
+        let mut stderr = std::io::stderr().lock();
+        write!(stderr, "{}", args).and_then(|_| stderr.flush())
+    }
+}
+
+/// Active les traces de débogage sur stderr selon le nombre de -v :
+/// -v pour les informations, -vv pour le détail (requêtes HTTP), -vvv pour tout.
+pub fn init_tracing(verbose: u8, format: LogFormat) {
+    let level = match (verbose, format) {
+        (0, LogFormat::Text) => return,
+        // Les journaux JSON sont destinés à un agrégateur : les événements principaux (début
+        // d'installation, patch appliqué, erreur) y figurent même sans -v.
+        (0 | 1, _) => tracing::Level::INFO,
+        (2, _) => tracing::Level::DEBUG,
+        _ => tracing::Level::TRACE,
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .with_target(false);
+    match format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+// La couleur dépend du préfixe du message, déjà présent dans tous les messages
+// (« ERREUR », « ATTENTION », « OK : ») : les scripts qui lisent la sortie voient le même texte.
+fn colorize(line: &str) -> String {
+    let content = line.trim_start_matches('\n');
+    // Préfixes des messages français et de leur traduction (voir messages).
+    if content.starts_with("ERREUR") || content.starts_with("ERROR") || content.starts_with("CRITICAL ERROR") {
+        line.red().bold().to_string()
+    } else if content.starts_with("ATTENTION") || content.starts_with("WARNING") {
+        line.yellow().to_string()
+    } else if content.starts_with("OK :") || content.starts_with("OK:") || content.starts_with("Opération terminée avec succès")
+        || content.starts_with("Operation completed successfully")
+    {
+        line.green().to_string()
+    } else {
+        line.to_string()
+    }
+}
+
+#[doc(hidden)]
+pub fn print_line(to_stderr: bool, args: fmt::Arguments) {
+    let to_stderr = match stdout_mode() {
+        _ if to_stderr => true,
+        StdoutMode::Normal => false,
+        StdoutMode::ToStderr => true,
+        StdoutMode::Quiet => return,
+    };
+    let line = colorize(&args.to_string());
+    // Comme println!, une sortie fermée (ex. `| head`) n'est pas une erreur du patcher.
+    let write = || {
+        let _ = if to_stderr {
+            writeln!(anstream::stderr().lock(), "{}", line)
+        } else {
+            writeln!(anstream::stdout().lock(), "{}", line)
+        };
+    };
+    let bars = PROGRESS_BARS.lock().unwrap_or_else(PoisonError::into_inner);
+    match bars.as_ref() {
+        Some(bars) => bars.suspend(write),
+        None => write(),
+    }
+}
+
+/// Remplace `println!` dans tout le crate : même syntaxe, avec couleurs selon --color.
+macro_rules! println {
+    () => {
+        $crate::output::print_line(false, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print_line(false, format_args!($($arg)*))
+    };
+}
+
+/// Remplace `eprintln!` dans tout le crate : même syntaxe, avec couleurs selon --color.
+macro_rules! eprintln {
+    () => {
+        $crate::output::print_line(true, format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print_line(true, format_args!($($arg)*))
+    };
+}
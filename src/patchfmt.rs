@@ -0,0 +1,126 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::{calculate_crc32, fsutil, messages};
+
+/// Formats de patch pris en charge par flips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchFormat {
+    Bps,
+    Ips,
+    Ups,
+}
+
+impl PatchFormat {
+    /// Déduit le format de l'extension du fichier (.bps, .ips, .ups).
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or("").to_lowercase();
+        match ext.as_str() {
+            "bps" => Ok(PatchFormat::Bps),
+            "ips" => Ok(PatchFormat::Ips),
+            "ups" => Ok(PatchFormat::Ups),
+            _ => Err(messages::patch_format_unknown(path).into()),
+        }
+    }
+}
+
+impl fmt::Display for PatchFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchFormat::Bps => write!(f, "BPS"),
+            PatchFormat::Ips => write!(f, "IPS"),
+            PatchFormat::Ups => write!(f, "UPS"),
+        }
+    }
+}
+
+/// Applique un patch au contenu `source` et renvoie le contenu modifié.
+pub fn apply_patch(format: PatchFormat, patch: &[u8], source: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let output = match format {
+        PatchFormat::Bps => flips::BpsPatch::new(patch).apply(source).map(|out| out.to_bytes()),
+        PatchFormat::Ips => flips::IpsPatch::new(patch).apply(source).map(|out| out.to_bytes()),
+        PatchFormat::Ups => flips::UpsPatch::new(patch).apply(source).map(|out| out.to_bytes()),
+    };
+    output.map_err(|e| messages::patch_apply_failed(&format, &e).into())
+}
+
+/// Crée un patch qui transforme `source` en `target`. flips ne sait pas créer de patch UPS.
+pub fn create_delta(format: PatchFormat, source: &[u8], target: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let output = match format {
+        PatchFormat::Bps => flips::BpsDeltaBuilder::new().source(source).target(target).build().map(|out| out.as_ref().to_vec()),
+        PatchFormat::Ips => flips::IpsBuilder::new().source(source).target(target).build().map(|out| out.as_ref().to_vec()),
+        PatchFormat::Ups => return Err(messages::ups_creation_unsupported().into()),
+    };
+    output.map_err(|e| messages::patch_create_failed(&format, &e).into())
+}
+
+/// Convertit le patch `from` en `to` (formats déduits des extensions) : le patch d'origine est
+/// appliqué à `source`, puis un nouveau delta est calculé entre `source` et le résultat.
+/// Les deux patchs sont ensuite réappliqués pour vérifier qu'ils produisent le même fichier.
+pub fn run_convert(from: &Path, to: &Path, source: &Path) -> Result<(), Box<dyn Error>> {
+    let from_format = PatchFormat::from_path(from)?;
+    let to_format = PatchFormat::from_path(to)?;
+    if to_format == PatchFormat::Ups {
+        return Err(messages::ups_creation_unsupported().into());
+    }
+    println!("{}", messages::convert_header(from, &from_format, to, &to_format));
+
+    let source_data = fs::read(source).map_err(|e| messages::source_read_failed(source, &e))?;
+    let from_data = fs::read(from).map_err(|e| messages::patch_read_failed(from, &e))?;
+
+    let target_data = apply_patch(from_format, &from_data, &source_data)?;
+    println!("{}", messages::convert_original_applied(target_data.len(), calculate_crc32(&target_data)));
+
+    let to_data = create_delta(to_format, &source_data, &target_data)?;
+
+    let check_data = apply_patch(to_format, &to_data, &source_data)?;
+    if check_data != target_data {
+        return Err(messages::convert_mismatch(calculate_crc32(&check_data), calculate_crc32(&target_data)).into());
+    }
+    println!("{}", messages::convert_same_output());
+
+    fsutil::write_atomic(to, &to_data)?;
+    println!("{}", messages::convert_written(&to_format, to, to_data.len(), from_data.len()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempDir;
+
+    #[test]
+    fn bps_to_ips_to_bps_round_trip() {
+        let dir = TempDir::new("convert_round_trip");
+        let source: Vec<u8> = (0..4096u32).map(|i| (i * 7 % 251) as u8).collect();
+        let mut target = source.clone();
+        target[10..20].copy_from_slice(b"DELTARUNE!");
+        target[3000] ^= 0xFF;
+        target.extend_from_slice(b"fin ajoutee");
+
+        let source_path = dir.path().join("data.win");
+        let original_path = dir.path().join("original.bps");
+        let ips_path = dir.path().join("converti.ips");
+        let bps_path = dir.path().join("reconverti.bps");
+        fs::write(&source_path, &source).unwrap();
+        fs::write(&original_path, create_delta(PatchFormat::Bps, &source, &target).unwrap()).unwrap();
+
+        run_convert(&original_path, &ips_path, &source_path).unwrap();
+        run_convert(&ips_path, &bps_path, &source_path).unwrap();
+
+        let from_ips = apply_patch(PatchFormat::Ips, &fs::read(&ips_path).unwrap(), &source).unwrap();
+        let from_bps = apply_patch(PatchFormat::Bps, &fs::read(&bps_path).unwrap(), &source).unwrap();
+        assert_eq!(from_ips, from_bps);
+        assert_eq!(from_bps, target);
+    }
+
+    #[test]
+    fn ups_output_is_refused() {
+        let dir = TempDir::new("convert_ups");
+        let result = run_convert(&dir.path().join("a.bps"), &dir.path().join("b.ups"), &dir.path().join("data.win"));
+        assert!(result.is_err());
+        assert!(!dir.path().join("b.ups").exists());
+    }
+}
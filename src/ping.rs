@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::time::Instant;
+
+use crate::{messages, net};
+
+/// Vérification légère pour les lanceurs : un HEAD vers l'index, qui doit répondre avec succès
+/// et un contenu JSON. Le corps n'est pas téléchargé. Renvoie une erreur si le service est indisponible.
+pub fn run_ping(index_url: &str) -> Result<(), Box<dyn Error>> {
+    let started = Instant::now();
+    let result = net::head(index_url);
+    let elapsed_ms = started.elapsed().as_millis();
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("{}", messages::ping_failed(index_url, elapsed_ms));
+            return Err(messages::ping_unavailable(&e).into());
+        }
+    };
+    let content_type = net::header_value(&response, reqwest::header::CONTENT_TYPE);
+    println!("{}", messages::ping_response(index_url, &response.status(), elapsed_ms, content_type));
+
+    // application/json, ou un type dérivé (application/vnd.xxx+json).
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    if mime != "application/json" && !mime.ends_with("+json") {
+        return Err(messages::ping_not_json(content_type).into());
+    }
+    println!("{}", messages::ping_ok());
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+
+use crate::messages;
+
+/// Processus du jeu en cours d'exécution.
+pub struct RunningGame {
+    pub pid: Pid,
+    pub name: String,
+}
+
+/// Cherche les processus du jeu : exécutable situé dans `game_dir`, ou nom contenant
+/// « deltarune » (cas de Proton/Wine, où l'exécutable est le chargeur de Wine).
+pub fn find_running_game(game_dir: &Path) -> Vec<RunningGame> {
+    let game_dir = fs::canonicalize(game_dir).unwrap_or_else(|_| game_dir.to_path_buf());
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let own_pid = sysinfo::get_current_pid().ok();
+    system
+        .processes()
+        .values()
+        .filter(|process| Some(process.pid()) != own_pid)
+        .filter(|process| {
+            let in_game_dir = process.exe().is_some_and(|exe| exe.starts_with(&game_dir));
+            let named_like_game = process.name().to_string_lossy().to_lowercase().contains("deltarune");
+            in_game_dir || named_like_game
+        })
+        .map(|process| RunningGame {
+            pid: process.pid(),
+            name: process.name().to_string_lossy().into_owned(),
+        })
+        .collect()
+}
+
+/// Cherche le client Steam, qui peut mettre le jeu à jour pendant que le patcher opère.
+pub fn find_running_steam() -> Vec<RunningGame> {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    system
+        .processes()
+        .values()
+        .filter(|process| {
+            let name = process.name().to_string_lossy().to_lowercase();
+            name == "steam" || name == "steam.exe"
+        })
+        .map(|process| RunningGame {
+            pid: process.pid(),
+            name: process.name().to_string_lossy().into_owned(),
+        })
+        .collect()
+}
+
+pub fn describe(games: &[RunningGame]) -> String {
+    games
+        .iter()
+        .map(|game| format!("{} (PID {})", game.name, game.pid))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Jeu suspendu (SIGSTOP) le temps du patch. Il n'est jamais repris : ses fichiers
+/// ont changé sous lui, il est donc tué quand la garde est libérée, que le patch ait réussi ou non.
+pub struct SuspendedGame {
+    pids: Vec<Pid>,
+}
+
+impl SuspendedGame {
+    pub fn suspend(games: &[RunningGame]) -> Result<Self, Box<dyn Error>> {
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+
+        let mut suspended = SuspendedGame { pids: Vec::new() };
+        for game in games {
+            let Some(process) = system.process(game.pid) else {
+                continue; // Fermé entre-temps.
+            };
+            match process.kill_with(Signal::Stop) {
+                Some(true) => {
+                    println!("{}", messages::process_suspended(&game.name, &game.pid));
+                    suspended.pids.push(game.pid);
+                }
+                Some(false) => {
+                    return Err(messages::process_suspend_failed(&game.name, &game.pid).into());
+                }
+                None => {
+                    return Err(messages::process_suspend_unsupported().into());
+                }
+            }
+        }
+        Ok(suspended)
+    }
+}
+
+impl Drop for SuspendedGame {
+    fn drop(&mut self) {
+        if self.pids.is_empty() {
+            return;
+        }
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::Some(&self.pids), true);
+        for pid in &self.pids {
+            match system.process(*pid) {
+                Some(process) if process.kill() => println!("{}", messages::process_killed(pid)),
+                Some(_) => eprintln!("{}", messages::process_kill_failed(pid)),
+                None => {}
+            }
+        }
+    }
+}
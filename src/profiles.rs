@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::{manifest, messages};
+
+/// Installation du jeu mémorisée sous un nom (une par utilisateur ou par copie du jeu).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Profile {
+    #[serde(rename = "gameDir")]
+    pub game_dir: PathBuf,
+
+    /// Plateforme de l'index à utiliser (full, demo...) ; détectée à chaque installation si absente.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+type Profiles = BTreeMap<String, Profile>;
+
+fn profiles_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("profiles.json")
+}
+
+/// Répertoire d'état propre au profil : ses manifestes et son historique ne se mêlent pas aux autres.
+pub fn profile_state_dir(state_dir: &Path, name: &str) -> PathBuf {
+    state_dir.join("profiles").join(name)
+}
+
+fn load_profiles(state_dir: &Path) -> Result<Profiles, Box<dyn Error>> {
+    let path = profiles_path(state_dir);
+    if !path.is_file() {
+        return Ok(Profiles::new());
+    }
+    let data = fs::read(&path)?;
+    Ok(serde_json::from_slice(&data).map_err(|e| messages::profiles_invalid(&path, &e))?)
+}
+
+fn save_profiles(state_dir: &Path, profiles: &Profiles) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(state_dir)?;
+    crate::fsutil::write_atomic(&profiles_path(state_dir), &serde_json::to_vec_pretty(profiles)?)
+}
+
+// Le nom sert de nom de répertoire : pas de séparateur ni de chemin relatif.
+fn validate_name(name: &str) -> Result<(), Box<dyn Error>> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(messages::profile_name_invalid(name).into());
+    }
+    Ok(())
+}
+
+/// Profils enregistrés pour cette plateforme, avec leur nom.
+pub fn profiles_for_platform(state_dir: &Path, platform: &str) -> Result<Vec<(String, Profile)>, Box<dyn Error>> {
+    Ok(load_profiles(state_dir)?
+        .into_iter()
+        .filter(|(_, profile)| profile.platform.as_deref() == Some(platform))
+        .collect())
+}
+
+/// Retrouve un profil par son nom.
+pub fn get_profile(state_dir: &Path, name: &str) -> Result<Profile, Box<dyn Error>> {
+    let profiles = load_profiles(state_dir)?;
+    profiles.get(name).cloned().ok_or_else(|| {
+        let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+        let known = if known.is_empty() { messages::profiles_none_known() } else { known.join(", ") };
+        messages::profile_unknown_hint(name, &known).into()
+    })
+}
+
+pub fn run_profile_list(state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let profiles = load_profiles(state_dir)?;
+    if profiles.is_empty() {
+        println!("{}", messages::profiles_empty());
+        return Ok(());
+    }
+
+    println!("{}", messages::profiles_header());
+    for (name, profile) in &profiles {
+        let state = match manifest::latest_manifest(&profile_state_dir(state_dir, name), &profile.game_dir)? {
+            Some((_, latest)) => messages::profile_state_installed(&latest.platform, latest.files.len()),
+            None => messages::profile_state_none(),
+        };
+        println!("\n{}", name);
+        println!("{}", messages::profile_game(&profile.game_dir));
+        println!("{}", messages::profile_platform(&profile.platform.clone().unwrap_or_else(messages::profile_platform_auto)));
+        println!("{}", messages::profile_state(&state));
+    }
+    Ok(())
+}
+
+pub fn run_profile_add(state_dir: &Path, name: &str, game_dir: &Path, platform: Option<String>) -> Result<(), Box<dyn Error>> {
+    validate_name(name)?;
+    let mut profiles = load_profiles(state_dir)?;
+    if profiles.contains_key(name) {
+        return Err(messages::profile_exists(name).into());
+    }
+    if !game_dir.is_dir() {
+        eprintln!("{}", messages::profile_game_dir_inaccessible(game_dir));
+    }
+
+    let game_dir = fs::canonicalize(game_dir).unwrap_or_else(|_| game_dir.to_path_buf());
+    println!("{}", messages::profile_created(name, &game_dir));
+    profiles.insert(name.to_string(), Profile { game_dir, platform });
+    save_profiles(state_dir, &profiles)
+}
+
+pub fn run_profile_remove(state_dir: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    let mut profiles = load_profiles(state_dir)?;
+    if profiles.remove(name).is_none() {
+        return Err(messages::profile_unknown(name).into());
+    }
+    save_profiles(state_dir, &profiles)?;
+
+    // L'historique est conservé : il reste utile pour désinstaller un patch encore en place.
+    println!("{}", messages::profile_removed(name, &profile_state_dir(state_dir, name)));
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use clap::ValueEnum;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::messages;
+
+/// Unités de la barre globale, réparties entre les phases selon leur poids.
+const GLOBAL_UNITS: u64 = 1000;
+
+/// Affichage de la progression de `install`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Une seule barre de 0 à 100 % couvrant toutes les phases
+    Global,
+    /// Une barre par phase (téléchargement, extraction, patchs, copie)
+    Detailed,
+    /// Aucune barre
+    None,
+    /// Lignes « PROGRESS <0.0 à 1.0> » sur la sortie standard pour un lanceur externe ;
+    /// tous les autres messages passent sur la sortie d'erreur
+    Machine,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Download,
+    Extraction,
+    Patch,
+    Extras,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Download, Phase::Extraction, Phase::Patch, Phase::Extras];
+
+    fn label(self) -> String {
+        match self {
+            Phase::Download => messages::phase_download(),
+            Phase::Extraction => messages::phase_extraction(),
+            Phase::Patch => messages::phase_patch(),
+            Phase::Extras => messages::phase_extras(),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Événement de progression transmis à l'observateur de `Progress`, indépendamment des barres :
+/// c'est l'interface prévue pour une interface graphique ou un autre outil qui piloterait
+/// l'installation, sans avoir à analyser la sortie texte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// Début d'une phase ; `total` vaut 0 si la taille est inconnue.
+    PhaseStarted { phase: Phase, total: u64 },
+    /// Avancement dans la phase en cours, en octets.
+    Advanced { phase: Phase, done: u64, total: u64 },
+    PhaseFinished { phase: Phase },
+    /// Fin de l'installation (toutes phases confondues).
+    Finished,
+}
+
+impl ProgressEvent {
+    /// Pourcentage de la phase en cours, s'il est connu.
+    pub fn percent(&self) -> Option<u8> {
+        match *self {
+            ProgressEvent::Advanced { done, total, .. } if total > 0 => Some((done.min(total) * 100 / total) as u8),
+            ProgressEvent::PhaseFinished { .. } | ProgressEvent::Finished => Some(100),
+            _ => None,
+        }
+    }
+}
+
+/// Progression de l'installation. Toutes les phases sont mesurées en octets.
+///
+/// En mode global, chaque phase reçoit à son démarrage une part des unités
+/// restantes proportionnelle à son poids : les poids peuvent donc être affinés
+/// en cours de route (taille réelle des extras après extraction, par exemple)
+/// sans que la barre ne recule jamais.
+pub struct Progress {
+    mode: ProgressMode,
+    multi: MultiProgress,
+    global_bar: Option<ProgressBar>,
+    phase_bar: Option<ProgressBar>,
+    /// Position dans la progression globale (mode global et machine), sur GLOBAL_UNITS.
+    global_position: u64,
+    /// Dernière valeur écrite en mode machine.
+    machine_emitted: Option<u64>,
+    weights: [u64; 4],
+    started: [bool; 4],
+    phase_start: u64,
+    phase_units: u64,
+    phase_len: u64,
+    phase_done: u64,
+    current: Option<Phase>,
+    observer: Option<Box<dyn Fn(ProgressEvent)>>,
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template)
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=> ")
+}
+
+impl Progress {
+    pub fn new(mode: ProgressMode) -> Self {
+        let multi = MultiProgress::new();
+        let global_bar = (mode == ProgressMode::Global).then(|| {
+            let bar = multi.add(ProgressBar::new(GLOBAL_UNITS));
+            bar.set_style(style("{msg:24} [{bar:40}] {percent:>3}%"));
+            bar
+        });
+        if matches!(mode, ProgressMode::Global | ProgressMode::Detailed) {
+            crate::output::set_progress_bars(Some(multi.clone()));
+        }
+        Progress {
+            mode,
+            multi,
+            global_bar,
+            phase_bar: None,
+            global_position: 0,
+            machine_emitted: None,
+            weights: [1; 4],
+            started: [false; 4],
+            phase_start: 0,
+            phase_units: 0,
+            phase_len: 0,
+            phase_done: 0,
+            current: None,
+            observer: None,
+        }
+    }
+
+    /// Reçoit chaque événement de progression, en plus des barres affichées selon le mode.
+    pub fn set_observer(&mut self, observer: impl Fn(ProgressEvent) + 'static) {
+        self.observer = Some(Box::new(observer));
+    }
+
+    fn notify(&self, event: ProgressEvent) {
+        if let Some(observer) = &self.observer {
+            observer(event);
+        }
+    }
+
+    /// Poids relatif d'une phase (en octets estimés) pour la barre globale.
+    pub fn set_weight(&mut self, phase: Phase, weight: u64) {
+        self.weights[phase.index()] = weight.max(1);
+    }
+
+    // Met à jour la progression globale : barre en mode global, ligne PROGRESS en mode machine
+    // à chaque centième (une mise à jour par bloc lu noierait le lanceur sous les lignes).
+    fn set_global_position(&mut self, position: u64) {
+        self.global_position = position.min(GLOBAL_UNITS);
+        if let Some(bar) = &self.global_bar {
+            bar.set_position(self.global_position);
+        }
+        if self.mode == ProgressMode::Machine {
+            let hundredths = self.global_position * 100 / GLOBAL_UNITS;
+            if self.machine_emitted.is_none_or(|emitted| hundredths > emitted) {
+                self.machine_emitted = Some(hundredths);
+                crate::output::machine_line(format_args!("PROGRESS {:.2}", hundredths as f64 / 100.0));
+            }
+        }
+    }
+
+    pub fn start_phase(&mut self, phase: Phase, len: u64) {
+        self.finish_phase();
+        self.phase_len = len;
+        self.phase_done = 0;
+
+        match self.mode {
+            ProgressMode::Global | ProgressMode::Machine => {
+                let position = self.global_position;
+                let remaining_weight: u64 = Phase::ALL
+                    .iter()
+                    .filter(|p| !self.started[p.index()])
+                    .map(|p| self.weights[p.index()])
+                    .sum();
+                let remaining_units = GLOBAL_UNITS.saturating_sub(position);
+                self.phase_start = position;
+                self.phase_units = remaining_units * self.weights[phase.index()] / remaining_weight.max(1);
+                if let Some(bar) = &self.global_bar {
+                    bar.set_message(phase.label());
+                }
+            }
+            ProgressMode::Detailed => {
+                // Longueur inconnue (serveur sans Content-Length) : compteur sans barre.
+                let bar = if len == 0 { ProgressBar::no_length() } else { ProgressBar::new(len) };
+                let bar = self.multi.add(bar);
+                bar.set_style(style("{msg:24} [{bar:40}] {bytes}/{total_bytes} ({eta})"));
+                bar.set_message(phase.label());
+                self.phase_bar = Some(bar);
+            }
+            ProgressMode::None => {}
+        }
+        self.started[phase.index()] = true;
+        self.current = Some(phase);
+        self.notify(ProgressEvent::PhaseStarted { phase, total: len });
+    }
+
+    pub fn advance(&mut self, bytes: u64) {
+        self.phase_done += bytes;
+        if let Some(bar) = &self.phase_bar {
+            bar.set_position(self.phase_done);
+        }
+        if matches!(self.mode, ProgressMode::Global | ProgressMode::Machine) {
+            let fraction_units = (self.phase_units * self.phase_done.min(self.phase_len))
+                .checked_div(self.phase_len)
+                .unwrap_or(0);
+            self.set_global_position(self.phase_start + fraction_units);
+        }
+        if let Some(phase) = self.current {
+            self.notify(ProgressEvent::Advanced { phase, done: self.phase_done, total: self.phase_len });
+        }
+    }
+
+    pub fn finish_phase(&mut self) {
+        if let Some(bar) = self.phase_bar.take() {
+            bar.finish();
+        }
+        if matches!(self.mode, ProgressMode::Global | ProgressMode::Machine) {
+            self.set_global_position(self.phase_start + self.phase_units);
+        }
+        self.phase_start += self.phase_units;
+        self.phase_units = 0;
+        if let Some(phase) = self.current.take() {
+            self.notify(ProgressEvent::PhaseFinished { phase });
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.finish_phase();
+        if matches!(self.mode, ProgressMode::Global | ProgressMode::Machine) {
+            self.set_global_position(GLOBAL_UNITS);
+        }
+        if let Some(bar) = &self.global_bar {
+            bar.finish();
+        }
+        self.notify(ProgressEvent::Finished);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if matches!(self.mode, ProgressMode::Global | ProgressMode::Detailed) {
+            crate::output::set_progress_bars(None);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use crate::messages;
+
+/// Bac à sable dans lequel le patcher peut tourner sous Linux.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sandbox {
+    Flatpak,
+    Snap,
+}
+
+/// Détecte un lancement depuis Flatpak ou Snap, où l'accès aux fichiers de l'hôte est restreint.
+pub fn detect() -> Option<Sandbox> {
+    if std::env::var_os("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").exists() {
+        Some(Sandbox::Flatpak)
+    } else if std::env::var_os("SNAP_NAME").is_some() || std::env::var_os("SNAP").is_some() {
+        Some(Sandbox::Snap)
+    } else {
+        None
+    }
+}
+
+/// Explique comment donner au bac à sable l'accès à `path`.
+pub fn access_hint(sandbox: Sandbox, path: &Path) -> String {
+    match sandbox {
+        Sandbox::Flatpak => {
+            let app_id = std::env::var("FLATPAK_ID").unwrap_or_else(|_| messages::sandbox_flatpak_app_id());
+            messages::sandbox_flatpak_hint(&path.display(), &app_id)
+        }
+        Sandbox::Snap => messages::sandbox_snap_hint(),
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::{Component, Path};
+use ignore::gitignore::Gitignore;
+use serde::{Deserialize, Serialize};
+
+use crate::{index_style_path, is_patch_metadata, messages, PatchDetail};
+
+/// Fichier de la racine du jeu listant, en syntaxe gitignore, les fichiers que le patch ne doit
+/// ni patcher ni remplacer (mods de l'utilisateur).
+pub const PATCHIGNORE_NAME: &str = ".patchignore";
+
+/// Fichiers du patch retenus par les options de `install` (--chapter, --patches-only, --variant)
+/// et par le .patchignore du jeu.
+#[derive(Default, Clone)]
+pub struct Selection {
+    /// Chapitres à patcher ; vide pour tous.
+    pub chapters: Vec<u32>,
+    /// N'installe que les patchs, sans copier les fichiers supplémentaires.
+    pub patches_only: bool,
+    /// Variante demandée (--variant) ; la variante principale de l'index si absente.
+    pub variant: Option<String>,
+    /// Fichiers supplémentaires propres aux variantes non retenues, au format de l'index.
+    excluded_extras: HashSet<String>,
+    /// Motifs du .patchignore du jeu, s'il en a un.
+    patchignore: Option<Gitignore>,
+}
+
+/// Variante des fichiers supplémentaires déclarée par l'index (polices, images...), par exemple
+/// « fr-FR » et « fr-CA ». Les fichiers supplémentaires absents de toutes les variantes sont communs.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Variant {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Fichiers supplémentaires propres à cette variante, chemins relatifs de l'archive.
+    pub files: Vec<String>,
+}
+
+// Numéro de chapitre d'un chemin relatif au jeu (chapter2_windows/... -> 2).
+fn chapter_of(relative_path: &Path) -> Option<u32> {
+    let Some(Component::Normal(first)) = relative_path.components().next() else {
+        return None;
+    };
+    let digits: String = first.to_str()?.strip_prefix("chapter")?.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+impl Selection {
+    pub fn new(chapters: Vec<u32>, patches_only: bool, variant: Option<String>) -> Self {
+        Selection { chapters, patches_only, variant, excluded_extras: HashSet::new(), patchignore: None }
+    }
+
+    // Les fichiers hors des répertoires de chapitre (racine du jeu) sont toujours retenus.
+    fn in_selected_chapters(&self, relative_path: &Path) -> bool {
+        self.chapters.is_empty() || chapter_of(relative_path).is_none_or(|chapter| self.chapters.contains(&chapter))
+    }
+
+    fn matches_patchignore(&self, relative_path: &Path) -> bool {
+        self.patchignore
+            .as_ref()
+            .is_some_and(|patchignore| patchignore.matched_path_or_any_parents(relative_path, false).is_ignore())
+    }
+
+    // Fichier supplémentaire retenu par les options, avant le .patchignore : il est extrait dans tous
+    // les cas, pour que les fichiers écartés par le .patchignore puissent être listés.
+    fn keeps_extra_entry(&self, relative_path: &Path) -> bool {
+        !self.patches_only
+            && self.in_selected_chapters(relative_path)
+            && !self.excluded_extras.contains(&index_style_path(relative_path))
+    }
+
+    pub fn keeps_patch(&self, detail: &PatchDetail) -> bool {
+        let source_path = Path::new(&detail.source_path);
+        self.in_selected_chapters(source_path) && !self.matches_patchignore(source_path)
+    }
+
+    pub fn keeps_extra(&self, relative_path: &Path) -> bool {
+        self.keeps_extra_entry(relative_path) && !self.matches_patchignore(relative_path)
+    }
+
+    /// Patch que les options retiendraient, mais dont le .patchignore protège le fichier source.
+    pub fn patchignores_patch(&self, detail: &PatchDetail) -> bool {
+        let source_path = Path::new(&detail.source_path);
+        self.in_selected_chapters(source_path) && self.matches_patchignore(source_path)
+    }
+
+    /// Fichier supplémentaire que les options retiendraient, mais que le .patchignore protège.
+    pub fn patchignores_extra(&self, relative_path: &Path) -> bool {
+        self.keeps_extra_entry(relative_path) && self.matches_patchignore(relative_path)
+    }
+
+    /// Lit le .patchignore à la racine du jeu, s'il existe. Un motif invalide est une erreur :
+    /// mieux vaut s'arrêter que d'écraser un fichier que l'utilisateur voulait protéger.
+    pub fn load_patchignore(&mut self, game_dir: &Path) -> Result<(), Box<dyn Error>> {
+        let path = game_dir.join(PATCHIGNORE_NAME);
+        if !path.is_file() {
+            return Ok(());
+        }
+        let (patchignore, error) = Gitignore::new(&path);
+        if let Some(e) = error {
+            return Err(messages::patchignore_invalid(&path, &e).into());
+        }
+        println!("{}", messages::patchignore_loaded(&path, patchignore.len()));
+        self.patchignore = Some(patchignore);
+        Ok(())
+    }
+
+    /// Résout la variante demandée parmi celles de l'index (la première, dite principale, par défaut)
+    /// et écarte les fichiers propres aux autres, qui ne seront ni extraits ni copiés.
+    pub fn resolve_variant(&self, variants: &[Variant]) -> Result<Selection, Box<dyn Error>> {
+        let mut selection = self.clone();
+        let Some(main) = variants.first() else {
+            return match &self.variant {
+                Some(requested) => Err(messages::no_variants(requested).into()),
+                None => Ok(selection),
+            };
+        };
+        let chosen = match &self.variant {
+            None => main,
+            Some(requested) => variants.iter().find(|v| v.name == *requested).ok_or_else(|| {
+                let available: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+                messages::unknown_variant(requested, &available.join(", "))
+            })?,
+        };
+        println!("{}", messages::variant_selected(&chosen.name, chosen.description.as_deref().unwrap_or("")));
+        selection.excluded_extras = variants
+            .iter()
+            .filter(|v| v.name != chosen.name)
+            .flat_map(|v| &v.files)
+            // Un fichier partagé par plusieurs variantes reste installé s'il fait partie de la variante retenue.
+            .filter(|path| !chosen.files.contains(path))
+            .map(|path| path.trim_start_matches("./").to_string())
+            .collect();
+        Ok(selection)
+    }
+
+    /// Entrée de l'archive à extraire : métadonnées, patchs retenus (`patch_paths`)
+    /// et fichiers supplémentaires non exclus.
+    pub fn keeps_entry(&self, relative_path: &Path, patch_paths: &HashSet<&str>) -> bool {
+        if is_patch_metadata(relative_path) {
+            return true;
+        }
+        if relative_path.extension().is_some_and(|ext| ext == "bps") {
+            return patch_paths.contains(index_style_path(relative_path).as_str());
+        }
+        self.keeps_extra_entry(relative_path)
+    }
+}
@@ -0,0 +1,303 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use serde_json::json;
+
+use crate::messages;
+
+/// Opération exposée par le serveur : une sous-commande du patcher, lancée dans un processus à part.
+struct Endpoint {
+    path: &'static str,
+    method: &'static str,
+    subcommand: &'static str,
+    /// Paramètres de la requête acceptés, transmis comme options de même nom (game_dir -> --game-dir).
+    params: &'static [&'static str],
+    /// Arguments ajoutés d'office : pas de question possible, progression lisible par le serveur.
+    extra_args: &'static [&'static str],
+}
+
+const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        path: "/install",
+        method: "POST",
+        subcommand: "install",
+        params: &["game_dir", "profile", "platform", "patch_version", "chapter"],
+        extra_args: &["--yes", "--progress", "machine"],
+    },
+    Endpoint { path: "/status", method: "GET", subcommand: "status", params: &["game_dir", "profile"], extra_args: &[] },
+    Endpoint { path: "/verify", method: "GET", subcommand: "verify", params: &["game_dir", "profile", "platform"], extra_args: &[] },
+    Endpoint { path: "/audit", method: "GET", subcommand: "audit", params: &["game_dir", "profile"], extra_args: &[] },
+    Endpoint { path: "/verify-backups", method: "GET", subcommand: "verify-backups", params: &["game_dir", "profile"], extra_args: &[] },
+    Endpoint { path: "/versions", method: "GET", subcommand: "versions", params: &[], extra_args: &[] },
+    Endpoint { path: "/ping", method: "GET", subcommand: "ping", params: &[], extra_args: &[] },
+];
+
+/// Requête HTTP réduite à ce dont le serveur a besoin.
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    origin: Option<String>,
+}
+
+/// Sortie du processus d'une opération, transmise au client au fil de l'eau.
+enum ChildOutput {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Serveur HTTP local pour un lanceur : chaque endpoint lance la sous-commande correspondante et
+/// renvoie sa sortie en Server-Sent Events (log, progress), puis un événement done avec son résultat.
+/// N'écoute que sur 127.0.0.1. `base_args` sont les options globales transmises à chaque sous-commande.
+pub fn run_serve(port: u16, base_args: Vec<OsString>) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| messages::serve_bind_failed(port, &e))?;
+    println!("{}", messages::serve_listening(port));
+    for endpoint in ENDPOINTS {
+        println!("  {} {}", endpoint.method, endpoint.path);
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("{}", messages::serve_connection_refused(&e));
+                continue;
+            }
+        };
+        let base_args = base_args.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &base_args) {
+                tracing::debug!("Connexion interrompue : {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, base_args: &[OsString]) -> Result<(), Box<dyn Error>> {
+    let request = match read_request(&stream) {
+        Ok(request) => request,
+        Err(e) => return send_json(&mut stream, "400 Bad Request", None, &json!({ "error": e.to_string() })),
+    };
+    tracing::info!("{} {}", request.method, request.path);
+
+    // Seule une page servie depuis la machine elle-même peut piloter le patcher : une requête
+    // envoyée par un site quelconque ouvert dans le navigateur porte son origine et est refusée.
+    let origin = request.origin.as_deref();
+    if origin.is_some_and(|origin| !is_local_origin(origin)) {
+        return send_json(&mut stream, "403 Forbidden", None, &json!({ "error": messages::serve_origin_refused() }));
+    }
+
+    if request.path == "/" {
+        let endpoints: Vec<_> = ENDPOINTS
+            .iter()
+            .map(|endpoint| json!({ "method": endpoint.method, "path": endpoint.path, "params": endpoint.params }))
+            .collect();
+        return send_json(&mut stream, "200 OK", origin, &json!({ "version": env!("CARGO_PKG_VERSION"), "endpoints": endpoints }));
+    }
+    let Some(endpoint) = ENDPOINTS.iter().find(|endpoint| endpoint.path == request.path) else {
+        return send_json(&mut stream, "404 Not Found", origin, &json!({ "error": messages::serve_unknown_endpoint(&request.path) }));
+    };
+    if request.method != endpoint.method {
+        return send_json(&mut stream, "405 Method Not Allowed", origin, &json!({ "error": messages::serve_wrong_method(endpoint.path, endpoint.method) }));
+    }
+
+    match endpoint_args(endpoint, &request.query, base_args) {
+        Ok(args) => run_endpoint(&mut stream, origin, &args),
+        Err(e) => send_json(&mut stream, "400 Bad Request", origin, &json!({ "error": e })),
+    }
+}
+
+/// Arguments de la sous-commande d'un endpoint. Chaque paramètre devient une seule option
+/// `--nom=valeur` : une valeur commençant par un tiret ne peut pas être prise pour une option.
+fn endpoint_args(endpoint: &Endpoint, query: &[(String, String)], base_args: &[OsString]) -> Result<Vec<OsString>, String> {
+    let mut args = base_args.to_vec();
+    args.push(endpoint.subcommand.into());
+    for (name, value) in query {
+        if !endpoint.params.contains(&name.as_str()) {
+            return Err(messages::serve_unknown_param(endpoint.path, name));
+        }
+        args.push(format!("--{}={}", name.replace('_', "-"), value).into());
+    }
+    args.extend(endpoint.extra_args.iter().map(OsString::from));
+    Ok(args)
+}
+
+/// Lance la sous-commande et relaie sa sortie au client. Si le client se déconnecte, l'opération
+/// continue jusqu'au bout : interrompre une installation en cours laisserait le jeu à moitié patché.
+fn run_endpoint(stream: &mut TcpStream, origin: Option<&str>, args: &[OsString]) -> Result<(), Box<dyn Error>> {
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(args)
+        .arg("--color")
+        .arg("never")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| messages::serve_spawn_failed(&e))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let readers = [
+        child.stdout.take().map(|out| spawn_reader(out, sender.clone(), ChildOutput::Stdout)),
+        child.stderr.take().map(|err| spawn_reader(err, sender, ChildOutput::Stderr)),
+    ];
+
+    let mut connected = write_head(stream, "200 OK", "text/event-stream", origin).is_ok();
+    for output in receiver {
+        if !connected {
+            continue;
+        }
+        let (event, data) = match output {
+            ChildOutput::Stdout(line) => match line.strip_prefix("PROGRESS ").and_then(|value| value.trim().parse::<f64>().ok()) {
+                Some(progress) => ("progress", json!({ "progress": progress })),
+                None => ("log", json!({ "stream": "stdout", "line": line })),
+            },
+            ChildOutput::Stderr(line) => ("log", json!({ "stream": "stderr", "line": line })),
+        };
+        connected = send_event(stream, event, &data).is_ok();
+    }
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+
+    let status = child.wait()?;
+    if connected {
+        send_event(stream, "done", &json!({ "success": status.success(), "code": status.code() }))?;
+    }
+    Ok(())
+}
+
+fn spawn_reader(
+    output: impl Read + Send + 'static,
+    sender: mpsc::Sender<ChildOutput>,
+    wrap: fn(String) -> ChildOutput,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            if sender.send(wrap(line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, Box<dyn Error>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(messages::serve_bad_request_line().into());
+    };
+
+    let mut origin = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("origin")
+        {
+            origin = Some(value.trim().to_string());
+        }
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((percent_decode(name)?, percent_decode(value)?))
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+    Ok(Request { method: method.to_string(), path: path.to_string(), query, origin })
+}
+
+// Décodage des paramètres d'URL (%XX et + pour l'espace).
+fn percent_decode(value: &str) -> Result<String, Box<dyn Error>> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                };
+                bytes.push(decoded.ok_or_else(|| messages::serve_bad_encoding(value))?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    Ok(String::from_utf8(bytes).map_err(|_| messages::serve_not_utf8(value))?)
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    let host = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .unwrap_or(origin);
+    let host = host.rsplit_once(':').map_or(host, |(host, _port)| host);
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+fn write_head(stream: &mut TcpStream, status: &str, content_type: &str, origin: Option<&str>) -> std::io::Result<()> {
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n", status, content_type)?;
+    if let Some(origin) = origin {
+        write!(stream, "Access-Control-Allow-Origin: {}\r\n", origin)?;
+    }
+    write!(stream, "\r\n")?;
+    stream.flush()
+}
+
+fn send_json(stream: &mut TcpStream, status: &str, origin: Option<&str>, body: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+    write_head(stream, status, "application/json; charset=utf-8", origin)?;
+    stream.write_all(body.to_string().as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn send_event(stream: &mut TcpStream, event: &str, data: &serde_json::Value) -> std::io::Result<()> {
+    write!(stream, "event: {}\ndata: {}\n\n", event, data)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Args, Command};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn hyphen_leading_value_stays_a_value() {
+        let status = ENDPOINTS.iter().find(|endpoint| endpoint.path == "/status").unwrap();
+        let query = [("game_dir".to_string(), "-jeu".to_string())];
+        let args = endpoint_args(status, &query, &[]).unwrap();
+
+        let parsed = Args::try_parse_from(std::iter::once(OsString::from("patcher")).chain(args)).unwrap();
+        match parsed.command {
+            Some(Command::Status { game_dir, .. }) => assert_eq!(game_dir.as_deref(), Some(Path::new("-jeu"))),
+            other => panic!("commande inattendue : {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_param_is_refused() {
+        let versions = ENDPOINTS.iter().find(|endpoint| endpoint.path == "/versions").unwrap();
+        let query = [("game_dir".to_string(), "jeu".to_string())];
+        assert!(endpoint_args(versions, &query, &[]).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::{gamemaker, messages};
+
+/// Longueur minimale d'une suite de caractères imprimables pour être retenue comme texte,
+/// comme l'outil `strings`.
+const MIN_PRINTABLE_LEN: usize = 4;
+
+// Repli pour un fichier qui n'est pas au format GameMaker : suites de caractères imprimables.
+fn printable_strings(data: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(data)
+        .split(|c: char| c.is_control() || c == char::REPLACEMENT_CHARACTER)
+        .filter(|text| text.chars().count() >= MIN_PRINTABLE_LEN)
+        .map(str::to_string)
+        .collect()
+}
+
+fn print_change(index: Option<usize>, before: Option<&str>, after: Option<&str>) {
+    match index {
+        Some(index) => println!("\n#{}", index),
+        None => println!(),
+    }
+    if let Some(before) = before {
+        println!("- {:?}", before);
+    }
+    if let Some(after) = after {
+        println!("+ {:?}", after);
+    }
+}
+
+/// Applique `patch` à `source` en mémoire (rien n'est écrit) et affiche les chaînes modifiées.
+/// Pour un fichier GameMaker, les chaînes du chunk STRG sont comparées une à une (le patch de
+/// traduction garde leur ordre) ; sinon, on compare les suites de caractères imprimables.
+pub fn run_show_changes(source: &Path, patch: &Path) -> Result<(), Box<dyn Error>> {
+    let source_data = fs::read(source).map_err(|e| messages::read_failed(source, &e))?;
+    let patch_data = fs::read(patch).map_err(|e| messages::read_failed(patch, &e))?;
+    let patched_data = flips::BpsPatch::new(patch_data)
+        .apply(source_data.clone())
+        .map_err(|e| messages::bps_apply_failed(&e))?
+        .to_bytes();
+
+    let mut changed = 0;
+    match (gamemaker::strings(&source_data), gamemaker::strings(&patched_data)) {
+        (Ok(before), Ok(after)) => {
+            println!("{}", messages::strings_count(before.len(), after.len()));
+            for index in 0..before.len().max(after.len()) {
+                let (old, new) = (before.get(index), after.get(index));
+                if old != new {
+                    print_change(Some(index), old.map(String::as_str), new.map(String::as_str));
+                    changed += 1;
+                }
+            }
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            println!("{}", messages::strings_fallback(&e));
+            let before = printable_strings(&source_data);
+            let after = printable_strings(&patched_data);
+            let before_set: HashSet<&String> = before.iter().collect();
+            let after_set: HashSet<&String> = after.iter().collect();
+            for text in before.iter().filter(|text| !after_set.contains(text)) {
+                print_change(None, Some(text), None);
+                changed += 1;
+            }
+            for text in after.iter().filter(|text| !before_set.contains(text)) {
+                print_change(None, None, Some(text));
+                changed += 1;
+            }
+        }
+    }
+
+    println!("{}", messages::strings_changed(changed, patch));
+    Ok(())
+}
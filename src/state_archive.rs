@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::{self, InstallManifest};
+use crate::{archive, calculate_crc32_stream, fsutil, messages};
+
+/// Version du format de l'archive d'état : un patcher refuse les archives d'un format plus récent.
+const STATE_FORMAT_VERSION: u32 = 1;
+const STATE_INFO_NAME: &str = "state.json";
+const MANIFESTS_DIR: &str = "manifests";
+const BACKUPS_DIR: &str = "backups";
+
+/// Description de l'archive d'état, en tête de l'archive.
+#[derive(Serialize, Deserialize, Debug)]
+struct StateInfo {
+    #[serde(rename = "formatVersion")]
+    format_version: u32,
+
+    /// Version du patcher qui a exporté l'état (informative).
+    #[serde(rename = "patcherVersion")]
+    patcher_version: String,
+
+    /// Plateforme de la dernière installation.
+    platform: String,
+
+    /// CRC32 de chaque sauvegarde, par chemin relatif au répertoire du jeu.
+    backups: BTreeMap<String, u32>,
+}
+
+fn append_json(builder: &mut tar::Builder<impl Write>, name: &str, value: &impl Serialize) -> Result<(), Box<dyn Error>> {
+    let data = serde_json::to_vec_pretty(value)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, name, data.as_slice())?;
+    Ok(())
+}
+
+/// Empaquette l'état d'installation de `game_dir` (manifestes et sauvegardes) dans une archive
+/// tar portable, pour migrer l'installation ou la joindre à une demande d'aide.
+pub fn run_export_state(game_dir: &Path, state_dir: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let manifests = manifest::list_manifests(state_dir, game_dir)?;
+    let Some((_, latest)) = manifests.last() else {
+        return Err(messages::export_nothing(game_dir).into());
+    };
+
+    let mut backups = BTreeMap::new();
+    for entry in manifests.iter().flat_map(|(_, m)| &m.files) {
+        let Some(backup) = &entry.backup else { continue };
+        let backup_path = game_dir.join(backup);
+        match File::open(&backup_path) {
+            Ok(file) => {
+                backups.insert(crate::index_style_path(backup), calculate_crc32_stream(file)?);
+            }
+            Err(e) => eprintln!("{}", messages::export_backup_unreadable(&backup_path, &e)),
+        }
+    }
+
+    let info = StateInfo {
+        format_version: STATE_FORMAT_VERSION,
+        patcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: latest.platform.clone(),
+        backups,
+    };
+
+    let file = File::create(output).map_err(|e| messages::create_failed(output, &e))?;
+    let mut builder = tar::Builder::new(BufWriter::new(file));
+    append_json(&mut builder, STATE_INFO_NAME, &info)?;
+    for (i, (_, manifest)) in manifests.iter().enumerate() {
+        append_json(&mut builder, &format!("{}/{:03}.json", MANIFESTS_DIR, i), manifest)?;
+    }
+    for backup in info.backups.keys() {
+        println!("{}", messages::export_adding_backup(backup));
+        builder.append_path_with_name(game_dir.join(backup), format!("{}/{}", BACKUPS_DIR, backup))?;
+    }
+    builder.into_inner()?.flush()?;
+
+    println!("{}", messages::export_done(output, manifests.len(), info.backups.len()));
+    Ok(())
+}
+
+// Chemin d'une entrée de l'archive d'état, refusé s'il sort de son répertoire.
+fn entry_path(entry: &tar::Entry<'_, impl Read>) -> Result<(String, PathBuf), Box<dyn Error>> {
+    let name = entry.path()?.to_string_lossy().replace('\\', "/");
+    let path = archive::enclosed_path(&name)
+        .ok_or_else(|| messages::state_dangerous_path(&name))?;
+    Ok((name, path))
+}
+
+fn open_state(input: &Path) -> Result<tar::Archive<File>, Box<dyn Error>> {
+    let file = File::open(input).map_err(|e| messages::open_failed(input, &e))?;
+    Ok(tar::Archive::new(file))
+}
+
+/// Restaure dans `game_dir` un état exporté par export-state : sauvegardes (CRC vérifiés)
+/// et manifestes, rattachés à ce répertoire. Tout est vérifié avant la moindre écriture.
+pub fn run_import_state(game_dir: &Path, state_dir: &Path, input: &Path) -> Result<(), Box<dyn Error>> {
+    // Premier passage, en lecture seule : format, CRC des sauvegardes et conflits.
+    let mut info: Option<StateInfo> = None;
+    let mut manifests: Vec<InstallManifest> = Vec::new();
+    let mut verified = 0;
+    for entry in open_state(input)?.entries()? {
+        let mut entry = entry?;
+        let (name, relative_path) = entry_path(&entry)?;
+        if name == STATE_INFO_NAME {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            let state_info: StateInfo = serde_json::from_slice(&data)
+                .map_err(|e| messages::state_info_invalid(STATE_INFO_NAME, input, &e))?;
+            if state_info.format_version > STATE_FORMAT_VERSION {
+                return Err(messages::state_format_too_recent(
+                    &state_info.patcher_version, state_info.format_version, env!("CARGO_PKG_VERSION"), STATE_FORMAT_VERSION
+                ).into());
+            }
+            info = Some(state_info);
+        } else if name.starts_with(MANIFESTS_DIR) {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            manifests.push(serde_json::from_slice(&data).map_err(|e| messages::state_manifest_invalid(&name, &e))?);
+        } else if let Some(backup) = name.strip_prefix(BACKUPS_DIR).and_then(|n| n.strip_prefix('/')) {
+            let info = info.as_ref().ok_or_else(|| messages::state_info_not_first(STATE_INFO_NAME, input))?;
+            let expected = *info.backups.get(backup)
+                .ok_or_else(|| messages::state_backup_undeclared(backup, STATE_INFO_NAME))?;
+            let actual = calculate_crc32_stream(&mut entry)?;
+            if actual != expected {
+                return Err(messages::state_backup_corrupted(backup, actual, expected).into());
+            }
+            let target = game_dir.join(relative_path.strip_prefix(BACKUPS_DIR)?);
+            if let Ok(existing) = File::open(&target)
+                && calculate_crc32_stream(existing)? != expected
+            {
+                return Err(messages::state_backup_conflict(&target).into());
+            }
+            verified += 1;
+        }
+    }
+    let info = info.ok_or_else(|| messages::state_not_archive(input, STATE_INFO_NAME))?;
+    if verified != info.backups.len() {
+        return Err(messages::state_incomplete(verified, info.backups.len()).into());
+    }
+    println!("{}", messages::state_backups_verified(verified, &info.platform));
+
+    // Second passage : écriture.
+    for entry in open_state(input)?.entries()? {
+        let mut entry = entry?;
+        let (_, relative_path) = entry_path(&entry)?;
+        let Ok(backup) = relative_path.strip_prefix(BACKUPS_DIR) else { continue };
+        let target = game_dir.join(backup);
+        if target.exists() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = fsutil::temp_path_for(&target);
+        let result = File::create(&temp_path)
+            .and_then(|mut file| std::io::copy(&mut entry, &mut file))
+            .and_then(|_| fsutil::move_file(&temp_path, &target));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(messages::write_failed(&target, &e).into());
+        }
+        println!("{}", messages::state_backup_restored(&target));
+    }
+    for manifest in manifests {
+        manifest::import_manifest(state_dir, manifest, game_dir)?;
+    }
+
+    println!("{}", messages::state_imported(game_dir));
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::path::Path;
+
+use crate::audit::{self, FileState};
+use crate::{manifest, messages};
+
+/// Date UTC (AAAA-MM-JJ HH:MM) d'un horodatage Unix en secondes.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let minutes = timestamp % 86_400 / 60;
+
+    // Conversion jours -> date du calendrier grégorien (algorithme de H. Hinnant).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes / 60, minutes % 60)
+}
+
+/// Indique si le patch est installé dans ce répertoire, en quelle version, depuis quand, et si des
+/// fichiers qu'il a installés ont changé depuis (même contrôle que audit, en résumé).
+pub fn run_status(game_dir: &Path, state_dir: &Path) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::status_header());
+    println!("{}", messages::status_game(game_dir));
+
+    if let Some(pending) = manifest::load_pending(state_dir, game_dir) {
+        println!("{}", messages::status_pending(&format_timestamp(pending.installed_at), pending.files.len()));
+    }
+
+    let Some((_, install_manifest)) = manifest::latest_manifest(state_dir, game_dir)? else {
+        println!("{}", messages::status_not_installed());
+        return Ok(());
+    };
+    println!("{}", messages::status_installed(&install_manifest.platform));
+    match &install_manifest.patch_version {
+        Some(version) => println!("{}", messages::status_version(version)),
+        None => println!("{}", messages::status_version_latest()),
+    }
+    println!("{}", messages::status_installed_at(&format_timestamp(install_manifest.installed_at)));
+
+    let mut modified = Vec::new();
+    let mut unknown = 0;
+    for entry in &install_manifest.files {
+        match audit::file_state(game_dir, entry)? {
+            FileState::Intact => {}
+            FileState::Unknown => unknown += 1,
+            FileState::Missing => modified.push(messages::status_file_missing(&entry.path)),
+            FileState::Reverted => modified.push(messages::status_file_reverted(&entry.path)),
+            FileState::Corrupted(_) => modified.push(messages::status_file_modified(&entry.path)),
+        }
+    }
+
+    println!("{}", messages::status_file_count(install_manifest.files.len()));
+    if modified.is_empty() {
+        if unknown > 0 {
+            println!("{}", messages::status_unknown(unknown));
+        } else {
+            println!("{}", messages::status_unmodified());
+        }
+    } else {
+        println!("{}", messages::status_modified(modified.len()));
+        for line in &modified {
+            println!("  {}", line);
+        }
+        println!("{}", messages::status_modified_advice());
+    }
+    Ok(())
+}
@@ -0,0 +1,462 @@
+//! Tests des fonctions d'installation et de désinstallation définies à la racine du crate.
+
+use super::*;
+use crate::testutil::{bps_patch, TempDir};
+use std::io::{Seek, SeekFrom, Write};
+
+/// Répertoire du jeu et répertoire d'état d'un test.
+struct Setup {
+    game: TempDir,
+    state: TempDir,
+}
+
+impl Setup {
+    fn new(name: &str) -> Self {
+        Setup { game: TempDir::new(&format!("{}_game", name)), state: TempDir::new(&format!("{}_state", name)) }
+    }
+
+    fn read(&self, relative_path: &str) -> Option<Vec<u8>> {
+        fs::read(self.game.path().join(relative_path)).ok()
+    }
+
+    fn uninstall(&self) -> Result<(), Box<dyn Error>> {
+        run_uninstall_process(self.game.path(), self.state.path(), false, true)
+    }
+}
+
+// Vérification du CRC, sauvegarde, application du patch et restauration, comme pour un data.win.
+fn patch_and_restore(source_path: &str, original: &[u8], patched: &[u8]) {
+    let setup = Setup::new("non_win");
+    let extract = TempDir::new("non_win_extract");
+    let source_file_path = setup.game.write(source_path, original);
+    let patch_file_path = extract.write("patch.bps", &bps_patch(original, patched));
+    let detail = PatchDetail { patch_path: "patch.bps".to_string(), source_path: source_path.to_string(), ..Default::default() };
+
+    assert!(select_patch_candidate(&source_file_path, &[&detail], extract.path()).is_ok());
+    assert!(can_apply_bps(&source_file_path, &patch_file_path).unwrap());
+    let backup_path = backup::create_linked_backup(&source_file_path).unwrap();
+    assert_eq!(backup_path, setup.game.path().join(format!("{}.bak", source_path)));
+    apply_bps(&source_file_path, &patch_file_path, &source_file_path).unwrap();
+    assert_eq!(setup.read(source_path).unwrap(), patched);
+    assert_eq!(fs::read(&backup_path).unwrap(), original);
+
+    setup.uninstall().unwrap();
+    assert_eq!(setup.read(source_path).unwrap(), original);
+    assert!(!backup_path.exists());
+}
+
+#[test]
+fn patches_executable_without_extension() {
+    patch_and_restore("DELTARUNE", b"\x7fELF executable d'origine", b"\x7fELF executable traduit, plus long");
+}
+
+#[test]
+fn patches_small_file_in_subdirectory() {
+    patch_and_restore("lang/fr.json", b"{}", b"{\"ok\":1}");
+}
+
+#[cfg(unix)]
+#[test]
+fn patched_executable_keeps_its_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let setup = Setup::new("exec_bit");
+    let extract = TempDir::new("exec_bit_extract");
+    let exe_path = setup.game.write("DELTARUNE", b"original");
+    fs::set_permissions(&exe_path, fs::Permissions::from_mode(0o755)).unwrap();
+    let patch_file_path = extract.write("DELTARUNE.bps", &bps_patch(b"original", b"patched"));
+
+    apply_bps(&exe_path, &patch_file_path, &exe_path).unwrap();
+    assert_eq!(fs::metadata(&exe_path).unwrap().permissions().mode() & 0o777, 0o755);
+}
+
+fn set_modified(path: &Path, seconds_ago: u64) {
+    let time = std::time::SystemTime::now() - std::time::Duration::from_secs(seconds_ago);
+    File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+}
+
+#[test]
+fn uninstall_restores_oldest_of_duplicate_backups() {
+    let setup = Setup::new("duplicate_backups");
+    setup.game.write("DELTARUNE", b"patched twice");
+    // DELTARUNE..bak (ancien nommage) vient avant DELTARUNE.bak dans l'ordre alphabétique :
+    // seule la date doit désigner la sauvegarde de la version d'origine.
+    let oldest = setup.game.write("DELTARUNE.bak", b"original");
+    let newest = setup.game.write("DELTARUNE..bak", b"patched once");
+    set_modified(&oldest, 3600);
+    set_modified(&newest, 60);
+
+    let original = setup.game.path().join("DELTARUNE");
+    let backups = BTreeMap::from([(original.clone(), vec![newest.clone(), oldest.clone()])]);
+    let (to_restore, redundant) = choose_backups(backups);
+    assert_eq!(to_restore, vec![(oldest.clone(), original)]);
+    assert_eq!(redundant, vec![newest.clone()]);
+
+    setup.uninstall().unwrap();
+    assert_eq!(setup.read("DELTARUNE").unwrap(), b"original");
+    assert!(!oldest.exists());
+    assert!(newest.exists(), "la sauvegarde redondante est conservée, pas restaurée");
+}
+
+// Répertoire qui a l'allure d'un Deltarune complet : exécutable et data.win GameMaker plausible.
+fn write_deltarune(game: &TempDir) {
+    game.write("DELTARUNE.exe", b"MZ");
+    let mut data = b"FORM".to_vec();
+    data.resize(MIN_CHAPTER_DATA_SIZE as usize, 0);
+    game.write("chapter3_windows/data.win", &data);
+}
+
+#[test]
+fn game_check_accepts_deltarune() {
+    let game = TempDir::new("game_check_ok");
+    write_deltarune(&game);
+    game.write("steam_appid.txt", b"1671210\n");
+    assert!(check_is_deltarune(game.path(), "full").unwrap());
+}
+
+#[test]
+fn game_check_only_warns_outside_steam() {
+    let game = TempDir::new("game_check_not_steam");
+    game.write("chapter3_windows/data.win", b"FORM");
+    assert!(!check_is_deltarune(game.path(), "full").unwrap());
+}
+
+#[test]
+fn game_check_refuses_unexpected_steam_install() {
+    let game = TempDir::new("game_check_steam");
+    game.write("chapter3_windows/data.win", b"FORM");
+    game.write("steam_api.dll", b"");
+    assert!(check_is_deltarune(game.path(), "full").is_err());
+}
+
+#[test]
+fn game_check_refuses_other_steam_app() {
+    let game = TempDir::new("game_check_app_id");
+    write_deltarune(&game);
+    game.write("steam_appid.txt", b"480");
+    assert!(check_is_deltarune(game.path(), "full").is_err());
+}
+
+// Même enchaînement que l'installation pour un data.win qui est un lien symbolique.
+#[cfg(unix)]
+#[test]
+fn follows_symlinked_data_inside_game_dir() {
+    let setup = Setup::new("symlink_inside");
+    let extract = TempDir::new("symlink_inside_extract");
+    let real_path = setup.game.write("shared/data.win", b"original");
+    let link_path = setup.game.path().join("chapter1_windows/data.win");
+    fs::create_dir_all(link_path.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(&real_path, &link_path).unwrap();
+    let patch_file_path = extract.write("data.bps", &bps_patch(b"original", b"patched"));
+
+    let write_path = fsutil::resolve_link_within(setup.game.path(), &link_path).unwrap();
+    assert_eq!(write_path, fs::canonicalize(&real_path).unwrap());
+    let backup_path = backup::create_linked_backup(&link_path).unwrap();
+    assert!(!fs::symlink_metadata(&backup_path).unwrap().file_type().is_symlink());
+    assert_eq!(fs::read(&backup_path).unwrap(), b"original");
+    apply_bps(&link_path, &patch_file_path, &write_path).unwrap();
+    assert!(fs::symlink_metadata(&link_path).unwrap().file_type().is_symlink(), "le lien est conservé");
+    assert_eq!(fs::read(&real_path).unwrap(), b"patched");
+
+    setup.uninstall().unwrap();
+    assert!(fs::symlink_metadata(&link_path).unwrap().file_type().is_symlink());
+    assert_eq!(fs::read(&real_path).unwrap(), b"original");
+}
+
+#[cfg(unix)]
+#[test]
+fn refuses_symlinked_data_outside_game_dir() {
+    let game = TempDir::new("symlink_outside_game");
+    let elsewhere = TempDir::new("symlink_outside_target");
+    let real_path = elsewhere.write("data.win", b"original");
+    let link_path = game.path().join("data.win");
+    std::os::unix::fs::symlink(&real_path, &link_path).unwrap();
+
+    assert!(fsutil::resolve_link_within(game.path(), &link_path).is_err());
+    assert_eq!(fs::read(&real_path).unwrap(), b"original");
+}
+
+fn push_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte | 0x80);
+            return;
+        }
+        out.push(byte);
+        value -= 1;
+    }
+}
+
+// CRC32 de `size - 1` octets nuls suivis de `last`, calculé sans passer par un fichier.
+fn crc_of_zeros_then(size: u64, last: u8) -> u32 {
+    let zeros = vec![0u8; 1024 * 1024];
+    let mut digest = CRC32.digest();
+    let mut remaining = size - 1;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        digest.update(&zeros[..chunk]);
+        remaining -= chunk as u64;
+    }
+    digest.update(&[last]);
+    digest.finalize()
+}
+
+// Source creuse de `size` octets dont seul le dernier est non nul ; le patch recopie tout
+// sauf ce dernier octet, qu'il remplace. Une taille ou une position tronquée à 32 bits
+// ferait échouer les tailles, les CRC ou le contenu de la fin du fichier.
+fn patch_large_file(size: u64) {
+    let game = TempDir::new("large_file");
+    let source_path = game.path().join("data.win");
+    let mut source = File::create(&source_path).unwrap();
+    source.set_len(size).unwrap();
+    source.seek(SeekFrom::End(-1)).unwrap();
+    source.write_all(b"S").unwrap();
+    drop(source);
+    let source_crc = crc_of_zeros_then(size, b'S');
+    let target_crc = crc_of_zeros_then(size, b'T');
+
+    let mut patch = b"BPS1".to_vec();
+    push_varint(&mut patch, size);
+    push_varint(&mut patch, size);
+    push_varint(&mut patch, 0);
+    push_varint(&mut patch, (size - 2) << 2); // SourceRead de size - 1 octets
+    push_varint(&mut patch, 1); // TargetRead d'un octet
+    patch.push(b'T');
+    patch.extend_from_slice(&source_crc.to_le_bytes());
+    patch.extend_from_slice(&target_crc.to_le_bytes());
+    patch.extend_from_slice(&calculate_crc32(&patch).to_le_bytes());
+    let patch_path = game.write("data.bps", &patch);
+
+    assert_eq!(bps::read_bps_sizes(&patch_path).unwrap(), (size, size));
+    assert_eq!(calculate_crc32_stream(File::open(&source_path).unwrap()).unwrap(), source_crc);
+    assert!(can_apply_bps(&source_path, &patch_path).unwrap());
+
+    let target_path = game.path().join("data.win.patched");
+    apply_bps(&source_path, &patch_path, &target_path).unwrap();
+    assert_eq!(fs::metadata(&target_path).unwrap().len(), size);
+    assert_eq!(calculate_crc32_stream(File::open(&target_path).unwrap()).unwrap(), target_crc);
+}
+
+#[test]
+fn patches_large_file_without_truncation() {
+    patch_large_file((1 << 20) + 5);
+}
+
+// Fichier creux : peu de place sur le disque, mais flips applique le patch en mémoire
+// (source et cible, plus de 8 Go). Lancer avec `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn patches_file_larger_than_4_gib() {
+    patch_large_file((4 << 30) + 16);
+}
+
+#[test]
+fn uninstall_restores_overwritten_extra_like_patched_file() {
+    let setup = Setup::new("extra_restore");
+    let extract = TempDir::new("extra_restore_extract");
+    let data_path = setup.game.write("chapter1_windows/data.win", b"original data");
+    setup.game.write("chapter1_windows/lang/en.json", b"original lang");
+    let patch_file_path = extract.write("chapter1_windows/data.win.bps", &bps_patch(b"original data", b"patched data"));
+    extract.write("chapter1_windows/lang/en.json", b"lang traduit");
+
+    backup::create_linked_backup(&data_path).unwrap();
+    apply_bps(&data_path, &patch_file_path, &data_path).unwrap();
+    let entries = copy_extra_files(extract.path(), setup.game.path(), &Selection::default(), &mut Progress::new(ProgressMode::None)).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].backup, Some(PathBuf::from("chapter1_windows/lang/en.json.bak")));
+    assert!(!entries[0].created);
+    assert_eq!(setup.read("chapter1_windows/lang/en.json").unwrap(), b"lang traduit");
+
+    setup.uninstall().unwrap();
+    for (relative_path, original) in [("chapter1_windows/data.win", &b"original data"[..]), ("chapter1_windows/lang/en.json", b"original lang")] {
+        assert_eq!(setup.read(relative_path).unwrap(), original);
+        assert!(setup.read(&format!("{}.bak", relative_path)).is_none());
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+// reference.bps vient d'un autre encodeur que flips et emploie les quatre actions BPS, dont une
+// copie de la cible qui recouvre sa propre sortie ; son CRC cible est aussi noté à part.
+#[test]
+fn applies_reference_patch() {
+    let out = TempDir::new("reference_patch");
+    let source_path = fixture("reference_source.bin");
+    let patch_path = fixture("reference.bps");
+    let target_path = out.path().join("reference_target.bin");
+    let expected_crc = u32::from_str_radix(fs::read_to_string(fixture("reference_target.crc32")).unwrap().trim(), 16).unwrap();
+
+    assert!(can_apply_bps(&source_path, &patch_path).unwrap());
+    apply_bps(&source_path, &patch_path, &target_path).unwrap();
+    assert_eq!(calculate_crc32(&fs::read(&target_path).unwrap()), expected_crc);
+    assert_eq!(bps::read_bps_footer(&patch_path).unwrap().target_crc, expected_crc);
+}
+
+fn patched_entry(relative_path: &str) -> ManifestEntry {
+    ManifestEntry {
+        path: PathBuf::from(relative_path),
+        backup: Some(PathBuf::from(format!("{}.bak", relative_path))),
+        ..Default::default()
+    }
+}
+
+// Les quatre états possibles d'un fichier patché au moment de la désinstallation.
+#[test]
+fn uninstall_handles_every_backup_layout() {
+    let setup = Setup::new("backup_layouts");
+    let game_dir = setup.game.path();
+    setup.game.write("both.win", b"patched");
+    setup.game.write("both.win.bak", b"original both");
+    setup.game.write("backup_only.win.bak", b"original backup_only");
+    setup.game.write("original_only.win", b"patched");
+    let entries: Vec<ManifestEntry> = ["both.win", "backup_only.win", "original_only.win", "neither.win"].into_iter().map(patched_entry).collect();
+
+    let backups = BTreeMap::from([
+        (game_dir.join("both.win"), vec![game_dir.join("both.win.bak")]),
+        (game_dir.join("backup_only.win"), vec![game_dir.join("backup_only.win.bak")]),
+    ]);
+    let (to_restore, redundant) = choose_backups(backups);
+    assert!(redundant.is_empty());
+    assert_eq!(
+        unrecoverable_files(game_dir, &entries, &[], &to_restore),
+        vec![game_dir.join("neither.win"), game_dir.join("original_only.win")]
+    );
+
+    setup.uninstall().unwrap();
+    assert_eq!(setup.read("both.win").unwrap(), b"original both");
+    assert_eq!(setup.read("backup_only.win").unwrap(), b"original backup_only");
+    assert_eq!(setup.read("original_only.win").unwrap(), b"patched", "sans sauvegarde, le fichier est laissé tel quel");
+    assert!(setup.read("neither.win").is_none());
+    assert!(setup.read("both.win.bak").is_none() && setup.read("backup_only.win.bak").is_none());
+}
+
+#[test]
+fn uninstall_ignores_unreadable_history() {
+    let setup = Setup::new("garbage_history");
+    setup.game.write("chapter1_windows/data.win", b"patched");
+    setup.game.write("chapter1_windows/data.win.bak", b"original");
+    setup.game.write("DELTARUNE.exe.bak", b"MZ original");
+    setup.state.write("history/1700000000000.json", b"\x00{ pas du json");
+
+    setup.uninstall().unwrap();
+    assert_eq!(setup.read("chapter1_windows/data.win").unwrap(), b"original");
+    assert_eq!(setup.read("DELTARUNE.exe").unwrap(), b"MZ original");
+    assert!(setup.read("chapter1_windows/data.win.bak").is_none());
+}
+
+// État laissé par une désinstallation interrompue : chapitre 1 restauré et inscrit au journal,
+// chapitre 2 arrêté entre la suppression du fichier patché et le renommage de sa sauvegarde,
+// chapitre 3 pas encore traité.
+#[test]
+fn uninstall_resumes_after_interruption() {
+    let setup = Setup::new("interrupted_uninstall");
+    let game_dir = setup.game.path();
+    setup.game.write("chapter1_windows/data.win", b"original 1");
+    setup.game.write("chapter2_windows/data.win.bak", b"original 2");
+    setup.game.write("chapter3_windows/data.win", b"patched 3");
+    setup.game.write("chapter3_windows/data.win.bak", b"original 3");
+    let journal = vec![PathBuf::from("chapter1_windows/data.win")];
+    manifest::save_uninstall_journal(setup.state.path(), game_dir, &journal).unwrap();
+
+    let entries: Vec<ManifestEntry> = (1..=3).map(|chapter| patched_entry(&format!("chapter{}_windows/data.win", chapter))).collect();
+    let backups = BTreeMap::from([2, 3].map(|chapter| {
+        let original = game_dir.join(format!("chapter{}_windows/data.win", chapter));
+        let backup = game_dir.join(format!("chapter{}_windows/data.win.bak", chapter));
+        (original, vec![backup])
+    }));
+    let (to_restore, _) = choose_backups(backups);
+    let restored_paths = manifest::load_uninstall_journal(setup.state.path(), game_dir);
+    assert_eq!(restored_paths, journal);
+    assert!(unrecoverable_files(game_dir, &entries, &restored_paths, &to_restore).is_empty(), "le fichier déjà restauré n'a plus besoin de sauvegarde");
+
+    setup.uninstall().unwrap();
+    for chapter in 1..=3 {
+        let relative_path = format!("chapter{}_windows/data.win", chapter);
+        assert_eq!(setup.read(&relative_path).unwrap(), format!("original {}", chapter).as_bytes());
+        assert!(setup.read(&format!("{}.bak", relative_path)).is_none());
+    }
+    assert!(manifest::load_uninstall_journal(setup.state.path(), game_dir).is_empty());
+}
+
+fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+    for (name, data) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, name, *data).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+// Archive tronquée au milieu du téléchargement, ou remplacée par autre chose (page d'erreur d'un
+// miroir), alors qu'une extraction complète d'une installation précédente est en cache.
+fn check_broken_archive_is_not_cached(damage: impl Fn(&mut Vec<u8>)) {
+    let download = TempDir::new("broken_archive");
+    let game = TempDir::new("broken_archive_game");
+    download.write("patch_files/chapter1_windows/data.win.bps", b"ancien patch");
+    download.write(EXTRACTION_COMPLETE_MARKER, b"");
+    let big_extra = vec![b'x'; 64 * 1024];
+    let mut archive = tar_gz(&[
+        ("chapter1_windows/data.win.bps", b"nouveau patch"),
+        ("chapter2_windows/data.win.bps", b"nouveau patch"),
+        ("lang/fr.json", &big_extra),
+    ]);
+    damage(&mut archive);
+    let archive_path = download.write("patch.tar.gz", &archive);
+    let details = ["chapter1_windows/data.win.bps", "chapter2_windows/data.win.bps"]
+        .map(|patch_path| PatchDetail { patch_path: patch_path.to_string(), ..Default::default() });
+    let patchs: Vec<&PatchDetail> = details.iter().collect();
+    let options = InstallOptions::defaults(true);
+
+    let result = extract_patch_archive(
+        &archive_path,
+        archive::ArchiveFormat::TarGz,
+        download.path(),
+        game.path(),
+        &patchs,
+        &options.selection,
+        &options,
+        &Deadline::new(None),
+        &mut Progress::new(ProgressMode::None),
+    );
+    assert!(result.is_err());
+    assert!(!download.path().join(EXTRACTION_COMPLETE_MARKER).exists());
+    assert!(cached_extract_dir(download.path(), &patchs, false).unwrap().is_none());
+    assert!(cached_extract_dir(download.path(), &patchs, true).is_err());
+}
+
+#[test]
+fn truncated_archive_is_not_reused_by_cache() {
+    check_broken_archive_is_not_cached(|archive| archive.truncate(archive.len() / 2));
+}
+
+#[test]
+fn corrupted_archive_is_not_reused_by_cache() {
+    check_broken_archive_is_not_cached(|archive| archive[..4].copy_from_slice(b"<htm"));
+}
+
+#[test]
+fn missing_source_is_skipped_silently() {
+    let game = TempDir::new("missing_skip");
+    assert!(handle_missing_source(&game.path().join("chapter5_windows/data.win"), MissingSource::Skip).is_ok());
+}
+
+#[test]
+fn missing_source_only_warns() {
+    let game = TempDir::new("missing_warn");
+    assert!(handle_missing_source(&game.path().join("chapter5_windows/data.win"), MissingSource::Warn).is_ok());
+}
+
+#[test]
+fn missing_source_stops_installation() {
+    let game = TempDir::new("missing_fail");
+    let source_file_path = game.path().join("chapter5_windows/data.win");
+    let error = handle_missing_source(&source_file_path, MissingSource::Fail).unwrap_err();
+    assert!(error.to_string().contains(&format!("{:?}", source_file_path)));
+}
@@ -0,0 +1,45 @@
+//! Outils partagés par les tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Répertoire temporaire propre à un test, supprimé à la fin du test.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("patcher_drfr_test_{}_{}_{}", name, std::process::id(), id));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("création du répertoire de test");
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Crée `relative_path` (et ses répertoires parents) avec ce contenu, et renvoie son chemin.
+    pub fn write(&self, relative_path: &str, contents: &[u8]) -> PathBuf {
+        let path = self.path.join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).expect("création des répertoires parents");
+        fs::write(&path, contents).expect("écriture du fichier de test");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Patch BPS qui transforme `source` en `target`.
+pub fn bps_patch(source: &[u8], target: &[u8]) -> Vec<u8> {
+    flips::BpsDeltaBuilder::new().source(source).target(target).build().expect("création du patch BPS").as_ref().to_vec()
+}
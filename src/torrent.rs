@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::calculate_crc32_stream;
+use crate::deadline::Deadline;
+use crate::messages;
+
+/// Client BitTorrent externe utilisé : aria2c gère les liens magnet et les fichiers .torrent.
+const CLIENT: &str = "aria2c";
+
+/// Télécharge l'archive du patch via BitTorrent (lien magnet ou URL d'un .torrent) en lançant
+/// aria2c, puis attend la fin du téléchargement. Le fichier est écrit dans `download_dir/file_name`
+/// et son CRC32 est renvoyé, pour être vérifié comme après un téléchargement HTTP.
+pub fn download(torrent: &str, download_dir: &Path, file_name: &str, deadline: &Deadline) -> Result<u32, Box<dyn Error>> {
+    println!("{}", messages::torrent_start(CLIENT, torrent));
+    let output_path = download_dir.join(file_name);
+    let _ = fs::remove_file(&output_path);
+
+    let mut command = Command::new(CLIENT);
+    command
+        .arg("--dir")
+        .arg(download_dir)
+        // Le patch ne contient qu'un fichier : on lui impose le nom attendu par la suite de l'installation.
+        .arg(format!("--index-out=1={}", file_name))
+        .arg("--seed-time=0")
+        .arg("--follow-torrent=mem")
+        .arg("--bt-save-metadata=false")
+        .arg("--allow-overwrite=true")
+        .arg("--console-log-level=warn")
+        .arg("--summary-interval=0");
+    if let Some(remaining) = deadline.remaining() {
+        command.arg(format!("--stop={}", remaining.as_secs().max(1)));
+    }
+    command.arg(torrent);
+
+    let status = command.status().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => messages::torrent_client_missing(CLIENT),
+        _ => messages::torrent_client_failed_to_start(CLIENT, &e),
+    })?;
+    deadline.check()?;
+    if !status.success() {
+        return Err(messages::torrent_client_failed(CLIENT, &status).into());
+    }
+    if !output_path.is_file() {
+        return Err(messages::torrent_no_output(CLIENT, &output_path).into());
+    }
+
+    println!("{}", messages::torrent_done());
+    // Accès en écriture : Windows le demande pour synchroniser un fichier (--fsync).
+    let file = fs::OpenOptions::new().read(true).write(true).open(&output_path)?;
+    crate::fsutil::sync_if_durable(&file)?;
+    Ok(calculate_crc32_stream(file)?)
+}
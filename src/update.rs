@@ -0,0 +1,75 @@
+use std::error::Error;
+use std::path::Path;
+
+use crate::manifest::{self, InstallManifest};
+use crate::versions::{self, PatchVersion};
+use crate::{fetch_patch_index, index_url, install_target, lock, messages, platform_info_for, process, run_uninstall_process, versions_url, InstallOptions, Target};
+
+/// Vrai si l'installation enregistrée correspond à la dernière version publiée. Une installation
+/// faite sans --patch-version n'a pas de numéro de version : les CRC32 qu'elle a laissés sont alors
+/// comparés aux CRC32 cibles de l'index courant.
+fn is_up_to_date(install_manifest: &InstallManifest, latest: &PatchVersion) -> Result<bool, Box<dyn Error>> {
+    if let Some(installed) = &install_manifest.patch_version {
+        return Ok(*installed == latest.version);
+    }
+    let patch_index = fetch_patch_index(index_url())?;
+    let platform_info = platform_info_for(&patch_index, &install_manifest.platform)?;
+    let mut patched = install_manifest
+        .files
+        .iter()
+        .filter(|entry| platform_info.patchs.iter().any(|detail| entry.path == Path::new(&detail.source_path)))
+        .peekable();
+    if patched.peek().is_none() {
+        return Ok(false);
+    }
+    Ok(patched.all(|entry| {
+        platform_info
+            .patchs
+            .iter()
+            .any(|detail| entry.path == Path::new(&detail.source_path) && entry.crc.is_some() && detail.target_crc == entry.crc)
+    }))
+}
+
+/// Met à jour le patch s'il existe une version plus récente que celle installée : les fichiers
+/// d'origine sont restaurés, puis la nouvelle version est installée. Ne fait rien si le patch est à jour.
+pub fn run_update(target: &Target, state_dir: &Path, options: &InstallOptions) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::update_header());
+    let Some((_, install_manifest)) = manifest::latest_manifest(&target.state_dir, &target.game_dir)? else {
+        return Err(messages::update_not_installed().into());
+    };
+    let Some(latest) = versions::fetch_versions(&versions_url())?.pop() else {
+        return Err(messages::no_version_published().into());
+    };
+    let installed = install_manifest.patch_version.clone().unwrap_or_else(messages::version_unspecified);
+
+    if is_up_to_date(&install_manifest, &latest)? {
+        println!("{}", messages::update_up_to_date(&latest.version));
+        return Ok(());
+    }
+    println!("{}", messages::update_available(&latest.version, &installed));
+    if !latest.notes.is_empty() {
+        println!("{}", latest.notes);
+    }
+
+    let running = process::find_running_game(&target.game_dir);
+    if !running.is_empty() {
+        return Err(messages::game_running(&process::describe(&running)).into());
+    }
+
+    println!("{}", messages::update_restoring());
+    {
+        let _lock = lock::lock_game_dir(state_dir, &target.game_dir)?;
+        run_uninstall_process(&target.game_dir, &target.state_dir, false, options.assume_yes)?;
+    }
+
+    // La version installée est enregistrée pour que la prochaine mise à jour la compare directement.
+    let options = InstallOptions {
+        patch_version: Some(latest.version.clone()),
+        platform: Some(install_manifest.platform.clone()),
+        ..options.clone()
+    };
+    let target = Target { platform: Some(install_manifest.platform), ..target.clone() };
+    install_target(&target, state_dir, &options, false).map_err(|e| {
+        messages::update_install_failed(&e).into()
+    })
+}
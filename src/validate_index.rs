@@ -0,0 +1,84 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::{archive, bps, messages, platform_info_for, PatchDetail, PatchIndex, LOCAL_INDEX_NAME};
+
+/// Compare les CRC32 déclarés par l'index pour chaque patch à ceux du footer de son `.bps`,
+/// qui font foi. Renvoie une ligne par divergence ; un patch sans CRC dans l'index n'est pas comparé.
+/// Chemins de l'index (patchs et fichiers d'origine) qui ne pourraient pas être créés sous Windows.
+fn windows_name_problems<'a>(patchs: impl IntoIterator<Item = &'a PatchDetail>) -> Vec<String> {
+    patchs
+        .into_iter()
+        .flat_map(|detail| [&detail.patch_path, &detail.source_path])
+        .filter_map(|path| {
+            let problem = archive::windows_name_problem(Path::new(path))?;
+            Some(messages::index_windows_name(path, &problem, &archive::windows_safe_path(Path::new(path)).display()))
+        })
+        .collect()
+}
+
+pub fn footer_mismatches<'a>(patches_dir: &Path, patchs: impl IntoIterator<Item = &'a PatchDetail>) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    for detail in patchs {
+        let footer = match bps::read_bps_footer(&patches_dir.join(&detail.patch_path)) {
+            Ok(footer) => footer,
+            Err(e) => {
+                mismatches.push(messages::index_footer_unreadable(&detail.patch_path, &e));
+                continue;
+            }
+        };
+        let checks = [
+            (messages::crc_label_source(), detail.source_crc, footer.source_crc),
+            (messages::crc_label_target(), detail.target_crc, footer.target_crc),
+        ];
+        for (label, declared, actual) in checks {
+            if let Some(declared) = declared
+                && declared != actual
+            {
+                mismatches.push(messages::index_footer_crc_mismatch(&detail.patch_path, &label, declared, actual));
+            }
+        }
+    }
+    mismatches
+}
+
+/// Vérifie un index avant publication : pour chaque plateforme (ou seulement `platform`), les CRC32
+/// source et cible déclarés doivent correspondre au footer des patchs de `patches_dir`.
+pub fn run_validate_index(patches_dir: &Path, index_path: Option<&Path>, platform: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let index_path = index_path.map_or_else(|| patches_dir.join(LOCAL_INDEX_NAME), Path::to_path_buf);
+    let data = fs::read(&index_path)
+        .map_err(|e| messages::index_read_failed(&index_path, &e))?;
+    let patch_index: PatchIndex = serde_json::from_slice(&data)
+        .map_err(|e| messages::index_invalid(&index_path, &e))?;
+
+    let mut platforms: Vec<&str> = match platform {
+        Some(platform) => {
+            platform_info_for(&patch_index, platform)?;
+            vec![platform]
+        }
+        None => patch_index.keys().map(String::as_str).collect(),
+    };
+    platforms.sort();
+
+    let mut total = 0;
+    for platform in platforms {
+        let platform_info = &patch_index[platform];
+        let mut mismatches = footer_mismatches(patches_dir, &platform_info.patchs);
+        mismatches.extend(windows_name_problems(&platform_info.patchs));
+        if mismatches.is_empty() {
+            println!("{}", messages::index_platform_ok(platform, platform_info.patchs.len()));
+            continue;
+        }
+        eprintln!("{}", messages::index_platform_problems(platform, mismatches.len()));
+        for mismatch in &mismatches {
+            eprintln!("  {}", mismatch);
+        }
+        total += mismatches.len();
+    }
+
+    if total > 0 {
+        return Err(messages::index_problems(total, &index_path).into());
+    }
+    Ok(())
+}
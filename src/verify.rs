@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use crate::{calculate_crc32_stream, fetch_patch_index, manifest, messages, platform_info_for, select_platform, PatchDetail, PatchIndex};
+
+/// Vérifie l'intégrité d'une installation contre l'index : chaque fichier patché doit avoir le
+/// CRC32 cible de son patch. Contrairement à audit, qui compare au manifeste local, la référence
+/// est celle publiée par l'équipe : la vérification fonctionne aussi sans historique d'installation.
+pub fn run_verify(game_dir: &Path, state_dir: &Path, platform: Option<&str>, index_url: &str) -> Result<(), Box<dyn Error>> {
+    let patch_index = fetch_patch_index(index_url)?;
+    verify_against_index(game_dir, state_dir, platform, &patch_index)
+}
+
+// Ne fait que lire le répertoire du jeu : fonctionne sur un jeu monté en lecture seule.
+fn verify_against_index(game_dir: &Path, state_dir: &Path, platform: Option<&str>, patch_index: &PatchIndex) -> Result<(), Box<dyn Error>> {
+    let latest = manifest::latest_manifest(state_dir, game_dir)?.map(|(_, install_manifest)| install_manifest);
+    let platform_key = match (platform, &latest) {
+        (Some(platform), _) => platform.to_string(),
+        (None, Some(install_manifest)) => install_manifest.platform.clone(),
+        (None, None) => select_platform(game_dir),
+    };
+    let platform_info = platform_info_for(patch_index, &platform_key)?;
+
+    // Un même fichier peut avoir plusieurs patchs (un par version d'origine) : il est sain s'il a
+    // le CRC32 cible de l'un d'eux. Avec un manifeste, seuls les fichiers qu'il a patchés sont
+    // attendus (un chapitre non installé n'est pas une erreur).
+    let mut expected: BTreeMap<&str, Vec<&PatchDetail>> = BTreeMap::new();
+    for detail in &platform_info.patchs {
+        let installed = latest
+            .as_ref()
+            .is_none_or(|install_manifest| install_manifest.files.iter().any(|entry| entry.path == Path::new(&detail.source_path)));
+        if installed {
+            expected.entry(detail.source_path.as_str()).or_default().push(detail);
+        }
+    }
+    if expected.is_empty() {
+        return Err(messages::verify_nothing(&platform_key).into());
+    }
+
+    println!("{}", messages::verify_header(&platform_key));
+    let (mut ok, mut unpatched, mut corrupted, mut missing, mut unknown) = (0, 0, 0, 0, 0);
+    for (source_path, details) in &expected {
+        let path = game_dir.join(source_path);
+        let Ok(file) = File::open(&path) else {
+            eprintln!("{}", messages::verify_missing(source_path));
+            missing += 1;
+            continue;
+        };
+        let crc = calculate_crc32_stream(file).map_err(|e| messages::read_error(&path, &e))?;
+        if details.iter().all(|detail| detail.target_crc.is_none()) {
+            println!("{}", messages::verify_no_target_crc(source_path));
+            unknown += 1;
+        } else if details.iter().any(|detail| detail.target_crc == Some(crc)) {
+            println!("{}", messages::verify_ok(source_path));
+            ok += 1;
+        } else if details.iter().any(|detail| detail.source_crc == Some(crc)) {
+            eprintln!("{}", messages::verify_unpatched(source_path, crc));
+            unpatched += 1;
+        } else {
+            eprintln!("{}", messages::verify_corrupted(source_path, crc));
+            corrupted += 1;
+        }
+    }
+
+    println!("{}", messages::verify_summary(ok, unpatched, corrupted, missing, unknown));
+    if unpatched + missing > 0 {
+        println!("{}", messages::verify_unpatched_advice());
+    }
+    if corrupted > 0 {
+        println!("{}", messages::verify_corrupted_advice());
+    }
+    if unpatched + corrupted + missing > 0 {
+        return Err(messages::verify_failed(unpatched + corrupted + missing).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempDir;
+    use crate::{calculate_crc32, PlatformInfo};
+
+    fn index_for(source_path: &str, original: &[u8], patched: &[u8]) -> PatchIndex {
+        let detail = PatchDetail {
+            source_path: source_path.to_string(),
+            source_crc: Some(calculate_crc32(original)),
+            target_crc: Some(calculate_crc32(patched)),
+            ..Default::default()
+        };
+        PatchIndex::from([("full".to_string(), PlatformInfo { patchs: vec![detail], ..Default::default() })])
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_works_on_read_only_game_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let game = TempDir::new("verify_read_only_game");
+        let state = TempDir::new("verify_read_only_state");
+        let data_path = game.write("chapter1_windows/data.win", b"patched");
+        let patch_index = index_for("chapter1_windows/data.win", b"original", b"patched");
+
+        let set_mode = |mode| {
+            for path in [data_path.as_path(), data_path.parent().unwrap(), game.path()] {
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+            }
+        };
+        set_mode(0o555);
+        let patched = verify_against_index(game.path(), state.path(), Some("full"), &patch_index);
+        let unpatched = verify_against_index(game.path(), state.path(), Some("full"), &index_for("chapter1_windows/data.win", b"patched", b"other"));
+        let entries: Vec<_> = std::fs::read_dir(game.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        set_mode(0o755);
+
+        assert!(patched.is_ok(), "{:?}", patched.err().map(|e| e.to_string()));
+        assert!(unpatched.is_err());
+        assert_eq!(entries, vec![std::ffi::OsString::from("chapter1_windows")], "verify ne doit rien écrire dans le jeu");
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use crate::{backup, backup_path_for, fsutil, calculate_crc32_stream, fetch_patch_index, index_url, messages, platform_info_for, select_platform};
+
+/// CRC32 d'une sauvegarde, compressée ou non, calculé en flux.
+pub fn backup_crc(backup_path: &Path) -> Result<u32, Box<dyn Error>> {
+    let file = File::open(backup_path)?;
+    if backup::is_compressed(backup_path) {
+        let decoder = zstd::stream::read::Decoder::new(file)?;
+        Ok(calculate_crc32_stream(decoder)
+            .map_err(|e| messages::backup_unreadable(backup_path, &e))?)
+    } else {
+        Ok(calculate_crc32_stream(file)?)
+    }
+}
+
+// Recrée la sauvegarde à partir du fichier du jeu, quand celui-ci est encore d'origine.
+fn repair_backup(source_path: &Path, backup_path: &Path) -> Result<(), Box<dyn Error>> {
+    if backup::is_compressed(backup_path) {
+        backup::create_compressed_backup(source_path)?;
+    } else {
+        fsutil::copy_file(source_path, backup_path)?;
+    }
+    Ok(())
+}
+
+/// Vérifie que chaque sauvegarde correspond bien à la version originale du fichier,
+/// d'après les CRC32 sources de l'index. Avec `repair`, une sauvegarde invalide est
+/// recréée si le fichier du jeu est lui-même encore d'origine.
+pub fn run_verify_backups(game_dir: &Path, platform: Option<&str>, repair: bool) -> Result<(), Box<dyn Error>> {
+    let patch_index = fetch_patch_index(index_url())?;
+    let platform_key = match platform {
+        Some(platform) => platform.to_string(),
+        None => select_platform(game_dir),
+    };
+    let platform_info = platform_info_for(&patch_index, &platform_key)?;
+
+    // Un même fichier peut avoir plusieurs versions d'origine (un patch par version du jeu).
+    let mut source_crcs: HashMap<&str, HashSet<u32>> = HashMap::new();
+    let mut target_crcs: HashMap<&str, HashSet<u32>> = HashMap::new();
+    for detail in &platform_info.patchs {
+        if let Some(crc) = detail.source_crc {
+            source_crcs.entry(&detail.source_path).or_default().insert(crc);
+        }
+        if let Some(crc) = detail.target_crc {
+            target_crcs.entry(&detail.source_path).or_default().insert(crc);
+        }
+    }
+
+    println!("{}", messages::verify_backups_header());
+    let mut checked = 0;
+    let mut problems = 0;
+    let mut seen = HashSet::new();
+    let source_paths = platform_info.patchs.iter().map(|d| d.source_path.as_str()).filter(|path| seen.insert(*path));
+    for source_path in source_paths {
+        let Some(expected) = source_crcs.get(source_path) else {
+            println!("{}", messages::verify_backups_no_crc(source_path));
+            continue;
+        };
+
+        let original_path = game_dir.join(source_path);
+        let candidates = [backup::compressed_backup_path_for(&original_path), backup_path_for(&original_path)];
+        let backups: Vec<_> = candidates.iter().filter(|path| path.is_file()).collect();
+        if backups.is_empty() {
+            println!("{}", messages::verify_backups_none(source_path));
+            continue;
+        }
+
+        for backup_path in backups {
+            checked += 1;
+            let problem = match backup_crc(backup_path) {
+                Ok(crc) if expected.contains(&crc) => {
+                    println!("{}", messages::verify_backups_ok(backup_path, crc));
+                    continue;
+                }
+                Ok(crc) if target_crcs.get(source_path).is_some_and(|targets| targets.contains(&crc)) => {
+                    messages::verify_backups_patched(backup_path, crc)
+                }
+                Ok(crc) => messages::verify_backups_corrupted(backup_path, crc),
+                Err(e) => messages::verify_backups_unreadable(backup_path, &e),
+            };
+            eprintln!("{}", messages::error_line(&problem));
+
+            let original_is_intact = File::open(&original_path)
+                .ok()
+                .and_then(|file| calculate_crc32_stream(file).ok())
+                .is_some_and(|crc| expected.contains(&crc));
+            if repair && original_is_intact {
+                repair_backup(&original_path, backup_path)?;
+                println!("{}", messages::verify_backups_repaired(backup_path, &original_path));
+            } else {
+                if original_is_intact {
+                    println!("{}", messages::verify_backups_repair_hint(&original_path));
+                } else {
+                    eprintln!("{}", messages::verify_backups_original_altered(&original_path));
+                }
+                problems += 1;
+            }
+        }
+    }
+
+    println!("{}", messages::verify_backups_summary(checked, problems));
+    if problems > 0 {
+        return Err(messages::verify_backups_failed(problems).into());
+    }
+    Ok(())
+}
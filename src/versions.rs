@@ -0,0 +1,65 @@
+use std::error::Error;
+use serde::Deserialize;
+
+use crate::{messages, net};
+
+/// Version publiée du patch, telle que listée par l'historique du serveur.
+#[derive(Deserialize, Debug)]
+pub struct PatchVersion {
+    pub version: String,
+
+    /// Date de publication, telle que fournie par le serveur (ex. 2025-06-04).
+    #[serde(default)]
+    pub date: String,
+
+    #[serde(default)]
+    pub notes: String,
+
+    /// Index des patchs de cette version, au même format que l'index courant.
+    #[serde(rename = "indexUrl")]
+    pub index_url: String,
+}
+
+/// Télécharge l'historique des versions, de la plus ancienne à la plus récente.
+pub fn fetch_versions(url: &str) -> Result<Vec<PatchVersion>, Box<dyn Error>> {
+    println!("{}", messages::fetching_versions(url));
+    let versions: Vec<PatchVersion> = net::get_json(url)?;
+    Ok(versions)
+}
+
+/// URL de l'index d'une version précise, avec la liste des versions disponibles si elle n'existe pas.
+pub fn index_url_for(url: &str, version: &str) -> Result<String, Box<dyn Error>> {
+    let versions = fetch_versions(url)?;
+    match versions.iter().find(|v| v.version == version) {
+        Some(found) => Ok(found.index_url.clone()),
+        None => {
+            let available: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+            let available = if available.is_empty() { messages::versions_none() } else { available.join(", ") };
+            Err(messages::version_not_found(version, &available).into())
+        }
+    }
+}
+
+pub fn run_versions(url: &str) -> Result<(), Box<dyn Error>> {
+    let versions = fetch_versions(url)?;
+    if versions.is_empty() {
+        println!("{}", messages::no_versions_published());
+        return Ok(());
+    }
+
+    println!("{}", messages::versions_header());
+    let latest = versions.len() - 1;
+    for (i, version) in versions.iter().enumerate().rev() {
+        let marker = if i == latest { messages::version_latest_marker() } else { String::new() };
+        if version.date.is_empty() {
+            println!("\n{}{}", version.version, marker);
+        } else {
+            println!("\n{} — {}{}", version.version, version.date, marker);
+        }
+        for line in version.notes.lines() {
+            println!("    {}", line);
+        }
+    }
+    println!("{}", messages::versions_install_hint());
+    Ok(())
+}
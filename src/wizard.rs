@@ -0,0 +1,106 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::{check_game_dir, config, install_target, lock, manifest, messages, output, run_uninstall_process, InstallOptions, Target};
+
+fn ask(question: &str) -> Result<String, Box<dyn Error>> {
+    output::prompt(format_args!("{} ", question))?;
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer)? == 0 {
+        return Err(messages::wizard_input_closed().into());
+    }
+    Ok(answer.trim().to_string())
+}
+
+// Mêmes fichiers que ceux qui servent à reconnaître la version du jeu (voir select_platform).
+fn looks_like_game(dir: &Path) -> bool {
+    dir.join("chapter3_windows/data.win").is_file() || dir.join("chapter2_windows/data.win").is_file()
+}
+
+/// Répertoire du jeu proposé d'office : celui du patcher, s'il a été posé dans le dossier du jeu.
+fn suggested_game_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    looks_like_game(&exe_dir).then_some(exe_dir)
+}
+
+/// Demande le répertoire du jeu jusqu'à en obtenir un valide. Les guillemets ajoutés par un
+/// glisser-déposer dans le terminal sont retirés ; les alias de la configuration (@nom) sont acceptés.
+fn ask_game_dir(config: &config::Config) -> Result<PathBuf, Box<dyn Error>> {
+    let suggested = suggested_game_dir();
+    loop {
+        let question = match &suggested {
+            Some(dir) => messages::wizard_game_dir_suggested(dir),
+            None => messages::wizard_game_dir_question(),
+        };
+        let answer = ask(&question)?;
+        let answer = answer.trim_matches(|c| c == '"' || c == '\'');
+        let game_dir = match (&suggested, answer.is_empty()) {
+            (Some(dir), true) => dir.clone(),
+            (None, true) => continue,
+            (_, false) => match config.resolve_game_dir(PathBuf::from(answer)) {
+                Ok(dir) => dir,
+                Err(e) => {
+                    eprintln!("{}", messages::error_line(&e));
+                    continue;
+                }
+            },
+        };
+        if let Err(e) = check_game_dir(&game_dir) {
+            eprintln!("{}", messages::error_line(&e));
+            continue;
+        }
+        if !looks_like_game(&game_dir) {
+            eprintln!("{}", messages::wizard_not_game(&game_dir));
+            continue;
+        }
+        return Ok(game_dir);
+    }
+}
+
+/// Assistant pour qui lance le patcher sans argument (double-clic) : demande le répertoire du jeu,
+/// indique si le patch y est déjà installé, puis propose d'installer ou de désinstaller. L'installation
+/// affiche son plan et demande confirmation avant de modifier quoi que ce soit (comme --preview),
+/// la désinstallation liste les fichiers à restaurer avant de demander confirmation.
+pub fn run_wizard(state_dir: &Path, config: &config::Config) -> Result<(), Box<dyn Error>> {
+    println!("{}", messages::wizard_title());
+    println!("{}", messages::wizard_intro());
+
+    let game_dir = ask_game_dir(config)?;
+    let installed = manifest::latest_manifest(state_dir, &game_dir)?;
+    match &installed {
+        Some((_, install_manifest)) => println!("{}", messages::wizard_installed(&install_manifest.platform)),
+        None => println!("{}", messages::wizard_not_installed()),
+    }
+
+    println!("{}", messages::wizard_menu());
+    println!("  1. {}", if installed.is_some() { messages::wizard_reinstall() } else { messages::wizard_install() });
+    println!("  2. {}", messages::wizard_uninstall());
+    println!("  3. {}", messages::wizard_quit());
+    let target = Target { game_dir, state_dir: state_dir.to_path_buf(), platform: None };
+    loop {
+        match ask(&messages::wizard_choice())?.as_str() {
+            "1" => {
+                println!("{}", messages::wizard_install_notice());
+                let options = InstallOptions { preview: true, ..InstallOptions::defaults(false) };
+                return install_target(&target, state_dir, &options, false);
+            }
+            "2" => {
+                // La désinstallation liste les fichiers à restaurer et demande confirmation.
+                let _lock = lock::lock_game_dir(state_dir, &target.game_dir)?;
+                return run_uninstall_process(&target.game_dir, state_dir, false, false);
+            }
+            "3" | "q" => {
+                println!("{}", messages::wizard_nothing_done());
+                std::process::exit(0);
+            }
+            _ => println!("{}", messages::wizard_invalid_choice()),
+        }
+    }
+}
+
+/// Laisse la fenêtre ouverte jusqu'à ce que l'utilisateur la ferme : lancé par un double-clic,
+/// le terminal disparaîtrait sinon avant qu'il ait pu lire le résultat.
+pub fn wait_before_exit() {
+    let _ = output::prompt(format_args!("{}", messages::wizard_press_enter()));
+    let _ = std::io::stdin().read_line(&mut String::new());
+}